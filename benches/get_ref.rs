@@ -0,0 +1,91 @@
+//! Compares the allocations and runtime of [`ProductConfigManager::get`] and
+//! [`ProductConfigManager::get_ref`] for callers that keep their user config, e.g. in a
+//! reconcile loop. Such callers have to clone the user config for every `get` call.
+//!
+//! Run via `cargo bench --bench get_ref`.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::collections::HashMap;
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use product_config::types::PropertyNameKind;
+use product_config::ProductConfigManager;
+
+const ITERATIONS: usize = 10_000;
+
+/// Counts all allocations of the process.
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Runs the function `ITERATIONS` times and returns the allocations per call and the elapsed
+/// time per call.
+fn measure<F: FnMut()>(mut f: F) -> (usize, Duration) {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        f();
+    }
+    let elapsed = start.elapsed();
+    (
+        (ALLOCATIONS.load(Ordering::Relaxed) - allocations) / ITERATIONS,
+        elapsed / ITERATIONS as u32,
+    )
+}
+
+fn main() {
+    let manager = ProductConfigManager::from_yaml_file("data/test_yamls/validate.yaml")
+        .expect("product config must be loadable");
+    let kind = PropertyNameKind::File("env.sh".to_string());
+
+    let user_config: HashMap<String, Option<String>> = [
+        ("ENV_INTEGER_PORT_MIN_MAX", "12345"),
+        ("ENV_PROPERTY_STRING_MEMORY", "1g"),
+        ("ENV_FLOAT", "55.555"),
+        ("ENV_UNKNOWN", "unknown"),
+    ]
+    .into_iter()
+    .map(|(name, value)| (name.to_string(), Some(value.to_string())))
+    .collect();
+
+    let (get_allocations, get_time) = measure(|| {
+        black_box(
+            manager
+                .get("0.5.0", "role_1", &kind, user_config.clone())
+                .expect("get must succeed"),
+        );
+    });
+    let (get_ref_allocations, get_ref_time) = measure(|| {
+        black_box(
+            manager
+                .get_ref("0.5.0", "role_1", &kind, &user_config)
+                .expect("get_ref must succeed"),
+        );
+    });
+
+    println!("get (cloned user config): {get_allocations} allocations, {get_time:?} per call");
+    println!(
+        "get_ref:                  {get_ref_allocations} allocations, {get_ref_time:?} per call"
+    );
+}
@@ -0,0 +1,1015 @@
+//! This module provides a serde [`serde::de::Deserializer`] to convert a [`HashMap`] with dotted
+//! keys back into a (more or less) arbitrary struct. It is the counterpart of [`crate::ser`].
+//!
+//! This can be used to load a flat configuration map (e.g. produced by [`crate::ser::to_hash_map`])
+//! into a strongly typed struct.
+//!
+//! Additionally, [`user_config_from_value`] converts a natively typed YAML or JSON user config into
+//! the user config expected by [`crate::ProductConfigManager::get`].
+//!
+//! # Example
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//! use product_config::{de, ser};
+//!
+//! #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+//! #[serde(rename_all = "camelCase")]
+//! pub struct TestConfig {
+//!     pub option_one: Option<u32>,
+//!     pub option_two: Option<String>
+//! }
+//!
+//! let config = TestConfig {
+//!   option_one: Some(123),
+//!   option_two: None
+//! };
+//!
+//! let config_map = ser::to_hash_map(&config).unwrap();
+//! let deserialized: TestConfig = de::from_hash_map(&config_map).unwrap();
+//!
+//! assert_eq!(config, deserialized);
+//! ```
+use crate::ser::{Error, Result, SerOptions};
+use serde::de::{self, DeserializeOwned, IntoDeserializer, Visitor};
+use serde_yaml::Value;
+use std::cell::RefCell;
+use std::collections::{btree_map, BTreeMap, BTreeSet, HashMap};
+use std::rc::Rc;
+use std::str::FromStr;
+
+/// This method tries to convert a HashMap into any struct.
+///
+/// Keys are split at dots (".", e.g. "parent_field.nested_field") to reconstruct nested
+/// structs and maps. Values are parsed from their string representation into the requested
+/// type, a value that can not be parsed results in [`Error::InvalidValue`].
+///
+/// These field types are supported:
+///
+/// * bool ("true" or "false")
+/// * integer types
+/// * floating-point types
+/// * char
+/// * String
+/// * Option: Missing keys will be deserialized as None
+/// * Enum: Unit variants only, the other variants are serialized without their name
+/// * Newtype structs
+/// * Map: The fields of the nested map are read from the dotted keys (e.g. "parent_field.nested_field")
+/// * structs: See Map
+/// * sequences (e.g. Vec), tuples and tuple structs: The value is split at "," (see
+///   [`from_hash_map_with_options`]). Missing sequences are empty, since
+///   [`crate::ser::to_hash_map`] omits empty sequences.
+///
+/// Nested sequences are split with the separator of their nesting depth like they are joined
+/// by [`crate::ser::to_hash_map`], e.g. "1,2;3,4" for a `Vec<(i16, u8)>`. The elements of a
+/// sequence (e.g. of a tuple) must be nested equally deep.
+///
+/// These are not supported:
+/// * bytes
+pub fn from_hash_map<T>(map: &HashMap<String, String>) -> Result<T>
+where
+    T: DeserializeOwned,
+{
+    from_hash_map_with_options(map, &SerOptions::default())
+}
+
+/// Same as [`from_hash_map`] but splits the elements of sequences and tuples according to the
+/// given [`SerOptions`], so the result of [`crate::ser::to_hash_map_with_options`] can be
+/// deserialized with the same options. Escaped separators are unescaped if
+/// [`SerOptions::escape_separator`] is set.
+pub fn from_hash_map_with_options<T>(
+    map: &HashMap<String, String>,
+    options: &SerOptions,
+) -> Result<T>
+where
+    T: DeserializeOwned,
+{
+    // Fields of empty sequences are omitted by the serializer and only known once serde
+    // reports them as missing. They are provided as empty sequences in the next attempt.
+    let mut empty_sequences = BTreeSet::new();
+    loop {
+        let mut root = BTreeMap::new();
+        // sorted for deterministic errors
+        for (key, value) in map.iter().collect::<BTreeMap<_, _>>() {
+            insert_node(&mut root, key, value)?;
+        }
+
+        let result = T::deserialize(Deserializer {
+            key: String::new(),
+            node: Node::Map(root),
+            depth: None,
+            context: Context {
+                options,
+                empty_sequences: &empty_sequences,
+            },
+        });
+        match result {
+            Err(Error::MissingField(key)) if !empty_sequences.contains(&key) => {
+                empty_sequences.insert(key);
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Converts a user config parsed from YAML (or JSON) into the user config shape expected by
+/// [`crate::ProductConfigManager::get`]. Native scalars are coerced into strings, so users can
+/// write `port: 8080` instead of `port: "8080"`:
+///
+/// * null: None
+/// * bool: "true" or "false"
+/// * number: its decimal representation (e.g. "8080" or "0.75")
+/// * string: the string itself
+/// * mapping: the entries are flattened using a dotted syntax (e.g. "parent.nested")
+///
+/// Sequences and tagged values are not supported.
+///
+/// # Arguments
+///
+/// * `value` - the parsed user config, must be a mapping
+pub fn user_config_from_value(value: Value) -> Result<HashMap<String, Option<String>>> {
+    let mut user_config = HashMap::new();
+    match value {
+        Value::Mapping(mapping) => {
+            flatten_mapping(&mut user_config, None, mapping)?;
+            Ok(user_config)
+        }
+        _ => Err(Error::Message(
+            "the user config must be a mapping of property names to values".to_string(),
+        )),
+    }
+}
+
+// Adds the entries of the mapping to the user config, prefixing the keys with the parent key
+fn flatten_mapping(
+    user_config: &mut HashMap<String, Option<String>>,
+    parent_key: Option<&str>,
+    mapping: serde_yaml::Mapping,
+) -> Result<()> {
+    for (key, value) in mapping {
+        let key = match scalar_to_string(&key) {
+            Some(key) => key,
+            None => return Err(Error::Message(format!("unsupported key {key:?}"))),
+        };
+        let key = match parent_key {
+            Some(parent_key) => format!("{parent_key}.{key}"),
+            None => key,
+        };
+
+        match value {
+            Value::Null => {
+                user_config.insert(key, None);
+            }
+            Value::Mapping(mapping) => flatten_mapping(user_config, Some(&key), mapping)?,
+            value => match scalar_to_string(&value) {
+                Some(value) => {
+                    user_config.insert(key, Some(value));
+                }
+                None => {
+                    return Err(Error::Message(format!(
+                    "unsupported value for '{key}': only scalars, null and mappings are supported"
+                )))
+                }
+            },
+        }
+    }
+    Ok(())
+}
+
+fn scalar_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::Bool(value) => Some(value.to_string()),
+        Value::Number(value) => Some(value.to_string()),
+        Value::String(value) => Some(value.clone()),
+        _ => None,
+    }
+}
+
+// The dotted keys of the HashMap form a tree of nested maps with the values as leaves
+enum Node {
+    Value(String),
+    Map(BTreeMap<String, Node>),
+    // A field that is not contained in the map but may be an empty sequence
+    Missing,
+    // The first element of sequences whose nesting depth is not known yet (outermost first),
+    // see `resolve_first_element`
+    Pending(Vec<Rc<PendingSequence>>),
+}
+
+// The settings shared by all nodes of a deserialization
+#[derive(Clone, Copy)]
+struct Context<'a> {
+    options: &'a SerOptions,
+    // Missing struct fields that are deserialized as empty sequences
+    empty_sequences: &'a BTreeSet<String>,
+}
+
+impl Context<'_> {
+    // Returns the separator of sequences with the given nesting depth, see `ser::SerOptions`
+    fn separator(&self, depth: usize) -> Result<&str> {
+        match depth {
+            0 => Ok(&self.options.separator),
+            _ => self
+                .options
+                .nested_separators
+                .get(depth - 1)
+                .map(String::as_str)
+                .ok_or(Error::NestingTooDeep(depth)),
+        }
+    }
+
+    // Splits the value at the separator of the nesting depth, unescaping the elements if
+    // separators are escaped
+    fn split(&self, value: &str, depth: usize) -> Result<Vec<String>> {
+        let separator = self.separator(depth)?;
+        if !self.options.escape_separator {
+            return Ok(value.split(separator).map(str::to_string).collect());
+        }
+
+        let mut elements = vec![];
+        let mut element = String::new();
+        let mut rest = value;
+        while let Some(c) = rest.chars().next() {
+            if c == '\\' {
+                // the backslash escapes the next character, e.g. a separator or a backslash
+                rest = &rest[1..];
+                if let Some(escaped) = rest.chars().next() {
+                    element.push(escaped);
+                    rest = &rest[escaped.len_utf8()..];
+                }
+            } else if let Some(remaining) = rest.strip_prefix(separator) {
+                elements.push(std::mem::take(&mut element));
+                rest = remaining;
+            } else {
+                element.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+        elements.push(element);
+        Ok(elements)
+    }
+}
+
+// A sequence value whose separator is only known once the nesting depth of its elements is
+// known, i.e. when the first scalar of its first element is deserialized
+#[derive(Default)]
+struct PendingSequence {
+    // The joined value, unknown for nested sequences until their parent is resolved
+    value: RefCell<Option<String>>,
+    // The nesting depth and the elements once resolved
+    resolved: RefCell<Option<(usize, Vec<String>)>>,
+}
+
+// Resolves the nesting depth of all sequences from the outermost one, whose first elements
+// contain each other and the (scalar) first element of the innermost one, which is returned.
+fn resolve_first_element(context: Context, sequences: &[Rc<PendingSequence>]) -> Result<String> {
+    let mut value = None;
+    for (index, sequence) in sequences.iter().enumerate() {
+        if let Some(value) = value.take() {
+            *sequence.value.borrow_mut() = Some(value);
+        }
+        let joined = sequence.value.borrow().clone().unwrap_or_default();
+        let depth = sequences.len() - 1 - index;
+        let elements = context.split(&joined, depth)?;
+        value = elements.first().cloned();
+        *sequence.resolved.borrow_mut() = Some((depth, elements));
+    }
+    Ok(value.unwrap_or_default())
+}
+
+// Returns the dotted key of a value within the map with the given (dotted) key
+fn join_key(parent_key: &str, key: &str) -> String {
+    if parent_key.is_empty() {
+        key.to_string()
+    } else {
+        format!("{parent_key}.{key}")
+    }
+}
+
+// Inserts the value into the tree, creating the parent maps of dotted keys as required
+fn insert_node(root: &mut BTreeMap<String, Node>, key: &str, value: &str) -> Result<()> {
+    let conflict = || {
+        Error::Message(format!(
+            "key '{key}' conflicts with another key that is used as value and as parent of nested keys"
+        ))
+    };
+
+    let mut parts = key.split('.').collect::<Vec<_>>();
+    // split always returns at least one element
+    let last = parts.pop().unwrap_or_default();
+
+    let mut current = root;
+    for part in parts {
+        let node = current
+            .entry(part.to_string())
+            .or_insert_with(|| Node::Map(BTreeMap::new()));
+        current = match node {
+            Node::Map(map) => map,
+            _ => return Err(conflict()),
+        };
+    }
+
+    match current.entry(last.to_string()) {
+        btree_map::Entry::Vacant(entry) => {
+            entry.insert(Node::Value(value.to_string()));
+            Ok(())
+        }
+        btree_map::Entry::Occupied(_) => Err(conflict()),
+    }
+}
+
+/// The Deserializer walks the tree of nodes created from the dotted keys.
+/// The key is only used for error messages and to track missing fields.
+struct Deserializer<'a> {
+    key: String,
+    node: Node,
+    // The nesting depth of the value if it is a sequence, None if it is not known (yet)
+    depth: Option<usize>,
+    context: Context<'a>,
+}
+
+impl<'a> Deserializer<'a> {
+    // Returns the value of a leaf or an error if there are nested keys instead
+    fn into_value(self, expected: &str) -> Result<String> {
+        match self.node {
+            Node::Value(value) => Ok(value),
+            Node::Map(_) => Err(Error::Message(format!(
+                "expected {expected} for '{}' but found nested keys",
+                self.key
+            ))),
+            Node::Missing => Err(Error::MissingField(self.key)),
+            Node::Pending(sequences) => resolve_first_element(self.context, &sequences),
+        }
+    }
+
+    // Returns the deserializers of the elements of a sequence with a known nesting depth
+    fn elements(key: &str, value: &str, depth: usize, context: Context<'a>) -> Result<Vec<Self>> {
+        if value.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        Ok(context
+            .split(value, depth)?
+            .into_iter()
+            .enumerate()
+            .map(|(index, element)| Deserializer {
+                key: format!("{key}[{index}]"),
+                node: Node::Value(element),
+                depth: depth.checked_sub(1),
+                context,
+            })
+            .collect())
+    }
+
+    fn parse<T: FromStr>(self, expected: &str) -> Result<T> {
+        let key = self.key.clone();
+        let value = self.into_value(expected)?;
+        value.parse().map_err(|_| Error::InvalidValue {
+            key,
+            value,
+            expected: expected.to_string(),
+        })
+    }
+}
+
+// All scalars are parsed via FromStr and only differ in the type and visitor method
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident($ty:ty, $expected:literal)),* $(,)?) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value>
+            where
+                V: Visitor<'de>,
+            {
+                visitor.$visit(self.parse::<$ty>($expected)?)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer<'_> {
+    type Error = Error;
+
+    // Without type information we can only provide strings or nested maps.
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.node {
+            Node::Map(_) => self.deserialize_map(visitor),
+            _ => visitor.visit_string(self.into_value("a string")?),
+        }
+    }
+
+    deserialize_parsed! {
+        deserialize_bool => visit_bool(bool, "a boolean"),
+        deserialize_i8 => visit_i8(i8, "an integer (i8)"),
+        deserialize_i16 => visit_i16(i16, "an integer (i16)"),
+        deserialize_i32 => visit_i32(i32, "an integer (i32)"),
+        deserialize_i64 => visit_i64(i64, "an integer (i64)"),
+        deserialize_u8 => visit_u8(u8, "an unsigned integer (u8)"),
+        deserialize_u16 => visit_u16(u16, "an unsigned integer (u16)"),
+        deserialize_u32 => visit_u32(u32, "an unsigned integer (u32)"),
+        deserialize_u64 => visit_u64(u64, "an unsigned integer (u64)"),
+        deserialize_f32 => visit_f32(f32, "a float (f32)"),
+        deserialize_f64 => visit_f64(f64, "a float (f64)"),
+        deserialize_char => visit_char(char, "a single character"),
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_string(self.into_value("a string")?)
+    }
+
+    // Not sure what to make out of a byte array, see ser.
+    fn deserialize_bytes<V>(self, _visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        Err(Error::UnsupportedType)
+    }
+
+    fn deserialize_byte_buf<V>(self, _visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        Err(Error::UnsupportedType)
+    }
+
+    // A missing key is mostly handled by serde itself (missing optional fields are None), so
+    // every key we are called with is a present optional unless it may be an empty sequence.
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.node {
+            Node::Missing => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    // The separator depends on the nesting depth of the sequence, which is only known for
+    // elements of sequences. Otherwise the value is split once the first element is known to
+    // be a scalar or another sequence, see `PendingSeqDeserializer`.
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let Deserializer {
+            key,
+            node,
+            depth,
+            context,
+        } = self;
+        match (node, depth) {
+            (Node::Missing, _) => visitor.visit_seq(SeqDeserializer {
+                elements: Vec::new().into_iter(),
+            }),
+            (Node::Value(value), Some(depth)) => visitor.visit_seq(SeqDeserializer {
+                elements: Deserializer::elements(&key, &value, depth, context)?.into_iter(),
+            }),
+            (Node::Value(value), None) => {
+                let sequence = PendingSequence {
+                    value: RefCell::new(Some(value)),
+                    ..PendingSequence::default()
+                };
+                visitor.visit_seq(PendingSeqDeserializer {
+                    key,
+                    sequences: vec![Rc::new(sequence)],
+                    index: 0,
+                    context,
+                })
+            }
+            (Node::Pending(mut sequences), _) => {
+                sequences.push(Rc::default());
+                visitor.visit_seq(PendingSeqDeserializer {
+                    key,
+                    sequences,
+                    index: 0,
+                    context,
+                })
+            }
+            (Node::Map(_), _) => Err(Error::Message(format!(
+                "expected a sequence for '{key}' but found nested keys"
+            ))),
+        }
+    }
+
+    fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.node {
+            Node::Map(map) => visitor.visit_map(MapDeserializer {
+                parent_key: self.key,
+                entries: map.into_iter(),
+                value: None,
+                context: self.context,
+            }),
+            node => {
+                let key = self.key.clone();
+                let value = Deserializer { node, ..self }.into_value("nested keys")?;
+                Err(Error::InvalidValue {
+                    key,
+                    value,
+                    expected: "nested keys".to_string(),
+                })
+            }
+        }
+    }
+
+    // Fields that were reported missing before are provided as (possibly empty) sequences.
+    // The missing fields reported by serde are qualified with the dotted key of the struct.
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let mut map = match self.node {
+            Node::Map(map) => map,
+            node => return Deserializer { node, ..self }.deserialize_map(visitor),
+        };
+
+        let missing = fields
+            .iter()
+            .filter(|field| !map.contains_key(**field))
+            .collect::<Vec<_>>();
+        for field in &missing {
+            if self
+                .context
+                .empty_sequences
+                .contains(&join_key(&self.key, field))
+            {
+                map.insert(field.to_string(), Node::Missing);
+            }
+        }
+
+        let result = visitor.visit_map(MapDeserializer {
+            parent_key: self.key.clone(),
+            entries: map.into_iter(),
+            value: None,
+            context: self.context,
+        });
+        match result {
+            Err(Error::MissingField(field)) if missing.iter().any(|missing| **missing == field) => {
+                Err(Error::MissingField(join_key(&self.key, &field)))
+            }
+            result => result,
+        }
+    }
+
+    // Only unit variants can be deserialized, the other variants are serialized without the
+    // variant name.
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let value = self.into_value("an enum variant")?;
+        visitor.visit_enum(IntoDeserializer::<Error>::into_deserializer(value))
+    }
+
+    fn deserialize_identifier<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+}
+
+struct SeqDeserializer<'a> {
+    elements: std::vec::IntoIter<Deserializer<'a>>,
+}
+
+impl<'de> de::SeqAccess<'de> for SeqDeserializer<'_> {
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        self.elements
+            .next()
+            .map(|element| seed.deserialize(element))
+            .transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.elements.len())
+    }
+}
+
+// The elements of a sequence with an unknown nesting depth. The first element is deserialized
+// from a pending node which resolves the nesting depth of this and all enclosing pending
+// sequences, the other elements are deserialized from the resolved elements.
+struct PendingSeqDeserializer<'a> {
+    // The dotted key of the sequence
+    key: String,
+    // This sequence (last) and the enclosing pending sequences
+    sequences: Vec<Rc<PendingSequence>>,
+    // The index of the next element
+    index: usize,
+    context: Context<'a>,
+}
+
+impl<'de> de::SeqAccess<'de> for PendingSeqDeserializer<'_> {
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        let Some(sequence) = self.sequences.last().cloned() else {
+            return Ok(None);
+        };
+        let index = self.index;
+        self.index += 1;
+        let key = format!("{}[{index}]", self.key);
+
+        if sequence.resolved.borrow().is_none() {
+            if sequence.value.borrow().as_deref() == Some("") {
+                *sequence.resolved.borrow_mut() = Some((0, Vec::new()));
+                return Ok(None);
+            }
+
+            let element = seed.deserialize(Deserializer {
+                key,
+                node: Node::Pending(self.sequences.clone()),
+                depth: None,
+                context: self.context,
+            })?;
+            // The first element did not contain a scalar (e.g. a unit), so this is the
+            // innermost sequence
+            if sequence.resolved.borrow().is_none() {
+                resolve_first_element(self.context, &self.sequences)?;
+            }
+            return Ok(Some(element));
+        }
+
+        let (depth, element) = match sequence.resolved.borrow().as_ref() {
+            Some((depth, elements)) => (*depth, elements.get(index).cloned()),
+            None => (0, None),
+        };
+        element
+            .map(|element| {
+                seed.deserialize(Deserializer {
+                    key,
+                    node: Node::Value(element),
+                    depth: depth.checked_sub(1),
+                    context: self.context,
+                })
+            })
+            .transpose()
+    }
+}
+
+struct MapDeserializer<'a> {
+    // The dotted key of the map itself, empty for the root
+    parent_key: String,
+    entries: btree_map::IntoIter<String, Node>,
+    // The value of the last key returned by `next_key_seed`
+    value: Option<(String, Node)>,
+    context: Context<'a>,
+}
+
+impl<'de> de::MapAccess<'de> for MapDeserializer<'_> {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>>
+    where
+        K: de::DeserializeSeed<'de>,
+    {
+        match self.entries.next() {
+            Some((key, node)) => {
+                self.value = Some((key.clone(), node));
+                seed.deserialize(IntoDeserializer::<Error>::into_deserializer(key))
+                    .map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        let (key, node) = self
+            .value
+            .take()
+            .ok_or_else(|| Error::Message("value is missing for map key".to_string()))?;
+        seed.deserialize(Deserializer {
+            key: join_key(&self.parent_key, &key),
+            node,
+            depth: None,
+            context: self.context,
+        })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{from_hash_map, from_hash_map_with_options, user_config_from_value};
+    use crate::ser::{to_hash_map, to_hash_map_with_options, Error, SerOptions};
+    use rstest::rstest;
+    use serde::{Deserialize, Serialize};
+    use std::collections::HashMap;
+
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    struct NewtypeStruct(String);
+
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    struct TupleStruct(i16, u8);
+
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    struct TestStruct {
+        nested_value: i32,
+        nested_string: String,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    enum TestEnum {
+        Unit,
+        Other,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    struct Test {
+        bool_test: bool,
+        i8_test: i8,
+        i64_test: i64,
+        u16_test: u16,
+        u64_test: u64,
+        f64_test: f64,
+        char_test: char,
+        string_test: String,
+        opt_none_test: Option<String>,
+        opt_some_test: Option<String>,
+        opt_struct_none_test: Option<TestStruct>,
+        map_test: HashMap<String, i32>,
+        enum_unit_variant_test: TestEnum,
+        sequence_test: Vec<String>,
+        tuple_test: (String, i8),
+        newtype_struct_test: NewtypeStruct,
+        struct_test: TestStruct,
+        tuple_struct_test: TupleStruct,
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let test = Test {
+            bool_test: true,
+            i8_test: -8,
+            i64_test: -64,
+            u16_test: 16,
+            u64_test: 64,
+            f64_test: 64.5,
+            char_test: 'l',
+            string_test: "test_string".to_string(),
+            opt_none_test: None,
+            opt_some_test: Some("test_opt_str".to_string()),
+            opt_struct_none_test: None,
+            map_test: HashMap::from([("foo".to_string(), 123), ("bar".to_string(), 456)]),
+            enum_unit_variant_test: TestEnum::Other,
+            sequence_test: vec!["one".to_string(), "two".to_string(), "three".to_string()],
+            tuple_test: ("first_tuple_thing".to_string(), 123),
+            newtype_struct_test: NewtypeStruct("foobar".to_string()),
+            struct_test: TestStruct {
+                nested_value: 1234,
+                nested_string: "nested".to_string(),
+            },
+            tuple_struct_test: TupleStruct(1, 2),
+        };
+
+        let map = to_hash_map(&test).unwrap();
+        let deserialized: Test = from_hash_map(&map).unwrap();
+
+        assert_eq!(deserialized, test);
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Nested {
+        port: u16,
+        name: Option<String>,
+        inner: Option<Inner>,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Inner {
+        enabled: bool,
+    }
+
+    #[rstest]
+    #[case::missing_optionals(
+        &[("port", "8080")],
+        Ok(Nested { port: 8080, name: None, inner: None })
+    )]
+    #[case::nested(
+        &[("port", "8080"), ("name", "test"), ("inner.enabled", "true")],
+        Ok(Nested { port: 8080, name: Some("test".to_string()), inner: Some(Inner { enabled: true }) })
+    )]
+    #[case::invalid_integer(
+        &[("port", "http")],
+        Err(Error::InvalidValue { key: "port".to_string(), value: "http".to_string(), expected: "an unsigned integer (u16)".to_string() })
+    )]
+    #[case::invalid_nested_bool(
+        &[("port", "8080"), ("inner.enabled", "yes")],
+        Err(Error::InvalidValue { key: "inner.enabled".to_string(), value: "yes".to_string(), expected: "a boolean".to_string() })
+    )]
+    #[case::value_instead_of_struct(
+        &[("port", "8080"), ("inner", "true")],
+        Err(Error::InvalidValue { key: "inner".to_string(), value: "true".to_string(), expected: "nested keys".to_string() })
+    )]
+    #[case::missing_required(
+        &[("name", "test")],
+        Err(Error::MissingField("port".to_string()))
+    )]
+    #[case::missing_nested_required(
+        &[("port", "8080"), ("inner.other", "true")],
+        Err(Error::MissingField("inner.enabled".to_string()))
+    )]
+    #[case::conflicting_keys(
+        &[("port", "8080"), ("port.value", "8080")],
+        Err(Error::Message("key 'port.value' conflicts with another key that is used as value and as parent of nested keys".to_string()))
+    )]
+    fn test_from_hash_map(
+        #[case] entries: &[(&str, &str)],
+        #[case] expected: Result<Nested, Error>,
+    ) {
+        let map = entries
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect::<HashMap<_, _>>();
+
+        assert_eq!(from_hash_map::<Nested>(&map), expected);
+    }
+
+    #[derive(Debug, Default, Deserialize, PartialEq, Serialize)]
+    struct Sequences {
+        strings: Vec<String>,
+        tuples: Vec<(i16, u8)>,
+        nested: Vec<Vec<String>>,
+        tuple_with_sequence: Option<(Vec<u8>, String)>,
+        optional: Option<Vec<String>>,
+    }
+
+    #[rstest]
+    #[case::nested_tuples(
+        SerOptions::default(),
+        Sequences { tuples: vec![(1, 2), (3, 4)], ..Sequences::default() },
+        &[("tuples", "1,2;3,4")]
+    )]
+    #[case::single_nested_element(
+        SerOptions::default(),
+        Sequences { nested: vec![vec!["a".to_string()]], ..Sequences::default() },
+        &[("nested", "a")]
+    )]
+    #[case::nested_sequences(
+        SerOptions::default(),
+        Sequences {
+            strings: vec!["a".to_string(), "b".to_string()],
+            nested: vec![vec!["a".to_string(), "b".to_string()], vec!["c".to_string()]],
+            tuple_with_sequence: Some((vec![1, 2], "c".to_string())),
+            optional: Some(vec!["d".to_string()]),
+            ..Sequences::default()
+        },
+        &[("strings", "a,b"), ("nested", "a,b;c"), ("tuple_with_sequence", "1,2;c"), ("optional", "d")]
+    )]
+    #[case::escaped_separators(
+        SerOptions { escape_separator: true, ..SerOptions::default() },
+        Sequences {
+            strings: vec!["a,b".to_string(), "c\\d".to_string()],
+            nested: vec![vec!["a;b".to_string(), "c,d".to_string()], vec!["e".to_string()]],
+            ..Sequences::default()
+        },
+        &[("strings", "a\\,b,c\\\\d"), ("nested", "a\\;b,c\\\\,d;e")]
+    )]
+    #[case::custom_separators(
+        SerOptions { separator: " ".to_string(), nested_separators: vec!["/".to_string()], ..SerOptions::default() },
+        Sequences {
+            strings: vec!["a".to_string(), "b".to_string()],
+            tuples: vec![(-1, 2), (3, 4)],
+            ..Sequences::default()
+        },
+        &[("strings", "a b"), ("tuples", "-1 2/3 4")]
+    )]
+    #[case::empty_sequences(SerOptions::default(), Sequences::default(), &[])]
+    fn test_round_trip_with_options(
+        #[case] options: SerOptions,
+        #[case] sequences: Sequences,
+        #[case] expected_map: &[(&str, &str)],
+    ) {
+        let map = to_hash_map_with_options(&sequences, &options).unwrap();
+        let expected_map = expected_map
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect::<HashMap<_, _>>();
+        assert_eq!(map, expected_map);
+
+        let deserialized: Sequences = from_hash_map_with_options(&map, &options).unwrap();
+        assert_eq!(deserialized, sequences);
+    }
+
+    #[rstest]
+    #[case::string("port: \"8080\"", Ok(vec![("port", Some("8080"))]))]
+    #[case::integer("port: 8080", Ok(vec![("port", Some("8080"))]))]
+    #[case::float("ratio: 0.75", Ok(vec![("ratio", Some("0.75"))]))]
+    #[case::boolean("enabled: true", Ok(vec![("enabled", Some("true"))]))]
+    #[case::null("password: null", Ok(vec![("password", None)]))]
+    #[case::tilde("password: ~", Ok(vec![("password", None)]))]
+    #[case::nested(
+        "auth:\n  enabled: false\n  port: 443",
+        Ok(vec![("auth.enabled", Some("false")), ("auth.port", Some("443"))])
+    )]
+    #[case::numeric_key("1: one", Ok(vec![("1", Some("one"))]))]
+    #[case::sequence(
+        "hosts: [a, b]",
+        Err(Error::Message("unsupported value for 'hosts': only scalars, null and mappings are supported".to_string()))
+    )]
+    #[case::no_mapping(
+        "8080",
+        Err(Error::Message("the user config must be a mapping of property names to values".to_string()))
+    )]
+    fn test_user_config_from_value(
+        #[case] yaml: &str,
+        #[case] expected: Result<Vec<(&str, Option<&str>)>, Error>,
+    ) {
+        let value = serde_yaml::from_str(yaml).unwrap();
+        let expected = expected.map(|entries| {
+            entries
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value.map(str::to_string)))
+                .collect::<HashMap<_, _>>()
+        });
+
+        assert_eq!(user_config_from_value(value), expected);
+    }
+}
@@ -1,24 +1,56 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt;
 use std::path::PathBuf;
+use std::sync::Arc;
 
-use snafu::Snafu;
+use snafu::{AsErrorSource, Snafu};
 
-use crate::types::PropertyValueSpec;
+use crate::types::{BoundKind, CharsetClass, IntegerSemantic, PropertyValueSpec, StringFormat};
 use crate::PropertyName;
 
+/// Suggests the replacements of a deprecated property, if any.
+fn replacements_hint(replacements: &[String]) -> String {
+    if replacements.is_empty() {
+        String::new()
+    } else {
+        format!(", use '{}' instead", replacements.join("', '"))
+    }
+}
+
 #[derive(Clone, Debug, PartialOrd, PartialEq, Snafu)]
 pub enum Error {
     #[snafu(display("file not found: {}", file_name.display()))]
     FileNotFound { file_name: PathBuf },
 
     #[snafu(display("could not parse yaml file - {}: {reason}", file.display()))]
-    YamlFileNotParsable { file: PathBuf, reason: String },
+    YamlFileNotParsable {
+        file: PathBuf,
+        reason: String,
+        source: YamlError,
+    },
+
+    #[snafu(display("cyclic include: {}", chain.iter().map(|file| file.display().to_string()).collect::<Vec<_>>().join(" -> ")))]
+    CyclicInclude { chain: Vec<PathBuf> },
+
+    #[snafu(display(
+        "includes are only supported for product config files, but found: {includes:?}"
+    ))]
+    IncludesNotSupported { includes: Vec<String> },
 
     #[snafu(display("could not parse yaml - {content}: {reason}"))]
-    YamlNotParsable { content: String, reason: String },
+    YamlNotParsable {
+        content: String,
+        reason: String,
+        source: YamlError,
+    },
 
     #[snafu(display("failed to parse '{version}' as SemVer version: {reason}"))]
     InvalidVersion { reason: String, version: String },
 
+    #[snafu(display("failed to parse '{requirement}' as SemVer version requirement: {reason}"))]
+    InvalidVersionRequirement { reason: String, requirement: String },
+
     #[snafu(display("[{property_name}]: current product version is '{product_version}' -> property not supported; available from version '{required_version}'"))]
     VersionNotSupported {
         property_name: PropertyName,
@@ -26,11 +58,41 @@ pub enum Error {
         required_version: String,
     },
 
-    #[snafu(display("[{property_name}]: current product version is '{product_version}' -> property deprecated since version '{deprecated_version}'"))]
+    #[snafu(display("[{property_name}]: current product version is '{product_version}' -> property deprecated since version '{deprecated_version}'{}", replacements_hint(replacements)))]
     VersionDeprecated {
         property_name: String,
         product_version: String,
         deprecated_version: String,
+        /// The replacements of the property (see [`crate::types::PropertySpec::deprecated_for`]).
+        replacements: Vec<String>,
+    },
+
+    #[snafu(display(
+        "[{property_name}]: value could not be computed from '{expression}': {reason}"
+    ))]
+    ComputedValueNotEvaluable {
+        property_name: String,
+        expression: String,
+        reason: String,
+    },
+
+    #[snafu(display("[{old}]: property is deprecated, but its replacement '{new}' is not set"))]
+    DeprecatedNotMigrated { old: String, new: String },
+
+    #[snafu(display("[{property_name}]: current product version is '{product_version}' -> property removed in version '{removed_version}'"))]
+    VersionRemoved {
+        property_name: String,
+        product_version: String,
+        removed_version: String,
+    },
+
+    #[snafu(display("[{target}]: expansion value '{expansion_value}' of property '{source}' is overridden by user value '{user_value}'"))]
+    ExpansionOverriddenByUser {
+        #[snafu(source(false))]
+        source: String,
+        target: String,
+        expansion_value: String,
+        user_value: String,
     },
 
     #[snafu(display("required config spec property not found: '{name}'"))]
@@ -39,6 +101,22 @@ pub enum Error {
     #[snafu(display("no config property found that matches '{property_name}'"))]
     PropertyNotFound { property_name: PropertyName },
 
+    #[snafu(display("[{property_name}]: property has no name for kind {kind} and is dropped"))]
+    PropertyNameNotFoundForKind {
+        property_name: String,
+        kind: crate::types::PropertyNameKind,
+    },
+
+    #[snafu(display(
+        "[{target_name}]: properties '{first}' and '{second}' have the same name for kind {kind}"
+    ))]
+    TargetPropertyNameConflict {
+        target_name: String,
+        kind: crate::types::PropertyNameKind,
+        first: String,
+        second: String,
+    },
+
     #[snafu(display("no roles in '{name}' match the provided role: '{role}'"))]
     PropertySpecRoleNotFound { name: PropertyName, role: String },
 
@@ -49,17 +127,36 @@ pub enum Error {
     PropertySpecRoleNotProvidedByUser { name: PropertyName },
 
     #[snafu(display(
-        "[{property_name}]: provided value '{received}' violates min/max bound '{expected}'"
+        "[{property_name}]: provided value '{received}' violates {bound_kind} min/max bound '{expected}'"
     ))]
     PropertyValueOutOfBounds {
         property_name: String,
         received: String,
         expected: String,
+        bound_kind: BoundKind,
+    },
+
+    #[snafu(display(
+        "[{property_name}]: exclusive_{side} must not be combined with {side} or {side}_bound_kind"
+    ))]
+    ConflictingBounds { property_name: String, side: String },
+
+    #[snafu(display("[{property_name}]: min bound '{min}' is greater than max bound '{max}'"))]
+    PropertyBoundsInverted {
+        property_name: String,
+        min: String,
+        max: String,
     },
 
     #[snafu(display("[{property_name}]: config value missing for required property"))]
     PropertyValueMissing { property_name: String },
 
+    #[snafu(display("[{property_name}]: config value must not be empty for required property"))]
+    PropertyValueEmpty { property_name: String },
+
+    #[snafu(display("[{property_name}]: no value resolved for optional property (no user, recommended or default value)"))]
+    NoValueResolved { property_name: String },
+
     #[snafu(display("[{property_name}]: provided property value(s) missing for version '{version}'. Got: {property_values:?}"))]
     PropertySpecValueMissingForVersion {
         property_name: PropertyName,
@@ -83,18 +180,185 @@ pub enum Error {
         datatype: String,
     },
 
+    #[snafu(display("[{property_name}]: value '{value}' is not a finite number"))]
+    DatatypeNotFinite {
+        property_name: String,
+        value: String,
+    },
+
     #[snafu(display("[{property_name}]: value '{value}' does not match regex"))]
     DatatypeRegexNotMatching {
         property_name: String,
         value: String,
     },
 
+    #[snafu(display(
+        "[{property_name}]: value '{value}' contains characters not in charset '{charset}'"
+    ))]
+    DatatypeCharsetNotMatching {
+        property_name: String,
+        value: String,
+        charset: CharsetClass,
+    },
+
+    #[snafu(display(
+        "[{property_name}]: value '{value}' is out of the valid range of '{semantic}' values"
+    ))]
+    DatatypeSemanticOutOfRange {
+        property_name: String,
+        value: String,
+        semantic: IntegerSemantic,
+    },
+
+    #[snafu(display("[{property_name}]: port {port} is privileged (below 1024)"))]
+    PrivilegedPort { property_name: String, port: i64 },
+
+    #[snafu(display(
+        "[{property_name}]: value '{value}' does not match any allowed pattern: {patterns:?}"
+    ))]
+    PropertyValueNotMatchingAllowedPatterns {
+        property_name: String,
+        value: String,
+        patterns: Vec<String>,
+    },
+
+    #[snafu(display("[{property_name}]: value '{value}' is not in format '{format}'"))]
+    DatatypeFormatNotMatching {
+        property_name: String,
+        value: String,
+        format: StringFormat,
+    },
+
+    #[snafu(display("[{property_name}]: value '{value}' must not contain line breaks"))]
+    DatatypeNewlineNotAllowed {
+        property_name: String,
+        value: String,
+    },
+
+    #[snafu(display(
+        "[{property_name}]: value has {lines} lines, but at most {max_lines} are allowed"
+    ))]
+    DatatypeTooManyLines {
+        property_name: String,
+        lines: usize,
+        max_lines: usize,
+    },
+
+    #[snafu(display("[{property_name}]: the array separator must not be empty"))]
+    EmptyArraySeparator { property_name: String },
+
+    #[snafu(display("[{property_name}]: array element {index} ('{value}') is invalid"))]
+    ArrayElementInvalid {
+        property_name: String,
+        index: usize,
+        value: String,
+    },
+
     #[snafu(display("empty regex pattern for unit '{unit}'"))]
     EmptyRegexPattern { unit: String },
 
+    #[snafu(display("invalid regex '{regex}': {reason}"))]
+    InvalidRegex { regex: String, reason: String },
+
     #[snafu(display("invalid regex pattern for unit '{unit}': '{regex}'"))]
     InvalidRegexPattern { unit: String, regex: String },
 
+    #[snafu(display("example '{example}' of unit '{unit}' does not match its regex '{regex}'"))]
+    UnitExampleNotMatching {
+        unit: String,
+        regex: String,
+        example: String,
+    },
+
+    #[snafu(display("the regex for unit '{unit}' ('{regex}') is not anchored at the start (^) and end ($), values are matched partially"))]
+    UnanchoredUnitRegex { unit: String, regex: String },
+
+    #[snafu(display("[{property_name}]: {first} '{first_version}' must not be after {second} '{second_version}'"))]
+    VersionOrderInvalid {
+        property_name: String,
+        first: String,
+        first_version: String,
+        second: String,
+        second_version: String,
+    },
+
+    #[snafu(display("invalid naming convention pattern for file '{file}': '{regex}'"))]
+    InvalidNamingConventionPattern { file: String, regex: String },
+
+    #[snafu(display(
+        "[{property_name}]: property name does not match the naming convention '{regex}' of file '{file}'"
+    ))]
+    PropertyNameNotMatchingConvention {
+        property_name: String,
+        file: String,
+        regex: String,
+    },
+
+    #[snafu(display("[{property_name}]: allowed values provider '{provider}' is not registered"))]
+    AllowedValuesProviderNotFound {
+        property_name: String,
+        provider: String,
+    },
+
+    #[snafu(display("count constraint '{constraint}' violated: {count} properties are set, at least {min} required"))]
+    CountConstraintBelowMin {
+        constraint: String,
+        count: usize,
+        min: usize,
+    },
+
+    #[snafu(display("count constraint '{constraint}' violated: {count} properties are set, at most {max} allowed"))]
+    CountConstraintAboveMax {
+        constraint: String,
+        count: usize,
+        max: usize,
+    },
+
+    #[snafu(display("mirror '{mirror}' violated in role '{role}': mirrored properties have diverging values {values:?}"))]
+    MirroredValuesDiverge {
+        mirror: String,
+        role: String,
+        values: BTreeMap<String, String>,
+    },
+
+    #[snafu(display("expansion cycle: {}", path.join(" -> ")))]
+    ExpansionCycle { path: Vec<String> },
+
+    #[snafu(display(
+        "[{property_name}]: property is removed and added or updated by the same change set"
+    ))]
+    ChangeSetConflict { property_name: String },
+
+    #[snafu(display("product config self check failed with {} problem(s): {}", errors.len(), errors.iter().map(ToString::to_string).collect::<Vec<_>>().join("; ")))]
+    SelfCheckFailed { errors: Vec<Error> },
+
+    #[snafu(display("[{property_name}]: allowed value '{value}' is listed more than once"))]
+    DuplicateAllowedValue {
+        property_name: String,
+        value: String,
+    },
+
+    #[snafu(display(
+        "[{property_name}]: property name is used by multiple properties of file '{file}'"
+    ))]
+    DuplicateFilePropertyName { property_name: String, file: String },
+
+    #[snafu(display(
+        "[{property_name}]: expansion '{expansion}' references role '{role}' that no property defines"
+    ))]
+    UndefinedExpansionRole {
+        property_name: String,
+        expansion: String,
+        role: String,
+    },
+
+    #[snafu(display("the regex for unit '{unit}' ('{regex}') uses features not supported by the regex crate: {features:?}"))]
+    RegexNotPortable {
+        unit: String,
+        regex: String,
+        features: Vec<String>,
+    },
+
     #[snafu(display("the regex for unit '{unit}' ('{regex}') could not be evaluated on property '{property_name}' (value: '{value}'): {reason}."))]
     RegexNotEvaluable {
         property_name: String,
@@ -104,6 +368,39 @@ pub enum Error {
         reason: String,
     },
 
+    #[snafu(display("failed to render the config as {format}: {reason}"))]
+    RenderFailed {
+        format: crate::writer::OutputFormat,
+        reason: String,
+    },
+
+    #[snafu(display("[{property_name}]: port '{port}' is already reserved"))]
+    PortAlreadyReserved { property_name: String, port: u16 },
+
+    /// An `accepted_units` or `default_unit` entry is no known unit suffix (e.g. "Mi" or "G").
+    /// These entries are unit suffixes used for unit conversion, not names of the units
+    /// declared in the spec (see [`crate::types::Spec::units`]), which are regex based and do
+    /// not define conversion factors.
+    #[snafu(display("[{property_name}]: unit '{unit}' is no known unit suffix"))]
+    UnknownUnit { property_name: String, unit: String },
+
+    #[snafu(display("[{property_name}]: unit '{unit}' of value '{value}' is not accepted, accepted units: {accepted_units:?}"))]
+    UnitNotAccepted {
+        property_name: String,
+        value: String,
+        unit: String,
+        accepted_units: Vec<String>,
+    },
+
+    #[snafu(display(
+        "[{property_name}]: value '{value}' can not be converted into unit '{unit}' without loss"
+    ))]
+    UnitConversionNotExact {
+        property_name: String,
+        value: String,
+        unit: String,
+    },
+
     #[snafu(display("[{property_name}]: unit not provided"))]
     UnitNotProvided { property_name: PropertyName },
 
@@ -113,3 +410,109 @@ pub enum Error {
         unit: String,
     },
 }
+
+/// The error returned by [`crate::ProductConfigManager::get_or_first_error`] and
+/// [`crate::ProductConfigManager::render`] for `?` based flows.
+#[derive(Clone, Debug, PartialEq, Snafu)]
+#[snafu(visibility(pub(crate)))]
+pub enum ConfigError {
+    #[snafu(display("failed to validate the product config"))]
+    Validation {
+        #[snafu(source(from(Error, Box::new)))]
+        source: Box<Error>,
+    },
+
+    #[snafu(display("[{property_name}]: invalid property"))]
+    InvalidProperty {
+        property_name: String,
+        #[snafu(source(from(Error, Box::new)))]
+        source: Box<Error>,
+    },
+}
+
+/// Wraps a [`serde_yaml::Error`] to keep it as the source of an [`Error`], while [`Error`] can
+/// still be cloned and compared. Two wrapped errors are compared via their messages.
+/// The wrapped error is returned by [`std::error::Error::source`] and can be downcast to
+/// [`serde_yaml::Error`].
+#[derive(Clone, Debug)]
+pub struct YamlError(Arc<serde_yaml::Error>);
+
+impl YamlError {
+    /// Returns the wrapped [`serde_yaml::Error`].
+    pub fn inner(&self) -> &serde_yaml::Error {
+        &self.0
+    }
+}
+
+impl From<serde_yaml::Error> for YamlError {
+    fn from(error: serde_yaml::Error) -> Self {
+        YamlError(Arc::new(error))
+    }
+}
+
+impl fmt::Display for YamlError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl AsErrorSource for YamlError {
+    fn as_error_source(&self) -> &(dyn std::error::Error + 'static) {
+        self.inner()
+    }
+}
+
+impl PartialEq for YamlError {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.to_string() == other.0.to_string()
+    }
+}
+
+impl PartialOrd for YamlError {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.0.to_string().partial_cmp(&other.0.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ProductConfigManager;
+    use std::error::Error as StdError;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_yaml_not_parsable_source() {
+        let err = ProductConfigManager::from_str("version: [").err().unwrap();
+
+        assert!(matches!(err, Error::YamlNotParsable { .. }));
+        let source = err.source().unwrap();
+        assert!(source.downcast_ref::<serde_yaml::Error>().is_some());
+    }
+
+    #[test]
+    fn test_yaml_file_not_parsable_source() {
+        let err = ProductConfigManager::from_yaml_file("data/test_yamls/invalid.yaml")
+            .err()
+            .unwrap();
+
+        assert!(matches!(err, Error::YamlFileNotParsable { .. }));
+        let source = err.source().unwrap();
+        assert!(source.downcast_ref::<serde_yaml::Error>().is_some());
+    }
+
+    #[test]
+    fn test_version_deprecated_display_replacements() {
+        let err = Error::VersionDeprecated {
+            property_name: "ENV_OLD".to_string(),
+            product_version: "0.5.0".to_string(),
+            deprecated_version: "0.4.0".to_string(),
+            replacements: vec!["ENV_NEW".to_string(), "ENV_OTHER".to_string()],
+        };
+
+        assert_eq!(
+            err.to_string(),
+            "[ENV_OLD]: current product version is '0.5.0' -> property deprecated since version '0.4.0', use 'ENV_NEW', 'ENV_OTHER' instead"
+        );
+    }
+}
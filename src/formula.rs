@@ -0,0 +1,221 @@
+//! Evaluates the arithmetic expressions of computed properties (see
+//! [`crate::types::PropertySpec::computed_from`]).
+//!
+//! The grammar supports `+`, `-`, `*`, `/`, parentheses, unary minus, numeric literals and
+//! references to other properties (names consisting of ASCII letters, digits, `_` and `.`).
+
+/// Evaluates the expression, resolving property references via `lookup`. Returns a
+/// human readable reason if the expression is invalid or can not be evaluated.
+///
+/// # Arguments
+///
+/// * `expression` - the arithmetic expression, e.g. "HEAP + OFF_HEAP"
+/// * `lookup` - resolves a referenced property name to its (numeric) value
+pub(crate) fn evaluate<F>(expression: &str, lookup: F) -> Result<f64, String>
+where
+    F: Fn(&str) -> Result<f64, String>,
+{
+    let tokens = tokenize(expression)?;
+    let mut parser = Parser {
+        tokens,
+        position: 0,
+        lookup,
+    };
+
+    let value = parser.expression()?;
+    match parser.tokens.get(parser.position) {
+        None => Ok(value),
+        Some(token) => Err(format!("unexpected token '{token}'")),
+    }
+}
+
+/// Formats values without fraction as integers (e.g. "1536" instead of "1536.0"), so computed
+/// values pass the validation of integer properties.
+pub(crate) fn format_value(value: f64) -> String {
+    if value.fract() == 0.0 && value.abs() < i64::MAX as f64 {
+        (value as i64).to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Number(f64),
+    Reference(String),
+    Operator(char),
+    OpenParen,
+    CloseParen,
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Number(number) => write!(f, "{number}"),
+            Token::Reference(name) => write!(f, "{name}"),
+            Token::Operator(operator) => write!(f, "{operator}"),
+            Token::OpenParen => f.write_str("("),
+            Token::CloseParen => f.write_str(")"),
+        }
+    }
+}
+
+fn tokenize(expression: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = vec![];
+    let mut chars = expression.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '+' | '-' | '*' | '/' => {
+                tokens.push(Token::Operator(c));
+                chars.next();
+            }
+            '(' => {
+                tokens.push(Token::OpenParen);
+                chars.next();
+            }
+            ')' => {
+                tokens.push(Token::CloseParen);
+                chars.next();
+            }
+            c if c.is_ascii_digit() => {
+                let mut literal = String::new();
+                while let Some(&c) = chars.peek() {
+                    if !(c.is_ascii_digit() || c == '.') {
+                        break;
+                    }
+                    literal.push(c);
+                    chars.next();
+                }
+                let number = literal
+                    .parse()
+                    .map_err(|_| format!("invalid number '{literal}'"))?;
+                tokens.push(Token::Number(number));
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let mut name = String::new();
+                while let Some(&c) = chars.peek() {
+                    if !(c.is_ascii_alphanumeric() || c == '_' || c == '.') {
+                        break;
+                    }
+                    name.push(c);
+                    chars.next();
+                }
+                tokens.push(Token::Reference(name));
+            }
+            c => return Err(format!("unexpected character '{c}'")),
+        }
+    }
+
+    Ok(tokens)
+}
+
+// A recursive descent parser evaluating while parsing:
+// expression = term (("+" | "-") term)*
+// term       = factor (("*" | "/") factor)*
+// factor     = "-" factor | number | reference | "(" expression ")"
+struct Parser<F> {
+    tokens: Vec<Token>,
+    position: usize,
+    lookup: F,
+}
+
+impl<F> Parser<F>
+where
+    F: Fn(&str) -> Result<f64, String>,
+{
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn next_operator(&mut self, operators: &[char]) -> Option<char> {
+        match self.tokens.get(self.position) {
+            Some(Token::Operator(operator)) if operators.contains(operator) => {
+                self.position += 1;
+                Some(*operator)
+            }
+            _ => None,
+        }
+    }
+
+    fn expression(&mut self) -> Result<f64, String> {
+        let mut value = self.term()?;
+        while let Some(operator) = self.next_operator(&['+', '-']) {
+            let rhs = self.term()?;
+            value = if operator == '+' {
+                value + rhs
+            } else {
+                value - rhs
+            };
+        }
+        Ok(value)
+    }
+
+    fn term(&mut self) -> Result<f64, String> {
+        let mut value = self.factor()?;
+        while let Some(operator) = self.next_operator(&['*', '/']) {
+            let rhs = self.factor()?;
+            value = if operator == '*' {
+                value * rhs
+            } else if rhs == 0.0 {
+                return Err("division by zero".to_string());
+            } else {
+                value / rhs
+            };
+        }
+        Ok(value)
+    }
+
+    fn factor(&mut self) -> Result<f64, String> {
+        match self.next() {
+            Some(Token::Operator('-')) => Ok(-self.factor()?),
+            Some(Token::Number(number)) => Ok(number),
+            Some(Token::Reference(name)) => (self.lookup)(&name),
+            Some(Token::OpenParen) => {
+                let value = self.expression()?;
+                match self.next() {
+                    Some(Token::CloseParen) => Ok(value),
+                    _ => Err("missing closing parenthesis".to_string()),
+                }
+            }
+            Some(token) => Err(format!("unexpected token '{token}'")),
+            None => Err("unexpected end of expression".to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn lookup(name: &str) -> Result<f64, String> {
+        match name {
+            "HEAP" => Ok(1024.0),
+            "conf.off.heap" => Ok(512.0),
+            "ZERO" => Ok(0.0),
+            _ => Err(format!("unknown reference '{name}'")),
+        }
+    }
+
+    #[rstest]
+    #[case::sum("HEAP + conf.off.heap", Ok(1536.0))]
+    #[case::precedence("HEAP + conf.off.heap * 2", Ok(2048.0))]
+    #[case::parentheses("(HEAP + conf.off.heap) * 2", Ok(3072.0))]
+    #[case::unary_minus("-HEAP + 2048", Ok(1024.0))]
+    #[case::float("HEAP / 3.2", Ok(320.0))]
+    #[case::division_by_zero("HEAP / ZERO", Err("division by zero".to_string()))]
+    #[case::unknown_reference("HEAP + UNKNOWN", Err("unknown reference 'UNKNOWN'".to_string()))]
+    #[case::missing_parenthesis("(HEAP + 1", Err("missing closing parenthesis".to_string()))]
+    #[case::trailing_operator("HEAP +", Err("unexpected end of expression".to_string()))]
+    #[case::trailing_token("HEAP 1", Err("unexpected token '1'".to_string()))]
+    #[case::invalid_character("HEAP % 2", Err("unexpected character '%'".to_string()))]
+    fn test_evaluate(#[case] expression: &str, #[case] expected: Result<f64, String>) {
+        assert_eq!(evaluate(expression, lookup), expected);
+    }
+}
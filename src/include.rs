@@ -0,0 +1,95 @@
+//! Resolves the includes of product config files (see [`crate::types::ProductConfig::includes`]),
+//! which split shared property groups into reusable fragment files.
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::error::Error;
+use crate::types::{ProductConfig, PropertyAnchor, Spec};
+use crate::validation::ValidationResult;
+
+/// A product config fragment referenced via `includes`. Fragments may declare their own units
+/// (YAML anchors do not work across files) and include further fragments.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Fragment {
+    spec: Option<Spec>,
+    #[serde(default)]
+    properties: Vec<PropertyAnchor>,
+    #[serde(default)]
+    includes: Vec<String>,
+}
+
+/// Merges the units and properties of all (transitively) included fragments into the config.
+/// Include paths are relative to the including file. Fragments included multiple times are
+/// only merged once, cyclic includes are rejected with [`Error::CyclicInclude`].
+///
+/// # Arguments
+///
+/// * `config` - the product config loaded from `file`
+/// * `file` - the path of the product config file
+///
+pub(crate) fn resolve_includes(config: &mut ProductConfig, file: &Path) -> ValidationResult<()> {
+    let includes = std::mem::take(&mut config.includes);
+    let canonical = canonicalize(file)?;
+    let mut chain = vec![(file.to_path_buf(), canonical.clone())];
+    let mut visited = BTreeSet::from([canonical]);
+    merge_includes(config, &includes, &mut chain, &mut visited)
+}
+
+/// Merges the included fragments of the last file of the include chain (pairs of the file
+/// path and its canonical path) recursively.
+fn merge_includes(
+    config: &mut ProductConfig,
+    includes: &[String],
+    chain: &mut Vec<(PathBuf, PathBuf)>,
+    visited: &mut BTreeSet<PathBuf>,
+) -> ValidationResult<()> {
+    let base = match chain.last() {
+        Some((file, _)) => file.parent().unwrap_or_else(|| Path::new("")).to_path_buf(),
+        None => PathBuf::new(),
+    };
+
+    for include in includes {
+        let fragment_file = base.join(include);
+        let canonical = canonicalize(&fragment_file)?;
+
+        if chain.iter().any(|(_, file)| *file == canonical) {
+            let mut files: Vec<PathBuf> = chain.iter().map(|(file, _)| file.clone()).collect();
+            files.push(fragment_file);
+            return Err(Error::CyclicInclude { chain: files });
+        }
+        if !visited.insert(canonical.clone()) {
+            continue;
+        }
+
+        let contents = fs::read_to_string(&fragment_file).map_err(|_| Error::FileNotFound {
+            file_name: fragment_file.clone(),
+        })?;
+        let fragment: Fragment =
+            serde_yaml::from_str(&contents).map_err(|serde_error| Error::YamlFileNotParsable {
+                file: fragment_file.clone(),
+                reason: serde_error.to_string(),
+                source: serde_error.into(),
+            })?;
+
+        if let Some(spec) = fragment.spec {
+            config.spec.merge(spec);
+        }
+        config.properties.extend(fragment.properties);
+
+        chain.push((fragment_file, canonical));
+        merge_includes(config, &fragment.includes, chain, visited)?;
+        chain.pop();
+    }
+
+    Ok(())
+}
+
+fn canonicalize(file: &Path) -> ValidationResult<PathBuf> {
+    file.canonicalize().map_err(|_| Error::FileNotFound {
+        file_name: file.to_path_buf(),
+    })
+}
@@ -11,29 +11,46 @@
 //! - apply mode for config changes (e.g. restart)
 //! - additional information like web links or descriptions
 //!
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::path::Path;
 use std::string::String;
+use std::sync::{Mutex, PoisonError};
 use std::{fs, str};
 
+use schemars::schema::RootSchema;
 use semver::Version;
+use snafu::ResultExt;
 
-use crate::error::Error;
-use crate::types::{ProductConfig, PropertyName, PropertyNameKind, PropertySpec, StackableVersion};
+use crate::error::{ConfigError, Error};
+use crate::results::ConfigStats;
+use crate::types::{
+    ApplyMode, ExplicitNone, ProductConfig, PropertyAnchor, PropertyName, PropertyNameKind,
+    PropertySpec, StackableVersion, REPEATED_VALUE_SEPARATOR,
+};
 use crate::util::expand_properties;
 use crate::validation::{check_allowed_values, ValidationResult};
+use crate::writer::OutputFormat;
 use std::ops::Deref;
 use std::str::FromStr;
 
+pub mod de;
 pub mod error;
 pub mod flask_app_config_writer;
+pub mod results;
 pub mod ser;
 pub mod types;
 pub mod writer;
 
+mod formula;
+mod include;
+mod self_check;
 mod util;
 mod validation;
 
+/// The name of the unit marking port properties, see
+/// [`ProductConfigManager::get_with_reserved_ports`].
+pub const PORT_UNIT_NAME: &str = "port";
+
 /// This will be returned for every validated configuration value (including user values
 /// and automatically added values from e.g. dependency, recommended etc.).
 #[derive(Clone, Debug, PartialOrd, PartialEq)]
@@ -54,6 +71,134 @@ pub enum PropertyValidationResult {
     /// On error, check the provided config and config values.
     /// Should never be used like this!
     Error(String, Error),
+    /// On errors, multiple checks failed for the value (e.g. out of bounds and not in the
+    /// allowed values). Contains all errors.
+    Errors(String, Vec<Error>),
+}
+
+impl PropertyValidationResult {
+    /// Returns the value of the validation result (the provided value for errors).
+    pub fn value(&self) -> &str {
+        match self {
+            PropertyValidationResult::Default(value)
+            | PropertyValidationResult::RecommendedDefault(value)
+            | PropertyValidationResult::Valid(value)
+            | PropertyValidationResult::Unknown(value)
+            | PropertyValidationResult::Warn(value, _)
+            | PropertyValidationResult::Error(value, _)
+            | PropertyValidationResult::Errors(value, _) => value,
+        }
+    }
+
+    /// Returns the (first) error of [`PropertyValidationResult::Error`] and
+    /// [`PropertyValidationResult::Errors`].
+    pub fn first_error(&self) -> Option<&Error> {
+        match self {
+            PropertyValidationResult::Error(_, err) => Some(err),
+            PropertyValidationResult::Errors(_, errors) => errors.first(),
+            _ => None,
+        }
+    }
+
+    /// Returns [`PropertyValidationResult::Error`] for a single error and
+    /// [`PropertyValidationResult::Errors`] for multiple errors.
+    fn from_errors(value: String, mut errors: Vec<Error>) -> Self {
+        if errors.len() == 1 {
+            PropertyValidationResult::Error(value, errors.remove(0))
+        } else {
+            PropertyValidationResult::Errors(value, errors)
+        }
+    }
+}
+
+/// The validation results per role and kind returned by [`ProductConfigManager::get_all`].
+pub type RoleKindValidationResults =
+    BTreeMap<String, BTreeMap<PropertyNameKind, BTreeMap<String, PropertyValidationResult>>>;
+
+/// The merged (raw) values and the validation results of the whole validation pipeline.
+type MergedValidationResults = (
+    BTreeMap<String, Option<String>>,
+    BTreeMap<String, PropertyValidationResult>,
+);
+
+/// This will be returned by [`ProductConfigManager::get_detailed`] for every validated
+/// configuration value and additionally contains the raw value before normalization,
+/// e.g. for auditing.
+#[derive(Clone, Debug, PartialOrd, PartialEq)]
+pub struct DetailedValidationResult {
+    /// The value as provided by the user (or the product config for default and recommended
+    /// values) before normalization.
+    pub raw: String,
+    /// The normalized value (e.g. CPU quantities in millicores). Equals the raw value for
+    /// errors and values without normalization.
+    pub normalized: String,
+    /// The validation result containing the normalized value.
+    pub result: PropertyValidationResult,
+}
+
+/// The documentation of a property returned by [`ProductConfigManager::property_docs`], e.g. to
+/// show documentation next to config fields.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PropertyDocs {
+    /// See [`PropertySpec::description`]
+    pub description: Option<String>,
+    /// See [`PropertySpec::comment`]
+    pub comment: Option<String>,
+    /// Additional documentation like web links, see [`PropertySpec::additional_doc`]
+    pub additional_doc: Vec<String>,
+}
+
+/// A deprecated property found by [`ProductConfigManager::migrate`] and how it was handled.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Migration {
+    /// The deprecated property was replaced by its single replacement, keeping the value.
+    Replaced { from: String, to: String },
+    /// The deprecated property was left untouched, because its replacement is already set.
+    ReplacementAlreadySet { from: String, to: String },
+    /// The deprecated property was left untouched, because it has several replacements.
+    AmbiguousReplacements {
+        from: String,
+        replacements: Vec<String>,
+    },
+    /// The deprecated property was left untouched, because it has no replacement.
+    NoReplacement { from: String },
+}
+
+/// The migrated user config and the applied (or skipped) migrations returned by
+/// [`ProductConfigManager::migrate`].
+pub type MigrationResult = (HashMap<String, Option<String>>, Vec<Migration>);
+
+/// Changes of a user config, e.g. computed during reconciliation, which are validated via
+/// [`ProductConfigManager::validate_change_set`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ChangeSet {
+    /// Properties to be added with their value
+    pub added: BTreeMap<String, Option<String>>,
+    /// Properties to be updated with their new value
+    pub updated: BTreeMap<String, Option<String>>,
+    /// Properties to be removed
+    pub removed: BTreeSet<String>,
+}
+
+impl ChangeSet {
+    /// Returns the names of all added, updated and removed properties.
+    pub fn changed_properties(&self) -> BTreeSet<&String> {
+        self.added
+            .keys()
+            .chain(self.updated.keys())
+            .chain(&self.removed)
+            .collect()
+    }
+}
+
+/// The result of [`ProductConfigManager::validate_change_set`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChangeSetValidation {
+    /// The validation results of the user config with the changes applied
+    pub results: BTreeMap<String, PropertyValidationResult>,
+    /// The changed properties that require a restart of the product, i.e. with
+    /// [`ApplyMode::Restart`] and a different validated value than before
+    pub restart_required: BTreeSet<String>,
 }
 
 /// The struct to interact with the product config. Reads and parses a YAML product configuration.
@@ -61,29 +206,84 @@ pub enum PropertyValidationResult {
 /// in the YAML product configuration.
 pub struct ProductConfigManager {
     config: ProductConfig,
+    // Only set if access tracking is enabled via `with_access_tracking`. Uses a Mutex to record
+    // the emitted property names from `get`, which only borrows the manager.
+    access_log: Option<Mutex<BTreeSet<String>>>,
+    // Properties with a feature gate are only applicable if the feature is enabled.
+    enabled_features: BTreeSet<String>,
+    // Applied to every value before validation if set via `with_value_transformer`.
+    value_transformer: Option<Box<ValueTransformer>>,
+    // Properties are looked up by all their names if the name does not match the kind.
+    cross_kind_lookup: bool,
+    // Optional properties without any value are reported via `with_unresolved_value_warnings`.
+    unresolved_value_warnings: bool,
+    // How user config keys without a value are handled, set via `with_explicit_none`.
+    explicit_none: ExplicitNone,
+    // Product versions are matched as their release (without pre-release and build metadata).
+    prerelease_as_release: bool,
+    // Product versions with less than three components are coerced, see `with_lenient_versions`.
+    lenient_versions: bool,
+    // Empty values of required properties are rejected, see `with_non_empty_if_required`.
+    non_empty_if_required: bool,
+    // Registered via `with_allowed_values_provider` and referenced by properties via
+    // `allowed_values_provider`.
+    allowed_values_providers: BTreeMap<String, Box<AllowedValuesProvider>>,
 }
 
+/// Transforms the raw value of a property (property name, raw value) before validation.
+pub type ValueTransformer = dyn Fn(&str, &str) -> String + Send + Sync;
+
+/// Provides the allowed values of a property at validation time, e.g. fetched from a cluster.
+pub type AllowedValuesProvider = dyn Fn() -> Vec<String> + Send + Sync;
+
 impl FromStr for ProductConfigManager {
     type Err = error::Error;
-    /// Create a ProductConfig from a YAML string.
+    /// Create a ProductConfig from a YAML string. Includes can not be resolved without the path
+    /// of the file and are rejected with [`Error::IncludesNotSupported`], use
+    /// [`ProductConfigManager::from_yaml_file`] instead.
     ///
     /// # Arguments
     ///
     /// * `contents` - the YAML string content
     fn from_str(contents: &str) -> ValidationResult<Self> {
-        Ok(ProductConfigManager {
-            config: serde_yaml::from_str(contents).map_err(|serde_error| {
-                error::Error::YamlNotParsable {
-                    content: contents.to_string(),
-                    reason: serde_error.to_string(),
-                }
-            })?,
-        })
+        let config: ProductConfig = serde_yaml::from_str(contents).map_err(|serde_error| {
+            error::Error::YamlNotParsable {
+                content: contents.to_string(),
+                reason: serde_error.to_string(),
+                source: serde_error.into(),
+            }
+        })?;
+        if !config.includes.is_empty() {
+            return Err(error::Error::IncludesNotSupported {
+                includes: config.includes,
+            });
+        }
+        Ok(Self::from(config))
+    }
+}
+
+impl From<ProductConfig> for ProductConfigManager {
+    /// Create a ProductConfigManager from an already parsed ProductConfig.
+    fn from(config: ProductConfig) -> Self {
+        ProductConfigManager {
+            config,
+            access_log: None,
+            enabled_features: BTreeSet::new(),
+            value_transformer: None,
+            cross_kind_lookup: false,
+            unresolved_value_warnings: false,
+            explicit_none: ExplicitNone::default(),
+            prerelease_as_release: false,
+            lenient_versions: false,
+            non_empty_if_required: false,
+            allowed_values_providers: BTreeMap::new(),
+        }
     }
 }
 
 impl ProductConfigManager {
-    /// Create a ProductConfig from a YAML file.
+    /// Create a ProductConfig from a YAML file. The units and properties of the fragment
+    /// files listed in `includes` (relative to the file) are merged into the product config.
     ///
     /// # Arguments
     ///
@@ -93,12 +293,276 @@ impl ProductConfigManager {
             file_name: file_path.as_ref().to_path_buf(),
         })?;
 
-        Self::from_str(&contents).map_err(|serde_error| error::Error::YamlFileNotParsable {
-            file: file_path.as_ref().to_path_buf(),
-            reason: serde_error.to_string(),
+        let mut config: ProductConfig = serde_yaml::from_str(&contents).map_err(|serde_error| {
+            error::Error::YamlFileNotParsable {
+                file: file_path.as_ref().to_path_buf(),
+                reason: serde_error.to_string(),
+                source: serde_error.into(),
+            }
+        })?;
+        include::resolve_includes(&mut config, file_path.as_ref())?;
+        Ok(Self::from(config))
+    }
+
+    /// Create a ProductConfigManager from a YAML string and run all checks of
+    /// [`ProductConfigManager::self_check`] and [`ProductConfigManager::self_check_warnings`]
+    /// on it. This is the recommended strict loader e.g. for CI, warnings are treated as
+    /// errors. All found problems are returned via [`Error::SelfCheckFailed`].
+    ///
+    /// # Arguments
+    ///
+    /// * `contents` - the YAML string content
+    pub fn load_validated(contents: &str) -> ValidationResult<Self> {
+        let manager = Self::from_str(contents)?;
+        let mut errors = manager.self_check();
+        errors.extend(manager.self_check_warnings());
+        if !errors.is_empty() {
+            return Err(error::Error::SelfCheckFailed { errors });
+        }
+        Ok(manager)
+    }
+
+    /// Returns the JSON schema of the product config file format, e.g. to validate product
+    /// config YAML files in editors.
+    pub fn json_schema() -> RootSchema {
+        schemars::schema_for!(ProductConfig)
+    }
+
+    /// Enables recording of all known property names emitted by [`ProductConfigManager::get`].
+    /// The recorded names can be retrieved via [`ProductConfigManager::accessed_properties`] and
+    /// [`ProductConfigManager::unaccessed_properties`] e.g. to detect dead properties.
+    pub fn with_access_tracking(mut self) -> Self {
+        self.access_log = Some(Mutex::new(BTreeSet::new()));
+        self
+    }
+
+    /// Enables the given product features. Properties with a feature gate (see
+    /// [`PropertySpec::feature_gate`]) are skipped unless their feature is enabled, which allows
+    /// one product config to serve multiple build variants of a product.
+    ///
+    /// # Arguments
+    ///
+    /// * `features` - the features the product was built with
+    pub fn with_features<I, S>(mut self, features: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.enabled_features
+            .extend(features.into_iter().map(Into::into));
+        self
+    }
+
+    /// Transforms every value before validation, e.g. to decode values or trim prefixes the
+    /// product config cannot know about. The transformer is called with the property name and
+    /// the raw value. The transformed value is validated and returned instead of the raw value.
+    ///
+    /// # Arguments
+    ///
+    /// * `transformer` - the function transforming the property name and raw value
+    pub fn with_value_transformer<F>(mut self, transformer: F) -> Self
+    where
+        F: Fn(&str, &str) -> String + Send + Sync + 'static,
+    {
+        self.value_transformer = Some(Box::new(transformer));
+        self
+    }
+
+    /// Registers a provider for dynamic allowed values (e.g. the storage classes available in a
+    /// cluster) under the given name. Properties reference the provider via
+    /// [`PropertySpec::allowed_values_provider`] and their values are checked against the
+    /// provided values at validation time, in addition to the static allowed values. Like static
+    /// allowed values, an empty list allows all values.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the name the provider is referenced by
+    /// * `provider` - the function returning the allowed values
+    pub fn with_allowed_values_provider<S, F>(mut self, name: S, provider: F) -> Self
+    where
+        S: Into<String>,
+        F: Fn() -> Vec<String> + Send + Sync + 'static,
+    {
+        self.allowed_values_providers
+            .insert(name.into(), Box::new(provider));
+        self
+    }
+
+    /// Validates user provided properties that are not declared for the requested kind, but for
+    /// another kind (e.g. an environment variable name passed for a config file), against that
+    /// property instead of returning them as [`PropertyValidationResult::Unknown`] overrides.
+    pub fn with_cross_kind_lookup(mut self) -> Self {
+        self.cross_kind_lookup = true;
+        self
+    }
+
+    /// Reports optional properties of the requested role and kind that are absent from the
+    /// result because neither the user nor the product config (recommended or default value)
+    /// provides a value. They are added as [`PropertyValidationResult::Warn`] with an empty
+    /// value and [`Error::NoValueResolved`], which helps to debug why a property does not appear.
+    /// Meant for debugging only: writers would emit the empty values.
+    pub fn with_unresolved_value_warnings(mut self) -> Self {
+        self.unresolved_value_warnings = true;
+        self
+    }
+
+    /// Sets how user config keys that are present without a value (`None`) are handled. By
+    /// default ([`ExplicitNone::Unset`]) such a key forces the property unset, which is reported
+    /// as [`Error::PropertyValueMissing`]. With [`ExplicitNone::UseDefault`] the recommended or
+    /// default value of the property is used instead and reported as
+    /// [`PropertyValidationResult::Default`]. Unlike an absent key this also adds optional
+    /// properties, since only required properties fall back to their defaults if absent.
+    ///
+    /// # Arguments
+    ///
+    /// * `explicit_none` - the handling of user config keys without a value
+    pub fn with_explicit_none(mut self, explicit_none: ExplicitNone) -> Self {
+        self.explicit_none = explicit_none;
+        self
+    }
+
+    /// Treats pre-release product versions (and versions with build metadata) like their
+    /// release, e.g. "1.2.0-rc1" like "1.2.0". By default, semver ordering applies and
+    /// "1.2.0-rc1" does not support properties introduced in "1.2.0". This affects all version
+    /// checks (supported, deprecated, removed) as well as the default and recommended values.
+    /// Errors (e.g. [`Error::VersionDeprecated`]) report the release version.
+    pub fn with_prerelease_as_release(mut self) -> Self {
+        self.prerelease_as_release = true;
+        self
+    }
+
+    /// Accepts product versions with only one or two components (e.g. "1" or "1.5") of legacy
+    /// products that do not use SemVer, which are coerced into "1.0.0" or "1.5.0" (see
+    /// [`StackableVersion::parse_lenient`]). By default, such versions are rejected with
+    /// [`Error::InvalidVersion`]. Only the product version passed to the manager is coerced, the
+    /// versions in the product config must still be valid SemVer.
+    pub fn with_lenient_versions(mut self) -> Self {
+        self.lenient_versions = true;
+        self
+    }
+
+    /// Rejects empty (or whitespace only) values of properties that are required for the
+    /// requested role with [`Error::PropertyValueEmpty`]. By default, an empty value satisfies
+    /// a required property as long as it passes the datatype checks.
+    pub fn with_non_empty_if_required(mut self) -> Self {
+        self.non_empty_if_required = true;
+        self
+    }
+
+    /// Parses the product version, coercing it if enabled via `with_lenient_versions` and
+    /// stripping pre-release and build metadata if enabled via `with_prerelease_as_release`.
+    fn parse_version(&self, version: &str) -> ValidationResult<StackableVersion> {
+        let product_version = if self.lenient_versions {
+            StackableVersion::parse_lenient(version)?
+        } else {
+            StackableVersion::parse(version)?
+        };
+        Ok(if self.prerelease_as_release {
+            product_version.release()
+        } else {
+            product_version
         })
     }
 
+    /// Limits the backtracking of all unit regexes when evaluating values. Backtracking regexes
+    /// (e.g. using look-around) may take exponential time on adversarial values. If the limit is
+    /// exceeded, the value is rejected with [`Error::RegexNotEvaluable`] instead of blocking.
+    /// If not set, the default limit of `fancy_regex` is used.
+    ///
+    /// # Arguments
+    ///
+    /// * `limit` - the maximum number of backtracking steps per evaluation
+    pub fn with_regex_backtrack_limit(mut self, limit: usize) -> ValidationResult<Self> {
+        self.config.spec.set_regex_backtrack_limit(limit)?;
+        for anchor in &mut self.config.properties {
+            anchor.property.set_regex_backtrack_limit(limit)?;
+        }
+        Ok(self)
+    }
+
+    /// Returns all known property names that were emitted by [`ProductConfigManager::get`] so far.
+    /// Always empty if access tracking is not enabled.
+    pub fn accessed_properties(&self) -> BTreeSet<String> {
+        match &self.access_log {
+            Some(access_log) => access_log
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clone(),
+            None => BTreeSet::new(),
+        }
+    }
+
+    /// Returns all property names declared in the product config that were never emitted by
+    /// [`ProductConfigManager::get`] so far.
+    /// Contains all declared property names if access tracking is not enabled.
+    pub fn unaccessed_properties(&self) -> BTreeSet<String> {
+        let accessed = self.accessed_properties();
+        self.config
+            .properties
+            .iter()
+            .flat_map(|property| property.all_property_names())
+            .filter(|name| !accessed.contains(name))
+            .collect()
+    }
+
+    /// Checks the product config itself for authoring errors and returns all found problems.
+    /// This does not depend on any user input and is intended to be run e.g. in CI on the
+    /// product config YAML. The following checks are performed:
+    /// * default and recommended values must be contained in the allowed values or match the
+    ///   allowed value patterns (if provided)
+    /// * allowed values must not contain duplicates
+    /// * default and recommended values must match the datatype (including its bounds)
+    /// * the `min` bound of datatypes must not be greater than the `max` bound
+    /// * property names must be unique per config file
+    /// * roles referenced by expansions must be defined by a property
+    /// * explicit expansion values must be allowed values and match the datatype of the
+    ///   expanded property
+    /// * accepted and default units must be known unit suffixes
+    /// * unit examples must match the unit regex
+    /// * unit regexes must be anchored at the start (^) and end ($)
+    /// * `as_of_version`, `deprecated_since` and `removed_in` of a property as well as
+    ///   `from_version` and `to_version` of default and recommended values must be in order
+    pub fn self_check(&self) -> Vec<Error> {
+        self_check::check_all(&self.config)
+    }
+
+    /// Returns the examples of the unit with the given name (declared in the spec or used in a
+    /// datatype), e.g. to generate documentation. Returns None if the unit is unknown or has
+    /// no examples. Use [`ProductConfigManager::self_check`] or
+    /// [`ProductConfigManager::load_validated`] to ensure that the examples match the unit regex.
+    ///
+    /// # Arguments
+    ///
+    /// * `unit_name` - the name of the unit, e.g. "port"
+    pub fn unit_examples(&self, unit_name: &str) -> Option<Vec<String>> {
+        self_check::all_units(&self.config)
+            .find(|unit| unit.name == unit_name)?
+            .examples
+            .clone()
+    }
+
+    /// Checks that all property names of a config file ([`PropertyNameKind::File`]) follow the
+    /// naming convention provided for that file, e.g. dotted lowercase names. Like
+    /// [`ProductConfigManager::self_check`], this is intended to be run e.g. in CI on the
+    /// product config YAML.
+    ///
+    /// # Arguments
+    ///
+    /// * `conventions` - map with file name and respective regular expression the property
+    ///   names of that file must match
+    pub fn self_check_file_naming(&self, conventions: &BTreeMap<String, String>) -> Vec<Error> {
+        self_check::check_file_property_names(&self.config, conventions)
+    }
+
+    /// Checks the product config itself for problems that do not prevent it from working but
+    /// should be looked at. Contrary to [`ProductConfigManager::self_check`], the returned
+    /// problems are warnings. The following checks are performed:
+    /// * unit regexes should only use features that are supported by the `regex` crate as well
+    ///   (e.g. no look-around or backreferences) to stay portable
+    pub fn self_check_warnings(&self) -> Vec<Error> {
+        self_check::check_unit_regexes_portable(&self.config)
+    }
+
     /// This function merges the user provided configuration properties with the product configuration
     /// and validates the result, both in a single step. The caller is expected to look at each
     /// [PropertyValidationResult] and take the appropriate action based on the product requirements.
@@ -138,613 +602,4819 @@ impl ProductConfigManager {
         kind: &PropertyNameKind,
         user_config: HashMap<String, Option<String>>,
     ) -> ValidationResult<BTreeMap<String, PropertyValidationResult>> {
-        let product_version = StackableVersion::parse(version)?;
+        self.get_ref(version, role, kind, &user_config)
+    }
+
+    /// Same as [`ProductConfigManager::get`] but borrows the user config instead of taking
+    /// ownership. User values are only cloned when inserted into the result, which avoids
+    /// cloning the whole user config in the caller e.g. in reconcile loops.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - the current product version
+    /// * `role` - role provided by the user
+    /// * `kind` - kind provided by the user
+    /// * `user_config` - map with property name and values (the explicit user config properties)
+    pub fn get_ref(
+        &self,
+        version: &str,
+        role: &str,
+        kind: &PropertyNameKind,
+        user_config: &HashMap<String, Option<String>>,
+    ) -> ValidationResult<BTreeMap<String, PropertyValidationResult>> {
+        let product_version = self.parse_version(version)?;
+        self.get_for_version(&product_version, role, kind, user_config)
+    }
+
+    /// Same as [`ProductConfigManager::get_ref`] but validates the user config of multiple
+    /// roles and kinds at once, parsing the version only once. Returns the results per role
+    /// and kind.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - the current product version
+    /// * `user_config_by_role_kind` - map with the user config (property name and values) per
+    ///   role and kind
+    pub fn get_all(
+        &self,
+        version: &str,
+        user_config_by_role_kind: &HashMap<
+            (String, PropertyNameKind),
+            HashMap<String, Option<String>>,
+        >,
+    ) -> ValidationResult<RoleKindValidationResults> {
+        let product_version = self.parse_version(version)?;
+
+        let mut result: BTreeMap<String, BTreeMap<_, _>> = BTreeMap::new();
+        for ((role, kind), user_config) in user_config_by_role_kind {
+            let validated = self.get_for_version(&product_version, role, kind, user_config)?;
+            result
+                .entry(role.clone())
+                .or_default()
+                .insert(kind.clone(), validated);
+        }
+        Ok(result)
+    }
+
+    fn get_for_version(
+        &self,
+        product_version: &Version,
+        role: &str,
+        kind: &PropertyNameKind,
+        user_config: &HashMap<String, Option<String>>,
+    ) -> ValidationResult<BTreeMap<String, PropertyValidationResult>> {
+        let (_, result) = self.merge_and_validate(product_version, role, kind, user_config)?;
+        Ok(result)
+    }
 
+    /// Runs the whole pipeline of [`ProductConfigManager::get`] (merge, expand, compute,
+    /// validate and warn) and returns the merged (raw) values alongside the validation results.
+    ///
+    /// # Arguments
+    ///
+    /// * `product_version` - the current product version
+    /// * `role` - role provided by the user
+    /// * `kind` - kind provided by the user
+    /// * `user_config` - map with property name and values (the explicit user config properties)
+    fn merge_and_validate(
+        &self,
+        product_version: &Version,
+        role: &str,
+        kind: &PropertyNameKind,
+        user_config: &HashMap<String, Option<String>>,
+    ) -> ValidationResult<MergedValidationResults> {
         // merge provided user properties with extracted property spec via role / kind and
         // dependencies to be validated later.
-        let merged_properties = self
-            .get_and_expand_properties(&product_version, role, kind, user_config)
-            .unwrap();
+        let mut merged_properties =
+            self.get_and_expand_properties(product_version, role, kind, user_config)?;
+        let computation_errors = self.compute_properties(
+            product_version,
+            role,
+            kind,
+            user_config,
+            &mut merged_properties,
+        )?;
+        let raw_values = merged_properties.clone();
 
-        self.validate(&product_version, role, kind, merged_properties)
+        let mut result = self.validate(product_version, role, kind, merged_properties)?;
+        result.extend(computation_errors);
+        self.warn_overridden_expansions(product_version, role, kind, user_config, &mut result)?;
+        self.warn_unresolved_values(product_version, role, kind, &mut result)?;
+        self.record_access(&result);
+        Ok((raw_values, result))
     }
 
-    /// Merge the provided user config properties with the product configuration (loaded from YAML)
-    /// depending on kind, role and version. The user configuration has the highest priority, followed
-    /// by the recommended values from the product configuration. Finally, if none are available,
-    /// the default values from the product configuration are used.
-    /// This function also expands properties if they are required for the given role or if the user
-    /// has requested so in the `user_config` parameter.
-    ///
+    /// Same as [`ProductConfigManager::get_ref`] but additionally rejects values of port
+    /// properties (see [`crate::types::Datatype::is_port`]) that collide with the provided
+    /// reserved ports, e.g. ports already assigned by an operator. Every element of repeatable
+    /// properties is checked.
     ///
     /// # Arguments
     ///
     /// * `version` - the current product version
-    /// * `role` - property role provided by the user
-    /// * `kind` - property name kind provided by the user
+    /// * `role` - role provided by the user
+    /// * `kind` - kind provided by the user
     /// * `user_config` - map with property name and values (the explicit user config properties)
-    pub(crate) fn get_and_expand_properties(
+    /// * `reserved` - the ports that are already in use
+    pub fn get_with_reserved_ports(
         &self,
-        version: &Version,
+        version: &str,
         role: &str,
         kind: &PropertyNameKind,
-        user_config: HashMap<String, Option<String>>,
-    ) -> ValidationResult<BTreeMap<String, Option<String>>> {
-        let mut merged_properties = BTreeMap::new();
+        user_config: &HashMap<String, Option<String>>,
+        reserved: &HashSet<u16>,
+    ) -> ValidationResult<BTreeMap<String, PropertyValidationResult>> {
+        let product_version = self.parse_version(version)?;
+        let mut result = self.get_ref(version, role, kind, user_config)?;
 
-        for property in &self.config.properties {
-            let property_names = property.all_property_names();
-            // If user provides a property that exists in the product config and fits the role and
-            // version, we have to expand if needed.
-            if util::hashmap_contains_any_key(&user_config, &property_names)
-                && property.has_role(role)
-                && property.is_version_supported(version)?
+        for (name, value) in result.iter_mut() {
+            if let PropertyValidationResult::Error(..)
+            | PropertyValidationResult::Errors(..)
+            | PropertyValidationResult::Unknown(_) = value
             {
-                merged_properties.extend(expand_properties(property, version, role, kind)?);
-            // If the user does not provide a property which is required in the product config,
-            // and fits the role and version, we have to expand if needed.
-            } else if property.has_role_required(role) && property.is_version_supported(version)? {
-                if let Some((name, value)) = property.recommended_or_default(version, kind) {
-                    merged_properties.insert(name, value);
-                }
-                merged_properties.extend(expand_properties(property, version, role, kind)?);
+                continue;
             }
-        }
 
-        // Add any unknown (not found in product config) properties provided by the user -> Overrides
-        merged_properties.extend(user_config);
+            let Some(property) = self.find_property(name, role, kind, &product_version) else {
+                continue;
+            };
+            if !property.datatype_for_kind(kind).is_port() {
+                continue;
+            }
 
-        // The user can provide "Meta" properties, that do not exists on their own and only expand
-        // into other "valid" properties. Therefore it requires the "no_copy" field to indicate
-        // that it should not end up in the final configuration.
-        Ok(self.remove_no_copy_properties(version, role, kind, &merged_properties))
+            let mut errors: Vec<Error> = property
+                .value_elements(value.value())
+                .into_iter()
+                .filter_map(|element| element.parse::<u16>().ok())
+                .filter(|port| reserved.contains(port))
+                .map(|port| Error::PortAlreadyReserved {
+                    property_name: name.clone(),
+                    port,
+                })
+                .collect();
+
+            let raw = value.value().to_string();
+            *value = match errors.len() {
+                0 => continue,
+                1 => PropertyValidationResult::Error(raw, errors.remove(0)),
+                _ => PropertyValidationResult::Errors(raw, errors),
+            };
+        }
+        Ok(result)
     }
 
-    fn remove_no_copy_properties(
+    /// Same as [`ProductConfigManager::get_ref`] but additionally returns the raw value
+    /// alongside the normalized value for every property.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - the current product version
+    /// * `role` - role provided by the user
+    /// * `kind` - kind provided by the user
+    /// * `user_config` - map with property name and values (the explicit user config properties)
+    pub fn get_detailed(
         &self,
-        version: &Version,
+        version: &str,
         role: &str,
         kind: &PropertyNameKind,
-        properties: &BTreeMap<String, Option<String>>,
-    ) -> BTreeMap<String, Option<String>> {
-        let mut result = BTreeMap::new();
+        user_config: &HashMap<String, Option<String>>,
+    ) -> ValidationResult<BTreeMap<String, DetailedValidationResult>> {
+        let product_version = self.parse_version(version)?;
+        let (raw_values, result) =
+            self.merge_and_validate(&product_version, role, kind, user_config)?;
 
-        for (name, value) in properties {
-            if let Some(prop) = self.find_property(name, role, kind, version) {
-                if prop.has_role_no_copy(role) {
-                    continue;
-                }
-            }
-            result.insert(name.clone(), value.clone());
-        }
-
-        result
+        Ok(result
+            .into_iter()
+            .map(|(name, result)| {
+                let raw = raw_values.get(&name).cloned().flatten().unwrap_or_default();
+                let detailed = DetailedValidationResult {
+                    raw,
+                    normalized: result.value().to_string(),
+                    result,
+                };
+                (name, detailed)
+            })
+            .collect())
     }
 
-    /// Validates the given `merged_properties` by performing the following actions:
-    /// * syntax checks on the values
-    /// * mandatory checks (if a property is required for the given role and version)
-    /// * comparison checks against the recommended and default values
+    /// Same as [`ProductConfigManager::get_ref`] but only returns the results of properties
+    /// categorized with the given tag (see [`PropertySpec::tags`]). Unknown properties (user
+    /// overrides) are not returned.
     ///
-    /// Properties that are not found in the product configuration are considered to be
-    /// user "overrides".
+    /// # Arguments
+    ///
+    /// * `version` - the current product version
+    /// * `role` - role provided by the user
+    /// * `kind` - kind provided by the user
+    /// * `tag` - the tag the properties must have
+    /// * `user_config` - map with property name and values (the explicit user config properties)
+    pub fn get_with_tag(
+        &self,
+        version: &str,
+        role: &str,
+        kind: &PropertyNameKind,
+        tag: &str,
+        user_config: &HashMap<String, Option<String>>,
+    ) -> ValidationResult<BTreeMap<String, PropertyValidationResult>> {
+        let product_version = self.parse_version(version)?;
+        let mut result = self.get_ref(version, role, kind, user_config)?;
+
+        result.retain(|name, _| {
+            self.find_property(name, role, kind, &product_version)
+                .is_some_and(|property| property.has_tag(tag))
+        });
+        Ok(result)
+    }
+
+    /// Same as [`ProductConfigManager::get_ref`] but passes every unknown property (user
+    /// override) with its value to the handler and returns the handler result instead of
+    /// [`PropertyValidationResult::Unknown`]. This allows custom validation rules e.g. for
+    /// plugin properties that are not part of the product config.
     ///
     /// # Arguments
+    ///
     /// * `version` - the current product version
-    /// * `role` - property role provided by the user
-    /// * `kind` - property name kind provided by the user
-    /// * `merged_properties` - merged user and property spec (matching role, kind etc.)
-    pub(crate) fn validate(
+    /// * `role` - role provided by the user
+    /// * `kind` - kind provided by the user
+    /// * `user_config` - map with property name and values (the explicit user config properties)
+    /// * `handler` - validates an unknown property (property name, value)
+    pub fn get_with_unknown_handler(
         &self,
-        version: &Version,
+        version: &str,
         role: &str,
         kind: &PropertyNameKind,
-        merged_properties: BTreeMap<String, Option<String>>,
+        user_config: &HashMap<String, Option<String>>,
+        handler: &dyn Fn(&str, &str) -> PropertyValidationResult,
     ) -> ValidationResult<BTreeMap<String, PropertyValidationResult>> {
-        let mut result = BTreeMap::new();
+        let mut result = self.get_ref(version, role, kind, user_config)?;
 
-        for (name, value) in merged_properties {
-            let prop = self.find_property(&name, role, kind, version);
+        for (name, value) in result.iter_mut() {
+            if let PropertyValidationResult::Unknown(unknown) = value {
+                *value = handler(name, unknown);
+            }
+        }
+        Ok(result)
+    }
 
-            match (prop, value) {
-                (Some(property), Some(val)) => {
-                    let check_datatype = validation::check_datatype(&property, &name, &val);
-                    if let Err(err) = check_datatype {
-                        result.insert(
-                            name.to_string(),
-                            PropertyValidationResult::Error(val.to_string(), err),
-                        );
-                        continue;
-                    }
+    /// Same as [`ProductConfigManager::get_ref`] but additionally returns the number of
+    /// properties per kind of result (see [`results::stats`]), e.g. to track how many properties
+    /// are overridden by users.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - the current product version
+    /// * `role` - role provided by the user
+    /// * `kind` - kind provided by the user
+    /// * `user_config` - map with property name and values (the explicit user config properties)
+    pub fn get_with_stats(
+        &self,
+        version: &str,
+        role: &str,
+        kind: &PropertyNameKind,
+        user_config: &HashMap<String, Option<String>>,
+    ) -> ValidationResult<(BTreeMap<String, PropertyValidationResult>, ConfigStats)> {
+        let result = self.get_ref(version, role, kind, user_config)?;
+        let stats = results::stats(&result);
+        Ok((result, stats))
+    }
 
-                    // TODO: what order?
-                    if let Err(err) = check_allowed_values(&name, &val, &property.allowed_values) {
-                        result.insert(
-                            name.to_string(),
-                            PropertyValidationResult::Error(val.to_string(), err),
-                        );
-                        continue;
-                    }
+    /// Same as [`ProductConfigManager::get_ref`] but additionally returns the [`ApplyMode`]
+    /// of every property (derived from [`PropertySpec::restart_required`]), e.g. to decide
+    /// whether changed properties require a restart. Unknown properties have
+    /// [`ApplyMode::None`].
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - the current product version
+    /// * `role` - role provided by the user
+    /// * `kind` - kind provided by the user
+    /// * `user_config` - map with property name and values (the explicit user config properties)
+    pub fn get_with_apply_mode(
+        &self,
+        version: &str,
+        role: &str,
+        kind: &PropertyNameKind,
+        user_config: &HashMap<String, Option<String>>,
+    ) -> ValidationResult<BTreeMap<String, (PropertyValidationResult, ApplyMode)>> {
+        let product_version = self.parse_version(version)?;
+        let result = self.get_ref(version, role, kind, user_config)?;
 
-                    if property.is_version_deprecated(version)? {
-                        result.insert(
-                            name.to_string(),
-                            PropertyValidationResult::Warn(
-                                val.to_string(),
-                                error::Error::VersionDeprecated {
-                                    property_name: name.to_string(),
-                                    product_version: version.to_string(),
-                                    // we would not reach here if deprecated_since is None
-                                    // so we can just unwrap.
-                                    deprecated_version: property
-                                        .deprecated_since
-                                        .unwrap()
-                                        .deref()
-                                        .to_string(),
-                                },
-                            ),
-                        );
-                        continue;
-                    }
+        Ok(result
+            .into_iter()
+            .map(|(name, result)| {
+                let apply_mode = self
+                    .find_property(&name, role, kind, &product_version)
+                    .map_or(ApplyMode::None, |property| property.apply_mode());
+                (name, (result, apply_mode))
+            })
+            .collect())
+    }
 
-                    // If we reach here the value is valid.
-                    // Check if it was provided by recommended value?
-                    if let Some(recommended) = &property.recommended_values {
-                        let recommended_value =
-                            property.filter_value(version, recommended.as_slice());
-                        if recommended_value == Some(val.to_string()) {
-                            result.insert(
-                                name.to_string(),
-                                PropertyValidationResult::RecommendedDefault(val.to_string()),
-                            );
-                            continue;
-                        }
-                    }
+    /// Applies the change set to the current user config and validates the resulting user config
+    /// like [`ProductConfigManager::get_ref`]. Additionally returns the changed properties that
+    /// require a restart (see [`PropertySpec::restart_required`]), including removed ones.
+    /// Changed properties whose validated value stays the same (e.g. a removed property whose
+    /// value equals the default) do not require a restart. Fails with
+    /// [`Error::ChangeSetConflict`] if a property is removed and added or updated.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - the current product version
+    /// * `role` - role provided by the user
+    /// * `kind` - kind provided by the user
+    /// * `current` - the current user config (property names and values)
+    /// * `changes` - the properties to be added, updated and removed
+    pub fn validate_change_set(
+        &self,
+        version: &str,
+        role: &str,
+        kind: &PropertyNameKind,
+        current: &BTreeMap<String, Option<String>>,
+        changes: &ChangeSet,
+    ) -> ValidationResult<ChangeSetValidation> {
+        let product_version = self.parse_version(version)?;
+        if let Some(name) = changes
+            .removed
+            .iter()
+            .find(|name| changes.added.contains_key(*name) || changes.updated.contains_key(*name))
+        {
+            return Err(Error::ChangeSetConflict {
+                property_name: name.clone(),
+            });
+        }
 
-                    // Check if it was provided by default value?
-                    if let Some(default) = &property.default_values {
-                        let default_value = property.filter_value(version, default.as_slice());
-                        if default_value == Some(val.to_string()) {
-                            result.insert(
-                                name.to_string(),
-                                PropertyValidationResult::Default(val.to_string()),
-                            );
-                            continue;
-                        }
-                    }
+        let current_user_config: HashMap<String, Option<String>> = current
+            .iter()
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+        let mut user_config = current_user_config.clone();
+        user_config.retain(|name, _| !changes.removed.contains(name));
+        user_config.extend(changes.added.clone());
+        user_config.extend(changes.updated.clone());
+        let current_results = self.get_ref(version, role, kind, &current_user_config)?;
+        let results = self.get_ref(version, role, kind, &user_config)?;
 
-                    result.insert(
-                        name.to_string(),
-                        PropertyValidationResult::Valid(val.to_string()),
-                    );
-                }
-                // if required and not set -> error
-                (Some(_property), None) => {
-                    result.insert(
-                        name.clone(),
-                        PropertyValidationResult::Error(
-                            "".to_string(),
-                            error::Error::PropertyValueMissing {
-                                property_name: name,
-                            },
-                        ),
-                    );
-                }
-                // unknown
-                (None, Some(val)) => {
-                    result.insert(name, PropertyValidationResult::Unknown(val.to_string()));
-                    continue;
-                }
-                _ => {}
-            }
-        }
+        let restart_required = changes
+            .changed_properties()
+            .into_iter()
+            .filter(|name| {
+                current_results
+                    .get(*name)
+                    .map(PropertyValidationResult::value)
+                    != results.get(*name).map(PropertyValidationResult::value)
+            })
+            .filter(|name| {
+                self.find_property(name, role, kind, &product_version)
+                    .is_some_and(|property| property.apply_mode() == ApplyMode::Restart)
+            })
+            .cloned()
+            .collect();
 
-        Ok(result)
+        Ok(ChangeSetValidation {
+            results,
+            restart_required,
+        })
     }
 
-    fn find_property(
+    /// Rewrites the properties of the user config that are deprecated in the given version to
+    /// their replacement (see [`PropertySpec::deprecated_for`]), e.g. to upgrade user configs
+    /// automatically. Only properties with a single replacement are migrated, the value is
+    /// copied to the replacement. All deprecated properties are reported in the returned
+    /// [`Migration`]s (ordered by property name), including the ones that were left untouched.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - the current product version
+    /// * `role` - role provided by the user
+    /// * `kind` - kind provided by the user
+    /// * `user_config` - map with property name and values (the explicit user config properties)
+    pub fn migrate(
         &self,
-        name: &str,
+        version: &str,
         role: &str,
         kind: &PropertyNameKind,
-        version: &Version,
-    ) -> Option<PropertySpec> {
-        for property_anchor in &self.config.properties {
-            if property_anchor.name_from_kind(kind) != Some(name.to_string()) {
-                continue;
-            }
+        user_config: &HashMap<String, Option<String>>,
+    ) -> ValidationResult<MigrationResult> {
+        let product_version = self.parse_version(version)?;
 
-            if !property_anchor.has_role(role) {
+        let mut migrated = user_config.clone();
+        let mut migrations = vec![];
+        for name in user_config.keys().collect::<BTreeSet<_>>() {
+            let Some(property) = self.find_property(name, role, kind, &product_version) else {
                 continue;
-            }
-
-            if property_anchor.is_version_supported(version).is_err() {
+            };
+            if !property.is_version_deprecated(&product_version)? {
                 continue;
             }
 
-            return Some(property_anchor.property.clone());
+            let from = name.clone();
+            let migration = match property.deprecated_for.unwrap_or_default().as_slice() {
+                [] => Migration::NoReplacement { from },
+                [to] if user_config.contains_key(to) => Migration::ReplacementAlreadySet {
+                    from,
+                    to: to.clone(),
+                },
+                [to] => {
+                    if let Some(value) = migrated.remove(name) {
+                        migrated.insert(to.clone(), value);
+                    }
+                    Migration::Replaced {
+                        from,
+                        to: to.clone(),
+                    }
+                }
+                replacements => Migration::AmbiguousReplacements {
+                    from,
+                    replacements: replacements.to_vec(),
+                },
+            };
+            migrations.push(migration);
         }
 
-        None
+        Ok((migrated, migrations))
     }
-}
 
-#[cfg(test)]
-mod tests {
-    macro_rules! collection {
-        // map-like
-        ($($k:expr => $v:expr),* $(,)?) => {
-            std::iter::Iterator::collect(IntoIterator::into_iter([$(($k, $v),)*]))
-        };
-        // set-like
-        ($($v:expr),* $(,)?) => {
-            std::iter::Iterator::collect(IntoIterator::into_iter([$($v,)*]))
+    /// Same as [`ProductConfigManager::get_ref`] but the results are keyed by the names of the
+    /// `target_kind` instead of the names of `kind`, e.g. to validate a config provided via
+    /// environment variables and write it into a config file. Properties without a name for
+    /// the target kind are dropped and reported via [`Error::PropertyNameNotFoundForKind`].
+    /// Unknown properties (user overrides) keep their names. Fails with
+    /// [`Error::TargetPropertyNameConflict`] if two results would have the same target name.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - the current product version
+    /// * `role` - role provided by the user
+    /// * `kind` - kind provided by the user (the kind of the user config)
+    /// * `target_kind` - the kind whose property names are used for the results
+    /// * `user_config` - map with property name and values (the explicit user config properties)
+    pub fn get_for_target_kind(
+        &self,
+        version: &str,
+        role: &str,
+        kind: &PropertyNameKind,
+        target_kind: &PropertyNameKind,
+        user_config: &HashMap<String, Option<String>>,
+    ) -> ValidationResult<(BTreeMap<String, PropertyValidationResult>, Vec<Error>)> {
+        let product_version = self.parse_version(version)?;
+        let result = self.get_ref(version, role, kind, user_config)?;
+
+        let mut remapped = BTreeMap::new();
+        // the names of the results in `kind` by target name to report conflicts
+        let mut sources = BTreeMap::new();
+        let mut dropped = vec![];
+        for (name, result) in result {
+            let target_name = match self.lookup_property(&name, role, kind, &product_version) {
+                Some(property) => match property.name_from_kind(target_kind) {
+                    Some(target_name) => target_name,
+                    None => {
+                        dropped.push(Error::PropertyNameNotFoundForKind {
+                            property_name: name,
+                            kind: target_kind.clone(),
+                        });
+                        continue;
+                    }
+                },
+                None => name.clone(),
+            };
+
+            if let Some(first) = sources.insert(target_name.clone(), name.clone()) {
+                return Err(Error::TargetPropertyNameConflict {
+                    target_name,
+                    kind: target_kind.clone(),
+                    first,
+                    second: name,
+                });
+            }
+            remapped.insert(target_name, result);
+        }
+
+        Ok((remapped, dropped))
+    }
+
+    /// Validates the user config like [`ProductConfigManager::get`] and renders the resulting
+    /// properties in the requested [`OutputFormat`] using the matching writer (see
+    /// [`writer`]). Fails with the first (by property name) error of the validation results
+    /// like [`ProductConfigManager::get_or_first_error`].
+    ///
+    /// Repeatable properties (see [`PropertySpec::repeatable`]) are written once per element in
+    /// the formats supporting repeated keys ([`OutputFormat::JavaProperties`] and
+    /// [`OutputFormat::HadoopXml`], see [`writer::split_repeated_values`]). The other formats
+    /// allow a key only once, so the elements are written as one value separated by
+    /// [`REPEATED_VALUE_SEPARATOR`].
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - the current product version
+    /// * `role` - role provided by the user
+    /// * `kind` - kind provided by the user
+    /// * `user_config` - map with property name and values (the explicit user config properties)
+    /// * `format` - the output format to render
+    pub fn render(
+        &self,
+        version: &str,
+        role: &str,
+        kind: &PropertyNameKind,
+        user_config: &HashMap<String, Option<String>>,
+        format: OutputFormat,
+    ) -> Result<String, ConfigError> {
+        let properties: BTreeMap<String, Option<String>> = self
+            .get_or_first_error(version, role, kind, user_config)?
+            .into_iter()
+            .map(|(name, value)| (name, Some(value)))
+            .collect();
+
+        let repeatable = self
+            .repeatable_properties(version, role, kind)
+            .context(error::ValidationSnafu)?;
+        let split = writer::split_repeated_values(properties.iter(), &repeatable);
+        let split = split.iter().map(|(name, value)| (name, value));
+
+        let render_failed = |reason: String| Error::RenderFailed { format, reason };
+        let rendered = match format {
+            OutputFormat::JavaProperties => writer::to_java_properties_string(split)
+                .map_err(|err| render_failed(err.to_string())),
+            OutputFormat::HadoopXml => Ok(writer::to_hadoop_xml(split)),
+            OutputFormat::Env => writer::to_env_file(properties.iter(), false)
+                .map_err(|err| render_failed(err.to_string())),
+            OutputFormat::Json => writer::to_json(properties.iter(), false)
+                .map_err(|err| render_failed(err.to_string())),
+            OutputFormat::Yaml => {
+                serde_yaml::to_string(&properties).map_err(|err| render_failed(err.to_string()))
+            }
+            OutputFormat::Toml => {
+                writer::to_toml(properties.iter()).map_err(|err| render_failed(err.to_string()))
+            }
         };
+        rendered.context(error::ValidationSnafu)
     }
 
-    use std::collections::{BTreeMap, HashMap};
+    /// Same as [`ProductConfigManager::get`] but returns the first (by property name)
+    /// [`PropertyValidationResult::Error`] (or the first of [`PropertyValidationResult::Errors`])
+    /// as [`ConfigError`], e.g. to propagate it via `?`.
+    /// Otherwise all results collapse to their values.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - the current product version
+    /// * `role` - role provided by the user
+    /// * `kind` - kind provided by the user
+    /// * `user_config` - map with property name and values (the explicit user config properties)
+    pub fn get_or_first_error(
+        &self,
+        version: &str,
+        role: &str,
+        kind: &PropertyNameKind,
+        user_config: &HashMap<String, Option<String>>,
+    ) -> Result<BTreeMap<String, String>, ConfigError> {
+        let result = self
+            .get_ref(version, role, kind, user_config)
+            .context(error::ValidationSnafu)?;
 
-    use super::*;
-    use crate::error::Error;
-    use crate::types::PropertyNameKind;
-    use crate::ProductConfigManager;
-    use rstest::*;
+        let mut values = BTreeMap::new();
+        for (name, value) in result {
+            let value = match value {
+                PropertyValidationResult::Default(value)
+                | PropertyValidationResult::RecommendedDefault(value)
+                | PropertyValidationResult::Valid(value)
+                | PropertyValidationResult::Unknown(value)
+                | PropertyValidationResult::Warn(value, _) => value,
+                PropertyValidationResult::Error(_, err) => {
+                    return Err(err).context(error::InvalidPropertySnafu {
+                        property_name: name,
+                    });
+                }
+                PropertyValidationResult::Errors(_, mut errors) => {
+                    return Err(errors.remove(0)).context(error::InvalidPropertySnafu {
+                        property_name: name,
+                    });
+                }
+            };
+            values.insert(name, value);
+        }
+        Ok(values)
+    }
 
-    fn macro_to_hash_map(map: HashMap<String, Option<String>>) -> HashMap<String, Option<String>> {
-        map
+    /// Checks that the user config does not contain any properties that are unknown for the
+    /// given version, role and kind (which would be returned as
+    /// [`PropertyValidationResult::Unknown`] by [`ProductConfigManager::get`]), e.g. for strict
+    /// validation in CI. Returns the sorted unknown property names otherwise. Returns an error
+    /// (e.g. [`Error::InvalidVersion`]) if the version cannot be parsed.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - the current product version
+    /// * `role` - role provided by the user
+    /// * `kind` - kind provided by the user
+    /// * `user_config` - map with property name and values (the explicit user config properties)
+    pub fn assert_no_unknown(
+        &self,
+        version: &str,
+        role: &str,
+        kind: &PropertyNameKind,
+        user_config: &HashMap<String, Option<String>>,
+    ) -> ValidationResult<Result<(), Vec<String>>> {
+        let unknown = self.unknown_properties(version, role, kind, user_config)?;
+
+        if unknown.is_empty() {
+            return Ok(Ok(()));
+        }
+        Ok(Err(unknown))
     }
 
-    fn macro_to_btree_map(
-        map: BTreeMap<String, Option<String>>,
-    ) -> BTreeMap<String, Option<String>> {
-        map
+    /// Returns the sorted names of the user config properties that do not match any property
+    /// of the product config for the given version, role and kind, e.g. to fail fast on typos
+    /// before the (more expensive) validation via [`ProductConfigManager::get`].
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - the current product version
+    /// * `role` - role provided by the user
+    /// * `kind` - kind provided by the user
+    /// * `user_config` - map with property name and values (the explicit user config properties)
+    pub fn unknown_properties(
+        &self,
+        version: &str,
+        role: &str,
+        kind: &PropertyNameKind,
+        user_config: &HashMap<String, Option<String>>,
+    ) -> ValidationResult<Vec<String>> {
+        let product_version = self.parse_version(version)?;
+
+        let mut unknown: Vec<String> = user_config
+            .keys()
+            .filter(|name| {
+                self.find_property(name, role, kind, &product_version)
+                    .is_none()
+            })
+            .cloned()
+            .collect();
+        unknown.sort();
+        Ok(unknown)
     }
 
-    fn macro_to_get_result(
-        map: BTreeMap<String, PropertyValidationResult>,
-    ) -> BTreeMap<String, PropertyValidationResult> {
-        map
+    /// Returns the names of all repeatable properties (see [`PropertySpec::repeatable`]) for the
+    /// given version, role and kind. Can be used to split repeated values before writing them
+    /// via [`writer::split_repeated_values`].
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - the current product version
+    /// * `role` - role provided by the user
+    /// * `kind` - kind provided by the user
+    pub fn repeatable_properties(
+        &self,
+        version: &str,
+        role: &str,
+        kind: &PropertyNameKind,
+    ) -> ValidationResult<BTreeSet<String>> {
+        let product_version = self.parse_version(version)?;
+
+        let mut result = BTreeSet::new();
+        for property in self.applicable_properties() {
+            if property.is_repeatable()
+                && property.has_role(role)
+                && property.is_version_supported(&product_version)?
+            {
+                if let Some(name) = property.name_from_kind(kind) {
+                    result.insert(name);
+                }
+            }
+        }
+        Ok(result)
     }
 
-    #[rstest]
-    #[case::expands_role_required_expandee_role_not_required(
-        "0.5.0",
+    /// Returns the elements of the value of the given property. Values of repeatable
+    /// properties (see [`PropertySpec::repeatable`]) are split at
+    /// [`REPEATED_VALUE_SEPARATOR`], values of other (or unknown) properties are returned as
+    /// single element, as they may contain the separator themselves.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - the current product version
+    /// * `role` - role provided by the user
+    /// * `kind` - kind provided by the user
+    /// * `name` - the property name
+    /// * `value` - the (validated) value of the property, e.g. [`PropertyValidationResult::value`]
+    pub fn value_elements<'a>(
+        &self,
+        version: &str,
+        role: &str,
+        kind: &PropertyNameKind,
+        name: &str,
+        value: &'a str,
+    ) -> ValidationResult<Vec<&'a str>> {
+        let product_version = self.parse_version(version)?;
+
+        Ok(
+            match self.lookup_property(name, role, kind, &product_version) {
+                Some(property) => property.value_elements(value),
+                None => vec![value],
+            },
+        )
+    }
+
+    /// Returns the names of all properties for the given version, role and kind, e.g. to list
+    /// the available properties to users. Internal properties (see [`PropertySpec::internal`])
+    /// are only included if `include_internal` is set.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - the current product version
+    /// * `role` - role provided by the user
+    /// * `kind` - kind provided by the user
+    /// * `include_internal` - whether internal properties should be included
+    pub fn list_properties(
+        &self,
+        version: &str,
+        role: &str,
+        kind: &PropertyNameKind,
+        include_internal: bool,
+    ) -> ValidationResult<Vec<String>> {
+        let product_version = self.parse_version(version)?;
+
+        let mut result = vec![];
+        for property in self.applicable_properties() {
+            if (!property.is_internal() || include_internal)
+                && property.has_role(role)
+                && property.is_version_supported(&product_version)?
+            {
+                if let Some(name) = property.name_from_kind(kind) {
+                    result.push(name);
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /// Returns the names of all properties the product config knows for the given version, role
+    /// and kind, including internal properties, e.g. to build an allow-list for user overrides.
+    /// Same as [`ProductConfigManager::list_properties`] with `include_internal` set.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - the current product version
+    /// * `role` - role provided by the user
+    /// * `kind` - kind provided by the user
+    pub fn known_properties(
+        &self,
+        version: &str,
+        role: &str,
+        kind: &PropertyNameKind,
+    ) -> ValidationResult<Vec<String>> {
+        self.list_properties(version, role, kind, true)
+    }
+
+    /// Returns the names of all known properties that have no value after merging the user
+    /// config, i.e. required properties without recommended or default value the user must
+    /// still set, e.g. for a setup wizard. [`ProductConfigManager::get`] would return these as
+    /// [`Error::PropertyValueMissing`]. The values are not validated.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - the current product version
+    /// * `role` - role provided by the user
+    /// * `kind` - kind provided by the user
+    /// * `user_config` - map with property name and values (the explicit user config properties)
+    pub fn missing_required(
+        &self,
+        version: &str,
+        role: &str,
+        kind: &PropertyNameKind,
+        user_config: &HashMap<String, Option<String>>,
+    ) -> ValidationResult<Vec<String>> {
+        let product_version = self.parse_version(version)?;
+        let merged_properties =
+            self.get_and_expand_properties(&product_version, role, kind, user_config)?;
+
+        Ok(merged_properties
+            .into_iter()
+            .filter(|(name, value)| {
+                value.is_none()
+                    && self
+                        .lookup_property(name, role, kind, &product_version)
+                        .is_some()
+            })
+            .map(|(name, _)| name)
+            .collect())
+    }
+
+    /// Returns the names of all properties categorized with the given tag (see
+    /// [`PropertySpec::tags`]) for the given version, role and kind, e.g. to show the security
+    /// relevant properties in an audit view.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - the current product version
+    /// * `role` - role provided by the user
+    /// * `kind` - kind provided by the user
+    /// * `tag` - the tag the properties must have
+    pub fn properties_with_tag(
+        &self,
+        version: &str,
+        role: &str,
+        kind: &PropertyNameKind,
+        tag: &str,
+    ) -> ValidationResult<Vec<String>> {
+        let product_version = self.parse_version(version)?;
+
+        let mut result = vec![];
+        for property in self.applicable_properties() {
+            if property.has_tag(tag)
+                && property.has_role(role)
+                && property.is_version_supported(&product_version)?
+            {
+                if let Some(name) = property.name_from_kind(kind) {
+                    result.push(name);
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /// Returns all properties for the given role and kind whose recommended values are
+    /// redundant, i.e. equal to the default values (the product would apply anyway) for all
+    /// version ranges. Can be used by config maintainers to prune recommended values. Each
+    /// property is returned with its recommended value once per version range.
+    ///
+    /// # Arguments
+    ///
+    /// * `role` - role provided by the user
+    /// * `kind` - kind provided by the user
+    pub fn redundant_recommendations(
+        &self,
+        role: &str,
+        kind: &PropertyNameKind,
+    ) -> Vec<(PropertyName, String)> {
+        let mut result = vec![];
+        for property in self.applicable_properties() {
+            if !property.has_role(role) {
+                continue;
+            }
+            let (Some(recommended), Some(defaults)) =
+                (&property.recommended_values, &property.default_values)
+            else {
+                continue;
+            };
+            let Some(name) = property
+                .property_names
+                .iter()
+                .find(|name| name.kind == *kind)
+            else {
+                continue;
+            };
+
+            if !recommended.is_empty()
+                && recommended
+                    .iter()
+                    .all(|recommended| defaults.contains(recommended))
+            {
+                for recommended in recommended {
+                    result.push((name.clone(), recommended.value.clone()));
+                }
+            }
+        }
+        result
+    }
+
+    /// Returns the property spec of the given property name, e.g. to describe the property to
+    /// users. Internal properties (see [`PropertySpec::internal`]) are only returned if
+    /// `include_internal` is set.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - the current product version
+    /// * `role` - role provided by the user
+    /// * `kind` - kind provided by the user
+    /// * `name` - the name of the property
+    /// * `include_internal` - whether internal properties should be included
+    pub fn describe(
+        &self,
+        version: &str,
+        role: &str,
+        kind: &PropertyNameKind,
+        name: &str,
+        include_internal: bool,
+    ) -> ValidationResult<Option<PropertySpec>> {
+        let product_version = self.parse_version(version)?;
+
+        Ok(self
+            .find_property(name, role, kind, &product_version)
+            .filter(|property| !property.is_internal() || include_internal))
+    }
+
+    /// Returns the allowed values (see [`PropertySpec::allowed_values`]) of the given property
+    /// name in declaration order, e.g. to build dropdowns. Returns `None` if the property is
+    /// unknown or has no allowed values.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the name of the property
+    /// * `role` - role provided by the user
+    /// * `kind` - kind provided by the user
+    /// * `version` - the current product version
+    pub fn allowed_values_for(
+        &self,
+        name: &str,
+        role: &str,
+        kind: &PropertyNameKind,
+        version: &str,
+    ) -> ValidationResult<Option<Vec<String>>> {
+        let product_version = self.parse_version(version)?;
+
+        Ok(self
+            .find_property(name, role, kind, &product_version)
+            .and_then(|property| property.allowed_values))
+    }
+
+    /// Returns the recommended (or otherwise default) value of a single property for the given
+    /// version, without validating or expanding the whole config like
+    /// [`ProductConfigManager::get`]. Returns `None` if the property is unknown and `Some(None)`
+    /// if it has no recommended or default value for the version.
+    ///
+    /// The value is normalized according to the datatype (e.g. "1" CPU to "1000m") like it
+    /// would be returned by [`ProductConfigManager::get`].
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the name of the property
+    /// * `role` - role provided by the user
+    /// * `kind` - kind provided by the user
+    /// * `version` - the current product version
+    pub fn default_for(
+        &self,
+        name: &str,
+        role: &str,
+        kind: &PropertyNameKind,
+        version: &str,
+    ) -> ValidationResult<Option<Option<String>>> {
+        let product_version = self.parse_version(version)?;
+
+        let Some(property) = self.find_property(name, role, kind, &product_version) else {
+            return Ok(None);
+        };
+        let Some((_, value)) = property.recommended_or_default(&product_version, kind) else {
+            return Ok(Some(None));
+        };
+
+        value
+            .map(|value| {
+                let value = match &self.value_transformer {
+                    Some(transformer) => transformer(name, &value),
+                    None => value,
+                };
+                property
+                    .value_elements(&value)
+                    .into_iter()
+                    .map(|element| {
+                        validation::check_datatype(property.datatype_for_kind(kind), name, element)
+                    })
+                    .collect::<ValidationResult<Vec<_>>>()
+                    .map(|elements| elements.join(&REPEATED_VALUE_SEPARATOR.to_string()))
+            })
+            .transpose()
+            .map(Some)
+    }
+
+    /// Returns the documentation (description, comment and additional doc links) of the given
+    /// property name or `None` if the property is unknown.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - the current product version
+    /// * `role` - role provided by the user
+    /// * `kind` - kind provided by the user
+    /// * `name` - the name of the property
+    pub fn property_docs(
+        &self,
+        version: &str,
+        role: &str,
+        kind: &PropertyNameKind,
+        name: &str,
+    ) -> ValidationResult<Option<PropertyDocs>> {
+        let product_version = self.parse_version(version)?;
+
+        Ok(self
+            .find_property(name, role, kind, &product_version)
+            .map(|property| PropertyDocs {
+                description: property.description,
+                comment: property.comment,
+                additional_doc: property.additional_doc.unwrap_or_default(),
+            }))
+    }
+
+    /// Returns the names of all config files ([`PropertyNameKind::File`]) the given role produces
+    /// in the given version, derived from the property specs. Can be used to render all config
+    /// files of a role without hard coding the file names.
+    ///
+    /// # Arguments
+    ///
+    /// * `role` - role provided by the user
+    /// * `version` - the current product version
+    pub fn file_kinds_for(&self, role: &str, version: &str) -> ValidationResult<BTreeSet<String>> {
+        let product_version = self.parse_version(version)?;
+
+        let mut result = BTreeSet::new();
+        for property in self.applicable_properties() {
+            if property.has_role(role) && property.is_version_supported(&product_version)? {
+                for property_name in &property.property_names {
+                    if let PropertyNameKind::File(file) = &property_name.kind {
+                        result.insert(file.clone());
+                    }
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /// Returns the property spec of the given property name serialized as YAML snippet, e.g. for
+    /// editing tools that render single properties. The snippet can be parsed into an equal
+    /// [`PropertySpec`] again.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the name of the property
+    /// * `role` - role provided by the user
+    /// * `kind` - kind provided by the user
+    pub fn property_yaml(&self, name: &str, role: &str, kind: &PropertyNameKind) -> Option<String> {
+        self.config
+            .properties
+            .iter()
+            .find(|property| {
+                property.name_from_kind(kind).as_deref() == Some(name) && property.has_role(role)
+            })
+            .and_then(|property| serde_yaml::to_string(&property.property).ok())
+    }
+
+    /// Returns the descriptions (see [`PropertySpec::description`]) of all properties with a
+    /// [`PropertyValidationResult::Error`] or [`PropertyValidationResult::Warn`] in the given
+    /// validation result. Properties without a description are left out. Can be used to show
+    /// context next to errors e.g. in user facing forms.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - the current product version
+    /// * `role` - role provided by the user
+    /// * `kind` - kind provided by the user
+    /// * `result` - the validation result of [`ProductConfigManager::get`] for the same version,
+    ///   role and kind
+    pub fn error_descriptions(
+        &self,
+        version: &str,
+        role: &str,
+        kind: &PropertyNameKind,
+        result: &BTreeMap<String, PropertyValidationResult>,
+    ) -> ValidationResult<BTreeMap<String, String>> {
+        let product_version = self.parse_version(version)?;
+
+        Ok(result
+            .iter()
+            .filter(|(_, value)| {
+                matches!(
+                    value,
+                    PropertyValidationResult::Error(..)
+                        | PropertyValidationResult::Errors(..)
+                        | PropertyValidationResult::Warn(..)
+                )
+            })
+            .filter_map(|(name, _)| {
+                self.find_property(name, role, kind, &product_version)
+                    .and_then(|property| property.description)
+                    .map(|description| (name.clone(), description))
+            })
+            .collect())
+    }
+
+    /// Checks the count constraints of the product config (see [`types::CountConstraint`]) against
+    /// the given validation result and returns all violations. A property counts if its value
+    /// equals the value of the constraint or, if the constraint has no value, if it has a non
+    /// default value (i.e. it is neither [`PropertyValidationResult::Default`] nor
+    /// [`PropertyValidationResult::RecommendedDefault`]). Properties with errors never count.
+    ///
+    /// # Arguments
+    ///
+    /// * `result` - the validation result of [`ProductConfigManager::get`]
+    pub fn check_count_constraints(
+        &self,
+        result: &BTreeMap<String, PropertyValidationResult>,
+    ) -> Vec<Error> {
+        let mut errors = vec![];
+        for constraint in &self.config.count_constraints {
+            let count = constraint
+                .properties
+                .iter()
+                .filter_map(|name| result.get(name))
+                .filter(|value| match value {
+                    PropertyValidationResult::Error(..) | PropertyValidationResult::Errors(..) => {
+                        false
+                    }
+                    _ => match &constraint.value {
+                        Some(expected) => value.value() == expected,
+                        None => !matches!(
+                            value,
+                            PropertyValidationResult::Default(_)
+                                | PropertyValidationResult::RecommendedDefault(_)
+                        ),
+                    },
+                })
+                .count();
+
+            match (constraint.min, constraint.max) {
+                (Some(min), _) if count < min => errors.push(Error::CountConstraintBelowMin {
+                    constraint: constraint.name.clone(),
+                    count,
+                    min,
+                }),
+                (_, Some(max)) if count > max => errors.push(Error::CountConstraintAboveMax {
+                    constraint: constraint.name.clone(),
+                    count,
+                    max,
+                }),
+                _ => {}
+            }
+        }
+        errors
+    }
+
+    /// Checks the mirrors of the product config (see [`types::Mirror`]) against the given
+    /// validation results and returns an error for every role in which mirrored properties have
+    /// different (normalized) values. Mirrored properties that are not part of the results or
+    /// have errors are ignored. The values are reported by property name and kind, e.g.
+    /// "log.dirs (file 'server.properties')".
+    ///
+    /// # Arguments
+    ///
+    /// * `results` - the validation results of [`ProductConfigManager::get_all`]
+    pub fn check_mirrors(&self, results: &RoleKindValidationResults) -> Vec<Error> {
+        let mut errors = vec![];
+        for (role, results_by_kind) in results {
+            for mirror in &self.config.mirrors {
+                let values: BTreeMap<String, String> = mirror
+                    .properties
+                    .iter()
+                    .filter_map(|property_name| {
+                        let value = results_by_kind
+                            .get(&property_name.kind)?
+                            .get(&property_name.name)?;
+                        match value {
+                            PropertyValidationResult::Error(..)
+                            | PropertyValidationResult::Errors(..) => None,
+                            // the same name may be used by different kinds
+                            _ => Some((
+                                format!("{} ({})", property_name.name, property_name.kind),
+                                value.value().to_string(),
+                            )),
+                        }
+                    })
+                    .collect();
+
+                let distinct: BTreeSet<&String> = values.values().collect();
+                if distinct.len() > 1 {
+                    errors.push(Error::MirroredValuesDiverge {
+                        mirror: mirror.name.clone(),
+                        role: role.clone(),
+                        values,
+                    });
+                }
+            }
+        }
+        errors
+    }
+
+    /// Returns all properties that are not feature gated or whose feature is enabled.
+    fn applicable_properties(&self) -> impl Iterator<Item = &PropertyAnchor> {
+        self.config
+            .properties
+            .iter()
+            .filter(|property| match &property.feature_gate {
+                Some(feature) => self.enabled_features.contains(feature),
+                None => true,
+            })
+    }
+
+    /// Records all known (not [`PropertyValidationResult::Unknown`]) property names of the
+    /// validation result if access tracking is enabled.
+    fn record_access(&self, result: &BTreeMap<String, PropertyValidationResult>) {
+        if let Some(access_log) = &self.access_log {
+            let mut access_log = access_log.lock().unwrap_or_else(PoisonError::into_inner);
+            for (name, value) in result {
+                if !matches!(value, PropertyValidationResult::Unknown(_)) {
+                    access_log.insert(name.clone());
+                }
+            }
+        }
+    }
+
+    /// Merge the provided user config properties with the product configuration (loaded from YAML)
+    /// depending on kind, role and version. The user configuration has the highest priority, followed
+    /// by the recommended values from the product configuration. Finally, if none are available,
+    /// the default values from the product configuration are used.
+    /// This function also expands properties if they are required for the given role or if the user
+    /// has requested so in the `user_config` parameter.
+    ///
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - the current product version
+    /// * `role` - property role provided by the user
+    /// * `kind` - property name kind provided by the user
+    /// * `user_config` - map with property name and values (the explicit user config properties)
+    pub(crate) fn get_and_expand_properties(
+        &self,
+        version: &Version,
+        role: &str,
+        kind: &PropertyNameKind,
+        user_config: &HashMap<String, Option<String>>,
+    ) -> ValidationResult<BTreeMap<String, Option<String>>> {
+        let mut merged_properties = BTreeMap::new();
+
+        for property in self.applicable_properties() {
+            let property_names = property.all_property_names();
+            // If user provides a property that exists in the product config and fits the role and
+            // version, we have to expand if needed.
+            if util::hashmap_contains_any_key(user_config, &property_names)
+                && property.has_role(role)
+                && property.is_version_supported(version)?
+            {
+                let value = util::effective_value(property, version, kind, user_config);
+                merged_properties.extend(expand_properties(
+                    property,
+                    version,
+                    role,
+                    kind,
+                    value.as_deref(),
+                )?);
+                // An explicit `None` may fall back to the recommended or default value.
+                if self.explicit_none == ExplicitNone::UseDefault {
+                    if let Some((name, Some(value))) =
+                        property.recommended_or_default(version, kind)
+                    {
+                        if let Some(None) = user_config.get(&name) {
+                            merged_properties.insert(name, Some(value));
+                        }
+                    }
+                }
+            // If the user does not provide a property which is required in the product config,
+            // and fits the role and version, we have to expand if needed.
+            } else if property.has_role_required(role) && property.is_version_supported(version)? {
+                if let Some((name, value)) = property.recommended_or_default(version, kind) {
+                    merged_properties.insert(name, value);
+                }
+                let value = util::effective_value(property, version, kind, user_config);
+                merged_properties.extend(expand_properties(
+                    property,
+                    version,
+                    role,
+                    kind,
+                    value.as_deref(),
+                )?);
+            }
+        }
+
+        // Add any unknown (not found in product config) properties provided by the user -> Overrides
+        let user_properties: Vec<(String, Option<String>)> = user_config
+            .iter()
+            .filter(|(name, value)| {
+                // Keep defaults resolved for explicit `None` values above
+                !(self.explicit_none == ExplicitNone::UseDefault
+                    && value.is_none()
+                    && matches!(merged_properties.get(*name), Some(Some(_))))
+            })
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+        merged_properties.extend(user_properties);
+
+        // The user can provide "Meta" properties, that do not exists on their own and only expand
+        // into other "valid" properties. Therefore it requires the "no_copy" field to indicate
+        // that it should not end up in the final configuration.
+        Ok(self.remove_no_copy_properties(version, role, kind, &merged_properties))
+    }
+
+    /// Computes the values of properties with [`PropertySpec::computed_from`] from the other
+    /// merged properties and adds them to the merged properties. Explicit user values are not
+    /// overridden. Returns [`PropertyValidationResult::Error`]s for properties whose expression
+    /// could not be evaluated, which replace their validation results.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - the current product version
+    /// * `role` - property role provided by the user
+    /// * `kind` - property name kind provided by the user
+    /// * `user_config` - map with property name and values (the explicit user config properties)
+    /// * `merged_properties` - merged user and property spec (matching role, kind etc.)
+    fn compute_properties(
+        &self,
+        version: &Version,
+        role: &str,
+        kind: &PropertyNameKind,
+        user_config: &HashMap<String, Option<String>>,
+        merged_properties: &mut BTreeMap<String, Option<String>>,
+    ) -> ValidationResult<BTreeMap<String, PropertyValidationResult>> {
+        let mut errors = BTreeMap::new();
+
+        for property in self.applicable_properties() {
+            let (Some(expression), Some(name)) =
+                (&property.computed_from, property.name_from_kind(kind))
+            else {
+                continue;
+            };
+            if user_config.contains_key(&name)
+                || !property.has_role(role)
+                || !property.is_version_supported(version)?
+            {
+                continue;
+            }
+
+            let lookup = |reference: &str| match merged_properties.get(reference) {
+                Some(Some(value)) => value.trim().parse::<f64>().map_err(|_| {
+                    format!("referenced property '{reference}' has the non-numeric value '{value}'")
+                }),
+                _ => Err(format!("unknown reference '{reference}'")),
+            };
+            match formula::evaluate(expression, lookup) {
+                Ok(value) => {
+                    merged_properties.insert(name, Some(formula::format_value(value)));
+                }
+                Err(reason) => {
+                    merged_properties.remove(&name);
+                    errors.insert(
+                        name.clone(),
+                        PropertyValidationResult::Error(
+                            String::new(),
+                            Error::ComputedValueNotEvaluable {
+                                property_name: name,
+                                expression: expression.clone(),
+                                reason,
+                            },
+                        ),
+                    );
+                }
+            }
+        }
+
+        Ok(errors)
+    }
+
+    /// If enabled via [`ProductConfigManager::with_unresolved_value_warnings`], adds a
+    /// [`PropertyValidationResult::Warn`] with [`Error::NoValueResolved`] for every optional
+    /// property of the role and kind that is missing from the result and has no recommended or
+    /// default value for the version.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - the current product version
+    /// * `role` - property role provided by the user
+    /// * `kind` - property name kind provided by the user
+    /// * `result` - the validation results to adapt
+    fn warn_unresolved_values(
+        &self,
+        version: &Version,
+        role: &str,
+        kind: &PropertyNameKind,
+        result: &mut BTreeMap<String, PropertyValidationResult>,
+    ) -> ValidationResult<()> {
+        if !self.unresolved_value_warnings {
+            return Ok(());
+        }
+
+        for property in self.applicable_properties() {
+            if !property.has_role(role)
+                || property.has_role_required(role)
+                || property.has_role_no_copy(role)
+                || !property.is_version_supported(version)?
+            {
+                continue;
+            }
+            let Some((name, None)) = property.recommended_or_default(version, kind) else {
+                continue;
+            };
+            let property_name = name.clone();
+            result.entry(name).or_insert_with(|| {
+                PropertyValidationResult::Warn(
+                    String::new(),
+                    error::Error::NoValueResolved { property_name },
+                )
+            });
+        }
+        Ok(())
+    }
+
+    /// Expansions (see [`PropertySpec::expands_to`]) with an explicit value are overridden by
+    /// user provided values of the expanded property. If the values differ, the (otherwise
+    /// passing) validation result of the expanded property is turned into a
+    /// [`PropertyValidationResult::Warn`] with [`Error::ExpansionOverriddenByUser`].
+    ///
+    /// # Arguments
+    /// * `version` - the current product version
+    /// * `role` - property role provided by the user
+    /// * `kind` - property name kind provided by the user
+    /// * `user_config` - map with property name and values (the explicit user config properties)
+    /// * `result` - the validation results to adapt
+    fn warn_overridden_expansions(
+        &self,
+        version: &Version,
+        role: &str,
+        kind: &PropertyNameKind,
+        user_config: &HashMap<String, Option<String>>,
+        result: &mut BTreeMap<String, PropertyValidationResult>,
+    ) -> ValidationResult<()> {
+        for property in self.applicable_properties() {
+            let Some(expands_to) = &property.expands_to else {
+                continue;
+            };
+            // same conditions as in get_and_expand_properties
+            let property_names = property.all_property_names();
+            if !(property.has_role(role)
+                && property.is_version_supported(version)?
+                && (util::hashmap_contains_any_key(user_config, &property_names)
+                    || property.has_role_required(role)))
+            {
+                continue;
+            }
+            let source = property
+                .name_from_kind(kind)
+                .unwrap_or_else(|| property_names.join(", "));
+            let value = util::effective_value(property, version, kind, user_config);
+
+            for to_expand in expands_to {
+                if !to_expand.is_triggered_by(value.as_deref()) {
+                    continue;
+                }
+                let (Some(expansion_value), Some(target)) =
+                    (&to_expand.value, to_expand.property.name_from_kind(kind))
+                else {
+                    continue;
+                };
+                if !to_expand.property.has_role(role)
+                    || !to_expand.property.is_version_supported(version)?
+                {
+                    continue;
+                }
+                let Some(Some(user_value)) = user_config.get(&target) else {
+                    continue;
+                };
+                if user_value == expansion_value {
+                    continue;
+                }
+
+                if let Some(
+                    value @ (PropertyValidationResult::Default(_)
+                    | PropertyValidationResult::RecommendedDefault(_)
+                    | PropertyValidationResult::Valid(_)),
+                ) = result.get_mut(&target)
+                {
+                    *value = PropertyValidationResult::Warn(
+                        value.value().to_string(),
+                        error::Error::ExpansionOverriddenByUser {
+                            source: source.clone(),
+                            target: target.clone(),
+                            expansion_value: expansion_value.clone(),
+                            user_value: user_value.clone(),
+                        },
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the allowed values of the provider referenced by the property (if any). Fails if
+    /// the provider is not registered.
+    fn provided_allowed_values(
+        &self,
+        name: &str,
+        property: &PropertySpec,
+    ) -> ValidationResult<Option<Vec<String>>> {
+        match &property.allowed_values_provider {
+            Some(provider) => match self.allowed_values_providers.get(provider) {
+                Some(provide) => Ok(Some(provide())),
+                None => Err(error::Error::AllowedValuesProviderNotFound {
+                    property_name: name.to_string(),
+                    provider: provider.clone(),
+                }),
+            },
+            None => Ok(None),
+        }
+    }
+
+    fn remove_no_copy_properties(
+        &self,
+        version: &Version,
+        role: &str,
+        kind: &PropertyNameKind,
+        properties: &BTreeMap<String, Option<String>>,
+    ) -> BTreeMap<String, Option<String>> {
+        let mut result = BTreeMap::new();
+
+        for (name, value) in properties {
+            if let Some(prop) = self.find_property(name, role, kind, version) {
+                if prop.has_role_no_copy(role) {
+                    continue;
+                }
+            }
+            result.insert(name.clone(), value.clone());
+        }
+
+        result
+    }
+
+    /// Validates the given `merged_properties` by performing the following actions:
+    /// * syntax checks on the values
+    /// * mandatory checks (if a property is required for the given role and version)
+    /// * comparison checks against the recommended and default values
+    ///
+    /// Properties that are not found in the product configuration are considered to be
+    /// user "overrides".
+    ///
+    /// # Arguments
+    /// * `version` - the current product version
+    /// * `role` - property role provided by the user
+    /// * `kind` - property name kind provided by the user
+    /// * `merged_properties` - merged user and property spec (matching role, kind etc.)
+    pub(crate) fn validate(
+        &self,
+        version: &Version,
+        role: &str,
+        kind: &PropertyNameKind,
+        merged_properties: BTreeMap<String, Option<String>>,
+    ) -> ValidationResult<BTreeMap<String, PropertyValidationResult>> {
+        let mut result = BTreeMap::new();
+        // used to check if replacements of deprecated properties are set
+        let merged_names = merged_properties.keys().cloned().collect::<BTreeSet<_>>();
+
+        for (name, value) in merged_properties {
+            let prop = self.lookup_property(&name, role, kind, version);
+
+            match (prop, value) {
+                (Some(property), Some(val)) => {
+                    let val = match &self.value_transformer {
+                        Some(transformer) => transformer(&name, &val),
+                        None => val,
+                    };
+
+                    if self.non_empty_if_required
+                        && property.has_role_required(role)
+                        && val.trim().is_empty()
+                    {
+                        result.insert(
+                            name.clone(),
+                            PropertyValidationResult::Error(
+                                val,
+                                error::Error::PropertyValueEmpty {
+                                    property_name: name,
+                                },
+                            ),
+                        );
+                        continue;
+                    }
+
+                    // repeatable properties are validated per element, all failing checks
+                    // are collected
+                    let mut errors = vec![];
+                    let provided_values = match self.provided_allowed_values(&name, &property) {
+                        Ok(provided_values) => provided_values,
+                        Err(err) => {
+                            errors.push(err);
+                            None
+                        }
+                    };
+                    let mut normalized_elements = vec![];
+                    let case_insensitive = property.is_case_insensitive();
+                    for element in property.value_elements(&val) {
+                        let allowed = validation::check_allowed_values_or_patterns(
+                            &name,
+                            element,
+                            &property.allowed_values,
+                            &property.allowed_value_patterns,
+                            case_insensitive,
+                        );
+                        // the element in the casing of the allowed values
+                        let element = allowed.as_deref().unwrap_or(element);
+                        match validation::check_datatype(
+                            property.datatype_for_kind(kind),
+                            &name,
+                            element,
+                        ) {
+                            Ok(normalized) => normalized_elements.push(normalized),
+                            Err(err) => errors.push(err),
+                        }
+                        if let Err(allowed_errors) = &allowed {
+                            errors.extend(allowed_errors.iter().cloned());
+                        }
+                        if let Err(err) =
+                            check_allowed_values(&name, element, &provided_values, case_insensitive)
+                        {
+                            errors.push(err);
+                        }
+                    }
+
+                    if property.is_version_removed(version)? {
+                        errors.push(error::Error::VersionRemoved {
+                            property_name: name.to_string(),
+                            product_version: version.to_string(),
+                            // we would not reach here if removed_in is None
+                            // so we can just unwrap.
+                            removed_version: property.removed_in.as_ref().unwrap().to_string(),
+                        });
+                    }
+
+                    if !errors.is_empty() {
+                        result.insert(
+                            name.to_string(),
+                            PropertyValidationResult::from_errors(val.to_string(), errors),
+                        );
+                        continue;
+                    }
+
+                    // the (normalized) value to be returned
+                    let normalized =
+                        normalized_elements.join(&REPEATED_VALUE_SEPARATOR.to_string());
+
+                    if property.is_version_deprecated(version)? {
+                        // nudge users to migrate to the replacements (if not set yet)
+                        let missing_replacements = property
+                            .deprecated_for
+                            .iter()
+                            .flatten()
+                            .filter(|replacement| !merged_names.contains(*replacement))
+                            .cloned()
+                            .collect::<Vec<_>>();
+                        if !missing_replacements.is_empty() {
+                            result.insert(
+                                name.to_string(),
+                                PropertyValidationResult::Warn(
+                                    normalized,
+                                    error::Error::DeprecatedNotMigrated {
+                                        old: name.to_string(),
+                                        new: missing_replacements.join(", "),
+                                    },
+                                ),
+                            );
+                            continue;
+                        }
+
+                        result.insert(
+                            name.to_string(),
+                            PropertyValidationResult::Warn(
+                                normalized,
+                                error::Error::VersionDeprecated {
+                                    property_name: name.to_string(),
+                                    product_version: version.to_string(),
+                                    // we would not reach here if deprecated_since is None
+                                    // so we can just unwrap.
+                                    deprecated_version: property
+                                        .deprecated_since
+                                        .unwrap()
+                                        .deref()
+                                        .to_string(),
+                                    replacements: property.deprecated_for.unwrap_or_default(),
+                                },
+                            ),
+                        );
+                        continue;
+                    }
+
+                    // valid values may still require caution, e.g. privileged ports
+                    if let Some(warning) = normalized_elements.iter().find_map(|element| {
+                        validation::datatype_warning(
+                            property.datatype_for_kind(kind),
+                            &name,
+                            element,
+                        )
+                    }) {
+                        result.insert(
+                            name.to_string(),
+                            PropertyValidationResult::Warn(normalized, warning),
+                        );
+                        continue;
+                    }
+
+                    // If we reach here the value is valid.
+                    // Check if it was provided by recommended value?
+                    if let Some(recommended) = &property.recommended_values {
+                        let recommended_value =
+                            property.filter_value(version, recommended.as_slice());
+                        if recommended_value == Some(val.to_string()) {
+                            result.insert(
+                                name.to_string(),
+                                PropertyValidationResult::RecommendedDefault(normalized),
+                            );
+                            continue;
+                        }
+                    }
+
+                    // Check if it was provided by default value?
+                    if let Some(default) = &property.default_values {
+                        let default_value = property.filter_value(version, default.as_slice());
+                        if default_value == Some(val.to_string()) {
+                            result.insert(
+                                name.to_string(),
+                                PropertyValidationResult::Default(normalized),
+                            );
+                            continue;
+                        }
+                    }
+
+                    result.insert(
+                        name.to_string(),
+                        PropertyValidationResult::Valid(normalized),
+                    );
+                }
+                // if required and not set -> error
+                (Some(_property), None) => {
+                    result.insert(
+                        name.clone(),
+                        PropertyValidationResult::Error(
+                            "".to_string(),
+                            error::Error::PropertyValueMissing {
+                                property_name: name,
+                            },
+                        ),
+                    );
+                }
+                (None, Some(val)) => {
+                    // known, but not supported by this version -> the value has no effect
+                    if let Some(property) =
+                        self.find_unsupported_property(&name, role, kind, version)
+                    {
+                        result.insert(
+                            name.clone(),
+                            PropertyValidationResult::Warn(
+                                val,
+                                error::Error::VersionNotSupported {
+                                    property_name: PropertyName {
+                                        name,
+                                        kind: kind.clone(),
+                                        datatype: None,
+                                    },
+                                    product_version: version.to_string(),
+                                    required_version: property.as_of_version.deref().to_string(),
+                                },
+                            ),
+                        );
+                        continue;
+                    }
+                    // unknown
+                    result.insert(name, PropertyValidationResult::Unknown(val.to_string()));
+                    continue;
+                }
+                _ => {}
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn find_property(
+        &self,
+        name: &str,
+        role: &str,
+        kind: &PropertyNameKind,
+        version: &Version,
+    ) -> Option<PropertySpec> {
+        self.find_property_matching(role, version, |property| {
+            property.name_from_kind(kind).as_deref() == Some(name)
+        })
+    }
+
+    /// Returns the property with the given name, kind and role that is not supported by the
+    /// version yet (see [`PropertySpec::as_of_version`]), e.g. to warn users that set it.
+    fn find_unsupported_property(
+        &self,
+        name: &str,
+        role: &str,
+        kind: &PropertyNameKind,
+        version: &Version,
+    ) -> Option<&PropertySpec> {
+        self.applicable_properties()
+            .map(|property_anchor| &property_anchor.property)
+            .find(|property| {
+                property.name_from_kind(kind).as_deref() == Some(name)
+                    && property.has_role(role)
+                    && !property.is_version_supported(version).unwrap_or(true)
+            })
+    }
+
+    /// Same as [`ProductConfigManager::find_property`] but matches the name against all
+    /// property names of a property regardless of their kind.
+    fn find_property_any_kind(
+        &self,
+        name: &str,
+        role: &str,
+        version: &Version,
+    ) -> Option<PropertySpec> {
+        self.find_property_matching(role, version, |property| {
+            property.all_property_names().iter().any(|n| n == name)
+        })
+    }
+
+    /// Looks up a property via [`ProductConfigManager::find_property`] and, if enabled via
+    /// [`ProductConfigManager::with_cross_kind_lookup`], falls back to
+    /// [`ProductConfigManager::find_property_any_kind`].
+    fn lookup_property(
+        &self,
+        name: &str,
+        role: &str,
+        kind: &PropertyNameKind,
+        version: &Version,
+    ) -> Option<PropertySpec> {
+        self.find_property(name, role, kind, version).or_else(|| {
+            if self.cross_kind_lookup {
+                self.find_property_any_kind(name, role, version)
+            } else {
+                None
+            }
+        })
+    }
+
+    fn find_property_matching<F>(
+        &self,
+        role: &str,
+        version: &Version,
+        matches_name: F,
+    ) -> Option<PropertySpec>
+    where
+        F: Fn(&PropertySpec) -> bool,
+    {
+        for property_anchor in self.applicable_properties() {
+            if !matches_name(property_anchor) {
+                continue;
+            }
+
+            if !property_anchor.has_role(role) {
+                continue;
+            }
+
+            if !property_anchor
+                .is_version_supported(version)
+                .unwrap_or(false)
+            {
+                continue;
+            }
+
+            return Some(property_anchor.property.clone());
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    macro_rules! collection {
+        // map-like
+        ($($k:expr => $v:expr),* $(,)?) => {
+            std::iter::Iterator::collect(IntoIterator::into_iter([$(($k, $v),)*]))
+        };
+        // set-like
+        ($($v:expr),* $(,)?) => {
+            std::iter::Iterator::collect(IntoIterator::into_iter([$($v,)*]))
+        };
+    }
+
+    use std::collections::{BTreeMap, HashMap};
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::error::Error;
+    use crate::types::{
+        ApplyMode, BoundKind, Datatype, ProductConfigBuilder, PropertyName, PropertyNameKind,
+        PropertySpec, PropertyValueSpec, Role, StackableRegex, StackableVersion, Unit,
+    };
+    use crate::writer::OutputFormat;
+    use crate::ProductConfigManager;
+    use rstest::*;
+
+    fn macro_to_hash_map(map: HashMap<String, Option<String>>) -> HashMap<String, Option<String>> {
+        map
+    }
+
+    fn macro_to_btree_map(
+        map: BTreeMap<String, Option<String>>,
+    ) -> BTreeMap<String, Option<String>> {
+        map
+    }
+
+    fn macro_to_get_result(
+        map: BTreeMap<String, PropertyValidationResult>,
+    ) -> BTreeMap<String, PropertyValidationResult> {
+        map
+    }
+
+    #[rstest]
+    #[case::expands_role_required_expandee_role_not_required(
+        "0.5.0",
+        &PropertyNameKind::File("env.sh".to_string()),
+        "role_1",
+        "data/test_yamls/expands_role_required_expandee_role_not_required.yaml",
+        macro_to_hash_map(collection!{ "ENV_PASSWORD".to_string() => Some("secret".to_string()) }),
+        macro_to_btree_map(collection!{
+            "ENV_PASSWORD".to_string() => Some("secret".to_string()),
+            "ENV_ENABLE_PASSWORD".to_string() => Some("true".to_string())
+        }),
+    )]
+    #[case::expands_role_required_expandee_role_not_required_no_user_input(
+        "0.5.0",
+        &PropertyNameKind::File("env.sh".to_string()),
+        "role_1",
+        "data/test_yamls/expands_role_required_expandee_role_not_required.yaml",
+        HashMap::new(),
+        macro_to_btree_map(collection!{
+            "ENV_PASSWORD".to_string() => None,
+            "ENV_ENABLE_PASSWORD".to_string() => Some("true".to_string())
+        }),
+    )]
+    #[case::expands_role_not_required_expandee_role_not_required_no_user_input(
+        "0.5.0",
+        &PropertyNameKind::File("env.sh".to_string()),
+        "role_1",
+        "data/test_yamls/expands_role_not_required_expandee_role_not_required.yaml",
+        HashMap::new(),
+        BTreeMap::new(),
+    )]
+    #[case::expands_role_not_required_expandee_role_required_no_user_input(
+        "0.5.0",
+        &PropertyNameKind::File("env.sh".to_string()),
+        "role_1",
+        "data/test_yamls/expands_role_not_required_expandee_role_required.yaml",
+        HashMap::new(),
+        macro_to_btree_map(collection!{
+            "ENV_ENABLE_PASSWORD".to_string() => None,
+        }),
+    )]
+    #[case::expands_role_not_required_expandee_role_required_with_user_input_1(
+        "0.5.0",
+        &PropertyNameKind::File("env.sh".to_string()),
+        "role_1",
+        "data/test_yamls/expands_role_not_required_expandee_role_required.yaml",
+        macro_to_hash_map(collection!{
+            "ENV_ENABLE_PASSWORD".to_string() => Some("true".to_string())
+        }),
+        macro_to_btree_map(collection!{
+            "ENV_ENABLE_PASSWORD".to_string() => Some("true".to_string()),
+        }),
+    )]
+    #[case::expands_role_not_required_expandee_role_required_with_user_input_2(
+        "0.5.0",
+        &PropertyNameKind::File("env.sh".to_string()),
+        "role_1",
+        "data/test_yamls/expands_role_not_required_expandee_role_required.yaml",
+        macro_to_hash_map(collection!{
+            "ENV_PASSWORD".to_string() => Some("secret".to_string())
+        }),
+        macro_to_btree_map(collection!{
+            "ENV_PASSWORD".to_string() => Some("secret".to_string()),
+            "ENV_ENABLE_PASSWORD".to_string() => Some("true".to_string()),
+        }),
+    )]
+    #[case::expands_role_required_expandee_role_required_no_user_input(
+        "0.5.0",
+        &PropertyNameKind::File("env.sh".to_string()),
+        "role_1",
+        "data/test_yamls/expands_role_required_expandee_role_required.yaml",
+        HashMap::new(),
+        macro_to_btree_map(collection!{
+            "ENV_PASSWORD".to_string() => None,
+            "ENV_ENABLE_PASSWORD".to_string() => Some("true".to_string()),
+        }),
+    )]
+    #[case::expands_role_required_expandee_role_required_with_user_input1(
+        "0.5.0",
+        &PropertyNameKind::File("env.sh".to_string()),
+        "role_1",
+        "data/test_yamls/expands_role_required_expandee_role_required.yaml",
+        macro_to_hash_map(collection!{
+            "ENV_PASSWORD".to_string() => Some("secret".to_string())
+        }),
+        macro_to_btree_map(collection!{
+            "ENV_PASSWORD".to_string() => Some("secret".to_string()),
+            "ENV_ENABLE_PASSWORD".to_string() => Some("true".to_string()),
+        }),
+    )]
+    #[case::test_product_config_no_user_input(
+        "0.5.0",
+        &PropertyNameKind::File("env.sh".to_string()),
+        "role_1",
+        "data/test_yamls/test_product_config.yaml",
+        HashMap::new(),
+        macro_to_btree_map(collection!{
+            "ENV_FLOAT".to_string() => Some("50.0".to_string()),
+            "ENV_INTEGER_PORT_MIN_MAX".to_string() => Some("20000".to_string()),
+            "ENV_PROPERTY_STRING_DEPRECATED".to_string() => None,
+            "ENV_PASSWORD".to_string() => None,
+            "ENV_ENABLE_PASSWORD".to_string() => Some("true".to_string()),
+    }),
+    )]
+    #[case::expands_role_required_no_copy_no_user_input(
+        "0.5.0",
+        &PropertyNameKind::File("env.sh".to_string()),
+        "role_1",
+        "data/test_yamls/expands_role_required_no_copy.yaml",
+        HashMap::new(),
+        macro_to_btree_map(collection!{
+            "ENV_SSL_CERTIFICATE_PATH".to_string() => Some("path/to/certificates".to_string()),
+            "ENV_SSL_ENABLED".to_string() => Some("true".to_string()),
+    }),
+    )]
+    #[case::expands_role_not_required_no_copy_no_user_input(
+        "0.5.0",
+        &PropertyNameKind::File("env.sh".to_string()),
+        "role_1",
+        "data/test_yamls/expands_role_not_required_no_copy.yaml",
+        HashMap::new(),
+        BTreeMap::new(),
+    )]
+    fn test_get_and_expand_properties(
+        #[case] version: &str,
+        #[case] kind: &PropertyNameKind,
+        #[case] role: &str,
+        #[case] path: &str,
+        #[case] user_data: HashMap<String, Option<String>>,
+        #[case] expected: BTreeMap<String, Option<String>>,
+    ) {
+        let product_version = StackableVersion::parse(version).unwrap();
+
+        let manager = ProductConfigManager::from_yaml_file(path).unwrap();
+
+        let result = manager
+            .get_and_expand_properties(&product_version, role, kind, &user_data)
+            .unwrap();
+
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case::get_no_user_input(
+        &PropertyNameKind::File("env.sh".to_string()),
+        "role_1",
+        "data/test_yamls/validate.yaml",
+        HashMap::new(),
+        macro_to_get_result(collection!{
+            "ENV_FLOAT".to_string() => PropertyValidationResult::RecommendedDefault("50.0".to_string()),
+            "ENV_INTEGER_PORT_MIN_MAX".to_string() => PropertyValidationResult::RecommendedDefault("20000".to_string()),
+            "ENV_ENABLE_PASSWORD".to_string() => PropertyValidationResult::Valid("true".to_string()),
+            "ENV_PASSWORD".to_string() => PropertyValidationResult::Error("".to_string(), Error::PropertyValueMissing { property_name: "ENV_PASSWORD".to_string() }),
+            "ENV_ENABLE_PASSWORD".to_string() => PropertyValidationResult::Valid("true".to_string()),
+            "ENV_PROPERTY_STRING_DEPRECATED".to_string() => PropertyValidationResult::Warn("100mb".to_string(), Error::VersionDeprecated { property_name: "ENV_PROPERTY_STRING_DEPRECATED".to_string(), product_version: "0.5.0".to_string(), deprecated_version: "0.4.0".to_string(), replacements: vec![] }),
+        })
+    )]
+    #[case::get_valid_float(
         &PropertyNameKind::File("env.sh".to_string()),
         "role_1",
-        "data/test_yamls/expands_role_required_expandee_role_not_required.yaml",
-        macro_to_hash_map(collection!{ "ENV_PASSWORD".to_string() => Some("secret".to_string()) }),
-        macro_to_btree_map(collection!{
-            "ENV_PASSWORD".to_string() => Some("secret".to_string()),
-            "ENV_ENABLE_PASSWORD".to_string() => Some("true".to_string())
+        "data/test_yamls/validate_float.yaml",
+        macro_to_hash_map(collection!{
+            "ENV_FLOAT".to_string() => Some("42.0".to_string())
+        }),
+        macro_to_get_result(collection!{
+            "ENV_FLOAT".to_string() => PropertyValidationResult::Valid("42.0".to_string()),
+        })
+    )]
+    #[case::get_recommended_float_no_user_input(
+        &PropertyNameKind::File("env.sh".to_string()),
+        "role_1",
+        "data/test_yamls/validate_float.yaml",
+        HashMap::new(),
+        macro_to_get_result(collection!{
+            "ENV_FLOAT".to_string() => PropertyValidationResult::RecommendedDefault("50.0".to_string()),
+        })
+    )]
+    #[case::get_invalid_float_bad_user_value(
+        &PropertyNameKind::File("env.sh".to_string()),
+        "role_1",
+        "data/test_yamls/validate_float.yaml",
+        macro_to_hash_map(collection!{
+            "ENV_FLOAT".to_string() => Some("CAFE".to_string())
+        }),
+        macro_to_get_result(collection!{
+            "ENV_FLOAT".to_string() => PropertyValidationResult::Error("CAFE".to_string(), Error::DatatypeNotMatching { property_name: "ENV_FLOAT".to_string(), value: "CAFE".to_string(), datatype: "f64".to_string() }),
+        })
+    )]
+    #[case::get_invalid_float_user_value_too_small(
+        &PropertyNameKind::File("env.sh".to_string()),
+        "role_1",
+        "data/test_yamls/validate_float.yaml",
+        macro_to_hash_map(collection!{
+            "ENV_FLOAT".to_string() => Some("-1".to_string())
+        }),
+        macro_to_get_result(collection!{
+            "ENV_FLOAT".to_string() => PropertyValidationResult::Error("-1".to_string(), Error::PropertyValueOutOfBounds { property_name: "ENV_FLOAT".to_string(), received: "-1".to_string(), expected: "0".to_string(), bound_kind: BoundKind::Inclusive }),
+        })
+    )]
+    #[case::get_invalid_float_user_value_too_high(
+        &PropertyNameKind::File("env.sh".to_string()),
+        "role_1",
+        "data/test_yamls/validate_float.yaml",
+        macro_to_hash_map(collection!{
+            "ENV_FLOAT".to_string() => Some("101".to_string())
+        }),
+        macro_to_get_result(collection!{
+        "ENV_FLOAT".to_string() => PropertyValidationResult::Error("101".to_string(), Error::PropertyValueOutOfBounds { property_name: "ENV_FLOAT".to_string(), received: "101".to_string(), expected: "100".to_string(), bound_kind: BoundKind::Inclusive }),
+        })
+    )]
+    #[case::get_invalid_ssl_certificate_path(
+        &PropertyNameKind::Env,
+        "role_1",
+        "data/test_yamls/validate_directory.yaml",
+        macro_to_hash_map(collection!{
+            "ENV_SSL_CERTIFICATE_PATH".to_string() => Some("CAFE".to_string())
+        }),
+        macro_to_get_result(collection!{
+            "ENV_SSL_CERTIFICATE_PATH".to_string() => PropertyValidationResult::Error("CAFE".to_string(), Error::DatatypeRegexNotMatching { property_name: "ENV_SSL_CERTIFICATE_PATH".to_string(), value: "CAFE".to_string() }),
+        })
+    )]
+    #[case::get_valid_default_certificate_path_no_user_input(
+        &PropertyNameKind::Env,
+        "role_1",
+        "data/test_yamls/validate_directory.yaml",
+        HashMap::new(),
+        macro_to_get_result(collection!{
+            "ENV_SSL_CERTIFICATE_PATH".to_string() => PropertyValidationResult::Default("path/to/certificates".to_string()),
+        })
+    )]
+    #[case::get_override_ssl_certificate_path(
+        &PropertyNameKind::File("should_not_be_found_therefore_is_an_override".to_string()),
+        "role_1",
+        "data/test_yamls/validate_directory.yaml",
+        macro_to_hash_map(collection!{
+            "ENV_SSL_CERTIFICATE_PATH".to_string() => Some("/opt/stackable/zookeeper-operator/pki".to_string())
+        }),
+        macro_to_get_result(collection!{
+            "ENV_SSL_CERTIFICATE_PATH".to_string() => PropertyValidationResult::Unknown("/opt/stackable/zookeeper-operator/pki".to_string()),
+        })
+    )]
+    #[case::get_override_ssl_certificate_path(
+        &PropertyNameKind::Env,
+        "role_1",
+        "data/test_yamls/validate_directory.yaml",
+        macro_to_hash_map(collection!{
+            "ENV_SSL_CERTIFICATE_PATH".to_string() => Some("/opt/stackable/zookeeper-operator/pki".to_string())
+        }),
+        macro_to_get_result(collection!{
+            "ENV_SSL_CERTIFICATE_PATH".to_string() => PropertyValidationResult::Valid("/opt/stackable/zookeeper-operator/pki".to_string()),
+        })
+    )]
+    #[case::get_recommended_port_no_user_input(
+        &PropertyNameKind::Env,
+        "role_1",
+        "data/test_yamls/validate_port.yaml",
+        HashMap::new(),
+        macro_to_get_result(collection!{
+            "ENV_INTEGER_PORT_MIN_MAX".to_string() => PropertyValidationResult::RecommendedDefault("20000".to_string()),
+        })
+    )]
+    #[case::get_port_user_value_too_small(
+        &PropertyNameKind::Env,
+        "role_1",
+        "data/test_yamls/validate_port.yaml",
+        macro_to_hash_map(collection!{
+            "ENV_INTEGER_PORT_MIN_MAX".to_string() => Some("42".to_string())
+        }),
+        macro_to_get_result(collection!{
+            "ENV_INTEGER_PORT_MIN_MAX".to_string() => PropertyValidationResult::Error("42".to_string(), Error::PropertyValueOutOfBounds { property_name: "ENV_INTEGER_PORT_MIN_MAX".to_string(), received: "42".to_string(), expected: "1024".to_string(), bound_kind: BoundKind::Inclusive })
+        })
+    )]
+    #[case::get_port_user_value_too_high(
+        &PropertyNameKind::Env,
+        "role_1",
+        "data/test_yamls/validate_port.yaml",
+        macro_to_hash_map(collection!{
+            "ENV_INTEGER_PORT_MIN_MAX".to_string() => Some("65536".to_string())
         }),
+        macro_to_get_result(collection!{
+        "ENV_INTEGER_PORT_MIN_MAX".to_string() => PropertyValidationResult::Error("65536".to_string(), Error::PropertyValueOutOfBounds { property_name: "ENV_INTEGER_PORT_MIN_MAX".to_string(), received: "65536".to_string(), expected: "65535".to_string(), bound_kind: BoundKind::Inclusive })
+        })
+    )]
+    #[case::get_port_user_value_invalid(
+        &PropertyNameKind::Env,
+        "role_1",
+        "data/test_yamls/validate_port.yaml",
+        macro_to_hash_map(collection!{
+            "ENV_INTEGER_PORT_MIN_MAX".to_string() => Some("invalid".to_string())
+        }),
+        macro_to_get_result(collection!{
+            "ENV_INTEGER_PORT_MIN_MAX".to_string() => PropertyValidationResult::Error("invalid".to_string(), Error::DatatypeNotMatching { property_name: "ENV_INTEGER_PORT_MIN_MAX".to_string(), value: "invalid".to_string(), datatype: "i64".to_string() })
+        })
+    )]
+    #[case::get_port_user_value_valid(
+        &PropertyNameKind::Env,
+        "role_1",
+        "data/test_yamls/validate_port.yaml",
+        macro_to_hash_map(collection!{
+            "ENV_INTEGER_PORT_MIN_MAX".to_string() => Some("1024".to_string()),
+        }),
+        macro_to_get_result(collection!{
+            "ENV_INTEGER_PORT_MIN_MAX".to_string() => PropertyValidationResult::Valid("1024".to_string()),
+        })
+    )]
+    #[case::get_iso8601_duration_user_value_invalid(
+        &PropertyNameKind::Env,
+        "role_1",
+        "data/test_yamls/validate_iso8601_duration.yaml",
+        macro_to_hash_map(collection!{
+            "ENV_STARTUP_DELAY".to_string() => Some("invalid".to_string())
+        }),
+        macro_to_get_result(collection!{
+            "ENV_STARTUP_DELAY".to_string() => PropertyValidationResult::Error("invalid".to_string(), Error::DatatypeRegexNotMatching { property_name: "ENV_STARTUP_DELAY".to_string(), value: "invalid".to_string() })
+        })
+    )]
+    #[case::get_iso8601_duration_user_value_invalid(
+        &PropertyNameKind::Env,
+        "role_1",
+        "data/test_yamls/validate_iso8601_duration.yaml",
+        macro_to_hash_map(collection!{
+            "ENV_STARTUP_DELAY".to_string() => Some("PT300S".to_string())
+        }),
+        macro_to_get_result(collection!{
+            "ENV_STARTUP_DELAY".to_string() => PropertyValidationResult::Valid("PT300S".to_string())
+        })
+    )]
+    fn test_get(
+        #[case] kind: &PropertyNameKind,
+        #[case] role: &str,
+        #[case] path: &str,
+        #[case] user_data: HashMap<String, Option<String>>,
+        #[case] expected: BTreeMap<String, PropertyValidationResult>,
+    ) -> ValidationResult<()> {
+        let manager = ProductConfigManager::from_yaml_file(path).unwrap();
+
+        let result = manager.get("0.5.0", role, kind, user_data)?;
+
+        assert_eq!(result, expected);
+
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::unset(None, vec!["ENV_PASSWORD"])]
+    #[case::set(Some("secret"), vec![])]
+    fn test_missing_required(
+        #[case] password: Option<&str>,
+        #[case] expected: Vec<&str>,
+    ) -> ValidationResult<()> {
+        let manager = ProductConfigManager::from_yaml_file("data/test_yamls/validate.yaml")?;
+        let kind = PropertyNameKind::File("env.sh".to_string());
+        let mut user_config = HashMap::new();
+        if let Some(password) = password {
+            user_config.insert("ENV_PASSWORD".to_string(), Some(password.to_string()));
+        }
+
+        let missing = manager.missing_required("0.5.0", "role_1", &kind, &user_config)?;
+
+        assert_eq!(missing, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_with_unknown_handler() -> ValidationResult<()> {
+        let manager = ProductConfigManager::from_yaml_file("data/test_yamls/validate.yaml")?;
+        let kind = PropertyNameKind::File("env.sh".to_string());
+        let user_data = macro_to_hash_map(collection! {
+            "ENV_INTEGER_PORT_MIN_MAX".to_string() => Some("12345".to_string()),
+            "plugin.cache.size".to_string() => Some("10".to_string()),
+            "plugin.internal.debug".to_string() => Some("true".to_string()),
+        });
+        // rejects plugin properties marked as internal
+        let handler = |name: &str, value: &str| {
+            if name.starts_with("plugin.internal.") {
+                PropertyValidationResult::Error(
+                    value.to_string(),
+                    Error::DatatypeRegexNotMatching {
+                        property_name: name.to_string(),
+                        value: value.to_string(),
+                    },
+                )
+            } else {
+                PropertyValidationResult::Valid(value.to_string())
+            }
+        };
+
+        let result =
+            manager.get_with_unknown_handler("0.5.0", "role_1", &kind, &user_data, &handler)?;
+
+        assert_eq!(
+            result.get("plugin.cache.size"),
+            Some(&PropertyValidationResult::Valid("10".to_string()))
+        );
+        assert_eq!(
+            result.get("plugin.internal.debug"),
+            Some(&PropertyValidationResult::Error(
+                "true".to_string(),
+                Error::DatatypeRegexNotMatching {
+                    property_name: "plugin.internal.debug".to_string(),
+                    value: "true".to_string(),
+                }
+            ))
+        );
+        // known properties are not passed to the handler
+        assert_eq!(
+            result.get("ENV_INTEGER_PORT_MIN_MAX"),
+            Some(&PropertyValidationResult::Valid("12345".to_string()))
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::pattern("ENV_NAMESPACE", "my-namespace-1", None)]
+    #[case::no_pattern("ENV_NAMESPACE", "1-namespace", Some(vec![Error::PropertyValueNotMatchingAllowedPatterns { property_name: "ENV_NAMESPACE".to_string(), value: "1-namespace".to_string(), patterns: vec!["^[a-z][a-z0-9-]*$".to_string()] }]))]
+    #[case::combined_allowed_value("ENV_LOG_TARGET", "stdout", None)]
+    #[case::combined_pattern("ENV_LOG_TARGET", "/var/log/product.log", None)]
+    #[case::combined_none(
+        "ENV_LOG_TARGET",
+        "syslog",
+        Some(vec![
+            Error::PropertyValueNotInAllowedValues { property_name: "ENV_LOG_TARGET".to_string(), value: "syslog".to_string(), allowed_values: vec!["stdout".to_string(), "stderr".to_string()] },
+            Error::PropertyValueNotMatchingAllowedPatterns { property_name: "ENV_LOG_TARGET".to_string(), value: "syslog".to_string(), patterns: vec!["^/.+\\.log$".to_string()] },
+        ])
+    )]
+    fn test_get_allowed_value_patterns(
+        #[case] name: &str,
+        #[case] value: &str,
+        #[case] expected_errors: Option<Vec<Error>>,
+    ) -> ValidationResult<()> {
+        let manager =
+            ProductConfigManager::from_yaml_file("data/test_yamls/allowed_value_patterns.yaml")?;
+
+        let result = manager.get(
+            "0.1.0",
+            "role_1",
+            &PropertyNameKind::Env,
+            macro_to_hash_map(collection! {
+                name.to_string() => Some(value.to_string())
+            }),
+        )?;
+
+        let expected = match expected_errors {
+            None => PropertyValidationResult::Valid(value.to_string()),
+            Some(errors) => PropertyValidationResult::from_errors(value.to_string(), errors),
+        };
+        assert_eq!(result.get(name), Some(&expected));
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_allowed_value_pattern() {
+        let yaml = std::fs::read_to_string("data/test_yamls/allowed_value_patterns.yaml")
+            .unwrap()
+            .replace("^[a-z][a-z0-9-]*$", "^[a-z");
+
+        let result = ProductConfigManager::from_str(&yaml);
+
+        assert!(
+            matches!(result, Err(Error::YamlNotParsable { reason, .. }) if reason.contains("invalid pattern '^[a-z'"))
+        );
+    }
+
+    #[test]
+    fn test_version_req_with_version_range() {
+        let result =
+            ProductConfigManager::from_yaml_file("data/test_yamls/version_req_conflict.yaml");
+
+        assert!(
+            matches!(result, Err(Error::YamlFileNotParsable { reason, .. }) if reason.contains("value 'both' provides a version requirement and a from/to version range"))
+        );
+    }
+
+    #[rstest]
+    #[case::canonical("true", PropertyValidationResult::Valid("true".to_string()))]
+    #[case::upper_case("TRUE", PropertyValidationResult::Valid("true".to_string()))]
+    #[case::mixed_case("False", PropertyValidationResult::Valid("false".to_string()))]
+    fn test_get_case_insensitive(
+        #[case] value: &str,
+        #[case] expected: PropertyValidationResult,
+    ) -> ValidationResult<()> {
+        let manager =
+            ProductConfigManager::from_yaml_file("data/test_yamls/case_insensitive.yaml")?;
+
+        let result = manager.get(
+            "0.1.0",
+            "role_1",
+            &PropertyNameKind::Env,
+            macro_to_hash_map(collection! {
+                "ENV_SSL_ENABLED".to_string() => Some(value.to_string())
+            }),
+        )?;
+
+        assert_eq!(result.get("ENV_SSL_ENABLED"), Some(&expected));
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_ref() -> ValidationResult<()> {
+        let manager = ProductConfigManager::from_yaml_file("data/test_yamls/validate.yaml")?;
+        let kind = PropertyNameKind::File("env.sh".to_string());
+        let user_data = macro_to_hash_map(collection! {
+            "ENV_INTEGER_PORT_MIN_MAX".to_string() => Some("12345".to_string()),
+            "ENV_PROPERTY_STRING_MEMORY".to_string() => Some("1g".to_string()),
+        });
+
+        let borrowed = manager.get_ref("0.5.0", "role_1", &kind, &user_data)?;
+        let owned = manager.get("0.5.0", "role_1", &kind, user_data)?;
+
+        assert_eq!(borrowed, owned);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::valid_elements(
+        Some("PLAINTEXT://:9092\nSSL://:9093".to_string()),
+        PropertyValidationResult::Valid("PLAINTEXT://:9092\nSSL://:9093".to_string())
+    )]
+    #[case::invalid_element(
+        Some("PLAINTEXT://:9092\n9093".to_string()),
+        PropertyValidationResult::Errors("PLAINTEXT://:9092\n9093".to_string(), vec![Error::DatatypeRegexNotMatching { property_name: "listeners".to_string(), value: "9093".to_string() }, Error::PropertyValueNotInAllowedValues { property_name: "listeners".to_string(), value: "9093".to_string(), allowed_values: vec!["PLAINTEXT://:9092".to_string(), "SSL://:9093".to_string()] }])
+    )]
+    #[case::not_in_allowed_values(
+        Some("PLAINTEXT://:9092\nFOO://:9093".to_string()),
+        PropertyValidationResult::Error("PLAINTEXT://:9092\nFOO://:9093".to_string(), Error::PropertyValueNotInAllowedValues { property_name: "listeners".to_string(), value: "FOO://:9093".to_string(), allowed_values: vec!["PLAINTEXT://:9092".to_string(), "SSL://:9093".to_string()] })
+    )]
+    fn test_get_repeatable(
+        #[case] value: Option<String>,
+        #[case] expected: PropertyValidationResult,
+    ) -> ValidationResult<()> {
+        let manager = ProductConfigManager::from_yaml_file("data/test_yamls/repeatable.yaml")?;
+        let kind = PropertyNameKind::File("server.properties".to_string());
+
+        let result = manager.get(
+            "0.5.0",
+            "role_1",
+            &kind,
+            macro_to_hash_map(collection! { "listeners".to_string() => value }),
+        )?;
+
+        assert_eq!(result.get("listeners"), Some(&expected));
+        assert_eq!(
+            manager.repeatable_properties("0.5.0", "role_1", &kind)?,
+            collection! { "listeners".to_string() }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_repeatable_elements() -> ValidationResult<()> {
+        let manager = ProductConfigManager::from_yaml_file("data/test_yamls/repeatable.yaml")?;
+        let kind = PropertyNameKind::File("server.properties".to_string());
+
+        let result = manager.get(
+            "0.5.0",
+            "role_1",
+            &kind,
+            macro_to_hash_map(collection! {
+                "listeners".to_string() => Some("PLAINTEXT://:9092\nSSL://:9093".to_string()),
+                "unknown".to_string() => Some("a\nb".to_string())
+            }),
+        )?;
+
+        let elements = |name: &str| {
+            manager.value_elements("0.5.0", "role_1", &kind, name, result[name].value())
+        };
+        assert_eq!(
+            elements("listeners")?,
+            vec!["PLAINTEXT://:9092", "SSL://:9093"]
+        );
+        // not repeatable
+        assert_eq!(elements("unknown")?, vec!["a\nb"]);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("500m", PropertyValidationResult::Valid("500m".to_string()))]
+    #[case("2", PropertyValidationResult::Valid("2000m".to_string()))]
+    #[case("2.5x", PropertyValidationResult::Error(
+        "2.5x".to_string(),
+        Error::DatatypeNotMatching {
+            property_name: "ENV_CPU".to_string(),
+            value: "2.5x".to_string(),
+            datatype: "cpu".to_string()
+        }
+    ))]
+    fn test_get_cpu(
+        #[case] value: &str,
+        #[case] expected: PropertyValidationResult,
+    ) -> ValidationResult<()> {
+        let manager = ProductConfigManager::from_yaml_file("data/test_yamls/cpu.yaml")?;
+
+        let result = manager.get(
+            "0.5.0",
+            "role_1",
+            &PropertyNameKind::Env,
+            macro_to_hash_map(collection! {
+                "ENV_CPU".to_string() => Some(value.to_string())
+            }),
+        )?;
+
+        assert_eq!(result.get("ENV_CPU"), Some(&expected));
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_or_first_error() -> ValidationResult<()> {
+        let manager = ProductConfigManager::from_yaml_file("data/test_yamls/descriptions.yaml")?;
+        let kind = PropertyNameKind::Env;
+
+        let result = manager.get_or_first_error(
+            "0.5.0",
+            "role_1",
+            &kind,
+            &macro_to_hash_map(collection! {
+                "ENV_VALID".to_string() => Some("1".to_string()),
+                "ENV_UNKNOWN".to_string() => Some("unknown".to_string())
+            }),
+        );
+        assert_eq!(
+            result,
+            Ok(BTreeMap::from([
+                ("ENV_UNKNOWN".to_string(), "unknown".to_string()),
+                ("ENV_VALID".to_string(), "1".to_string())
+            ]))
+        );
+
+        let result = manager.get_or_first_error(
+            "0.5.0",
+            "role_1",
+            &kind,
+            &macro_to_hash_map(collection! {
+                "ENV_VALID".to_string() => Some("1".to_string()),
+                "ENV_UNDESCRIBED".to_string() => Some("invalid".to_string()),
+                "ENV_DESCRIBED".to_string() => Some("invalid".to_string())
+            }),
+        );
+        assert_eq!(
+            result,
+            Err(ConfigError::InvalidProperty {
+                property_name: "ENV_DESCRIBED".to_string(),
+                source: Box::new(Error::DatatypeNotMatching {
+                    property_name: "ENV_DESCRIBED".to_string(),
+                    value: "invalid".to_string(),
+                    datatype: "i64".to_string()
+                })
+            })
+        );
+
+        let result = manager.get_or_first_error("invalid", "role_1", &kind, &HashMap::new());
+        assert!(matches!(result, Err(ConfigError::Validation { .. })));
+        Ok(())
+    }
+
+    #[test]
+    fn test_property_yaml() -> ValidationResult<()> {
+        let manager = ProductConfigManager::from_yaml_file("data/test_yamls/validate.yaml")?;
+        let kind = PropertyNameKind::File("env.sh".to_string());
+
+        let yaml = manager
+            .property_yaml("ENV_INTEGER_PORT_MIN_MAX", "role_1", &kind)
+            .unwrap();
+        let property: PropertySpec = serde_yaml::from_str(&yaml).unwrap();
+
+        assert_eq!(
+            Some(property),
+            manager.describe("0.5.0", "role_1", &kind, "ENV_INTEGER_PORT_MIN_MAX", true)?
+        );
+        assert!(manager
+            .property_yaml("ENV_INTEGER_PORT_MIN_MAX", "unknown_role", &kind)
+            .is_none());
+        assert!(manager.property_yaml("UNKNOWN", "role_1", &kind).is_none());
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(PropertyNameKind::Cli, "--level", "INFO", PropertyValidationResult::Valid("INFO".to_string()))]
+    #[case(PropertyNameKind::Env, "LEVEL", "3", PropertyValidationResult::Valid("3".to_string()))]
+    #[case(PropertyNameKind::Env, "LEVEL", "INFO", PropertyValidationResult::Error(
+        "INFO".to_string(),
+        Error::DatatypeNotMatching {
+            property_name: "LEVEL".to_string(),
+            value: "INFO".to_string(),
+            datatype: "i64".to_string()
+        }
+    ))]
+    fn test_get_datatype_per_kind(
+        #[case] kind: PropertyNameKind,
+        #[case] name: &str,
+        #[case] value: &str,
+        #[case] expected: PropertyValidationResult,
+    ) -> ValidationResult<()> {
+        let manager =
+            ProductConfigManager::from_yaml_file("data/test_yamls/datatype_per_kind.yaml")?;
+
+        let result = manager.get(
+            "0.5.0",
+            "role_1",
+            &kind,
+            macro_to_hash_map(collection! {
+                name.to_string() => Some(value.to_string())
+            }),
+        )?;
+
+        assert_eq!(result.get(name), Some(&expected));
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::cpu("data/test_yamls/cpu.yaml", "ENV_CPU", "2", "2000m")]
+    #[case::memory("data/test_yamls/memory.yaml", "ENV_MEMORY", "2Gi", "2048Mi")]
+    fn test_get_detailed(
+        #[case] file: &str,
+        #[case] name: &str,
+        #[case] raw: &str,
+        #[case] normalized: &str,
+    ) -> ValidationResult<()> {
+        let manager = ProductConfigManager::from_yaml_file(file)?;
+
+        let result = manager.get_detailed(
+            "0.5.0",
+            "role_1",
+            &PropertyNameKind::Env,
+            &macro_to_hash_map(collection! {
+                name.to_string() => Some(raw.to_string()),
+                "ENV_UNKNOWN".to_string() => Some("unknown".to_string())
+            }),
+        )?;
+
+        assert_eq!(
+            result.get(name),
+            Some(&DetailedValidationResult {
+                raw: raw.to_string(),
+                normalized: normalized.to_string(),
+                result: PropertyValidationResult::Valid(normalized.to_string()),
+            })
+        );
+        assert_eq!(
+            result.get("ENV_UNKNOWN"),
+            Some(&DetailedValidationResult {
+                raw: "unknown".to_string(),
+                normalized: "unknown".to_string(),
+                result: PropertyValidationResult::Unknown("unknown".to_string()),
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_with_reserved_ports() -> ValidationResult<()> {
+        let manager = ProductConfigManager::from_yaml_file("data/test_yamls/validate.yaml")?;
+        let kind = PropertyNameKind::File("env.sh".to_string());
+        let user_config = macro_to_hash_map(collection! {
+            "ENV_INTEGER_PORT_MIN_MAX".to_string() => Some("8080".to_string()),
+            "ENV_FLOAT".to_string() => Some("80".to_string())
+        });
+
+        let result = manager.get_with_reserved_ports(
+            "0.5.0",
+            "role_1",
+            &kind,
+            &user_config,
+            &HashSet::from([80, 8080]),
+        )?;
+        assert_eq!(
+            result.get("ENV_INTEGER_PORT_MIN_MAX"),
+            Some(&PropertyValidationResult::Error(
+                "8080".to_string(),
+                Error::PortAlreadyReserved {
+                    property_name: "ENV_INTEGER_PORT_MIN_MAX".to_string(),
+                    port: 8080
+                }
+            ))
+        );
+        // no port unit
+        assert_eq!(
+            result.get("ENV_FLOAT"),
+            Some(&PropertyValidationResult::Valid("80".to_string()))
+        );
+
+        let result = manager.get_with_reserved_ports(
+            "0.5.0",
+            "role_1",
+            &kind,
+            &user_config,
+            &HashSet::from([9090]),
+        )?;
+        assert_eq!(
+            result.get("ENV_INTEGER_PORT_MIN_MAX"),
+            Some(&PropertyValidationResult::Valid("8080".to_string()))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_with_reserved_ports_semantic() -> ValidationResult<()> {
+        let manager = ProductConfigManager::from_yaml_file("data/test_yamls/reserved_ports.yaml")?;
+
+        let result = manager.get_with_reserved_ports(
+            "0.1.0",
+            "role_1",
+            &PropertyNameKind::Env,
+            &macro_to_hash_map(collection! {
+                "ENV_HTTP_PORT".to_string() => Some("8080".to_string()),
+                "ENV_PORTS".to_string() => Some("8080\n9090\n8443".to_string())
+            }),
+            &HashSet::from([8080, 8443]),
+        )?;
+        assert_eq!(
+            result.get("ENV_HTTP_PORT"),
+            Some(&PropertyValidationResult::Error(
+                "8080".to_string(),
+                Error::PortAlreadyReserved {
+                    property_name: "ENV_HTTP_PORT".to_string(),
+                    port: 8080
+                }
+            ))
+        );
+        assert_eq!(
+            result.get("ENV_PORTS"),
+            Some(&PropertyValidationResult::Errors(
+                "8080\n9090\n8443".to_string(),
+                vec![
+                    Error::PortAlreadyReserved {
+                        property_name: "ENV_PORTS".to_string(),
+                        port: 8080
+                    },
+                    Error::PortAlreadyReserved {
+                        property_name: "ENV_PORTS".to_string(),
+                        port: 8443
+                    }
+                ]
+            ))
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("0.5.0", PropertyValidationResult::Valid("1".to_string()))]
+    #[case("0.7.0", PropertyValidationResult::Warn(
+        "1".to_string(),
+        Error::VersionDeprecated {
+            property_name: "ENV_REMOVED".to_string(),
+            product_version: "0.7.0".to_string(),
+            deprecated_version: "0.6.0".to_string(),
+            replacements: vec![]
+        }
+    ))]
+    #[case("1.0.0", PropertyValidationResult::Error(
+        "1".to_string(),
+        Error::VersionRemoved {
+            property_name: "ENV_REMOVED".to_string(),
+            product_version: "1.0.0".to_string(),
+            removed_version: "1.0.0".to_string()
+        }
+    ))]
+    #[case("1.1.0", PropertyValidationResult::Error(
+        "1".to_string(),
+        Error::VersionRemoved {
+            property_name: "ENV_REMOVED".to_string(),
+            product_version: "1.1.0".to_string(),
+            removed_version: "1.0.0".to_string()
+        }
+    ))]
+    fn test_get_removed(
+        #[case] version: &str,
+        #[case] expected: PropertyValidationResult,
+    ) -> ValidationResult<()> {
+        let manager = ProductConfigManager::from_yaml_file("data/test_yamls/removed.yaml")?;
+
+        let result = manager.get(
+            version,
+            "role_1",
+            &PropertyNameKind::Env,
+            macro_to_hash_map(collection! {
+                "ENV_REMOVED".to_string() => Some("1".to_string())
+            }),
+        )?;
+
+        assert_eq!(result.get("ENV_REMOVED"), Some(&expected));
+        Ok(())
+    }
+
+    #[test]
+    fn test_unknown_properties() -> ValidationResult<()> {
+        let manager = ProductConfigManager::from_yaml_file("data/test_yamls/validate.yaml")?;
+        let kind = PropertyNameKind::File("env.sh".to_string());
+
+        assert_eq!(
+            manager.unknown_properties(
+                "0.5.0",
+                "role_1",
+                &kind,
+                &macro_to_hash_map(collection! {
+                    "ENV_FLOAT".to_string() => Some("1.0".to_string()),
+                    // misspelled ENV_SSL_ENABLED
+                    "ENV_SLL_ENABLED".to_string() => Some("true".to_string()),
+                    // known for another kind only
+                    "conf.float".to_string() => Some("1.0".to_string())
+                })
+            )?,
+            vec!["ENV_SLL_ENABLED".to_string(), "conf.float".to_string()]
+        );
+        assert!(matches!(
+            manager.unknown_properties("invalid", "role_1", &kind, &HashMap::new()),
+            Err(Error::InvalidVersion { .. })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_assert_no_unknown() -> ValidationResult<()> {
+        let manager = ProductConfigManager::from_yaml_file("data/test_yamls/descriptions.yaml")?;
+        let kind = PropertyNameKind::Env;
+
+        assert_eq!(
+            manager.assert_no_unknown(
+                "0.5.0",
+                "role_1",
+                &kind,
+                &macro_to_hash_map(collection! {
+                    "ENV_VALID".to_string() => Some("1".to_string()),
+                    "ENV_DESCRIBED".to_string() => None
+                })
+            ),
+            Ok(Ok(()))
+        );
+        assert_eq!(
+            manager.assert_no_unknown(
+                "0.5.0",
+                "role_1",
+                &kind,
+                &macro_to_hash_map(collection! {
+                    "ENV_VALID".to_string() => Some("1".to_string()),
+                    "ENV_UNKNOWN_2".to_string() => Some("2".to_string()),
+                    "ENV_UNKNOWN_1".to_string() => Some("1".to_string())
+                })
+            ),
+            Ok(Err(vec![
+                "ENV_UNKNOWN_1".to_string(),
+                "ENV_UNKNOWN_2".to_string()
+            ]))
+        );
+        // known for another role only
+        assert_eq!(
+            manager.assert_no_unknown(
+                "0.5.0",
+                "role_2",
+                &kind,
+                &macro_to_hash_map(collection! {
+                    "ENV_VALID".to_string() => Some("1".to_string())
+                })
+            ),
+            Ok(Err(vec!["ENV_VALID".to_string()]))
+        );
+        // an invalid version is an error for empty and non empty user configs
+        assert!(matches!(
+            manager.assert_no_unknown("not-a-version", "role_1", &kind, &HashMap::new()),
+            Err(Error::InvalidVersion { .. })
+        ));
+        assert!(matches!(
+            manager.assert_no_unknown(
+                "not-a-version",
+                "role_1",
+                &kind,
+                &macro_to_hash_map(collection! {
+                    "ENV_VALID".to_string() => Some("1".to_string())
+                })
+            ),
+            Err(Error::InvalidVersion { .. })
+        ));
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("2Gi", PropertyValidationResult::Valid("2048Mi".to_string()))]
+    #[case("1024", PropertyValidationResult::Valid("1024Mi".to_string()))]
+    #[case("128Mi", PropertyValidationResult::Error(
+        "128Mi".to_string(),
+        Error::PropertyValueOutOfBounds {
+            property_name: "ENV_MEMORY".to_string(),
+            received: "128".to_string(),
+            expected: "256".to_string(),
+            bound_kind: BoundKind::Inclusive
+        }
+    ))]
+    fn test_get_memory(
+        #[case] value: &str,
+        #[case] expected: PropertyValidationResult,
+    ) -> ValidationResult<()> {
+        let manager = ProductConfigManager::from_yaml_file("data/test_yamls/memory.yaml")?;
+
+        let result = manager.get(
+            "0.5.0",
+            "role_1",
+            &PropertyNameKind::Env,
+            macro_to_hash_map(collection! {
+                "ENV_MEMORY".to_string() => Some(value.to_string())
+            }),
+        )?;
+
+        assert_eq!(result.get("ENV_MEMORY"), Some(&expected));
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::role_1("role_1", vec![
+        "ENV_INTEGER_PORT_MIN_MAX",
+        "ENV_FLOAT",
+        "ENV_PROPERTY_STRING_MEMORY",
+        "ENV_PROPERTY_STRING_DEPRECATED",
+        "ENV_ALLOWED_VALUES",
+        "ENV_SSL_ENABLED",
+        "ENV_SSL_CERTIFICATE_PATH",
+        "ENV_ENABLE_SECURITY",
+        "ENV_ENABLE_PASSWORD",
+        "ENV_PASSWORD",
+    ])]
+    #[case::role_2("role_2", vec![
+        "ENV_INTEGER_PORT_MIN_MAX",
+        "ENV_FLOAT",
+        "ENV_PROPERTY_STRING_DEPRECATED",
+        "ENV_ALLOWED_VALUES",
+    ])]
+    #[case::unknown_role("role_3", vec![])]
+    fn test_known_properties(
+        #[case] role: &str,
+        #[case] expected: Vec<&str>,
+    ) -> ValidationResult<()> {
+        let manager = ProductConfigManager::from_yaml_file("data/test_yamls/validate.yaml")?;
+
+        assert_eq!(
+            manager.known_properties(
+                "0.5.0",
+                role,
+                &PropertyNameKind::File("env.sh".to_string())
+            )?,
+            expected
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_feature_gate() -> ValidationResult<()> {
+        let kind = PropertyNameKind::Env;
+        let user_config = macro_to_hash_map(collection! {
+            "ENV_GATED".to_string() => Some("1".to_string())
+        });
+
+        let manager = ProductConfigManager::from_yaml_file("data/test_yamls/feature_gate.yaml")?;
+        assert_eq!(
+            manager.list_properties("0.5.0", "role_1", &kind, false)?,
+            vec!["ENV_UNGATED".to_string()]
+        );
+        assert_eq!(
+            manager.get_ref("0.5.0", "role_1", &kind, &user_config)?,
+            macro_to_get_result(collection! {
+                "ENV_GATED".to_string() => PropertyValidationResult::Unknown("1".to_string()),
+                "ENV_UNGATED".to_string() => PropertyValidationResult::Default("a".to_string())
+            })
+        );
+
+        let manager = ProductConfigManager::from_yaml_file("data/test_yamls/feature_gate.yaml")?
+            .with_features(["kerberos"]);
+        assert_eq!(
+            manager.list_properties("0.5.0", "role_1", &kind, false)?,
+            vec!["ENV_UNGATED".to_string(), "ENV_GATED".to_string()]
+        );
+        assert_eq!(
+            manager.get_ref("0.5.0", "role_1", &kind, &user_config)?,
+            macro_to_get_result(collection! {
+                "ENV_GATED".to_string() => PropertyValidationResult::Valid("1".to_string()),
+                "ENV_UNGATED".to_string() => PropertyValidationResult::Default("a".to_string())
+            })
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("ENV_INTEGER", "0", Some(("0", BoundKind::Exclusive)))]
+    #[case("ENV_INTEGER", "1", None)]
+    #[case("ENV_INTEGER", "100", None)]
+    #[case("ENV_INTEGER", "101", Some(("100", BoundKind::Inclusive)))]
+    #[case("ENV_FLOAT", "0", None)]
+    #[case("ENV_FLOAT", "0.999", None)]
+    #[case("ENV_FLOAT", "1", Some(("1", BoundKind::Exclusive)))]
+    fn test_get_exclusive_bounds(
+        #[case] name: &str,
+        #[case] value: &str,
+        #[case] violated_bound: Option<(&str, BoundKind)>,
+    ) -> ValidationResult<()> {
+        let manager =
+            ProductConfigManager::from_yaml_file("data/test_yamls/exclusive_bounds.yaml")?;
+
+        let result = manager.get(
+            "0.5.0",
+            "role_1",
+            &PropertyNameKind::Env,
+            macro_to_hash_map(collection! {
+                name.to_string() => Some(value.to_string())
+            }),
+        )?;
+
+        let expected = match violated_bound {
+            Some((bound, bound_kind)) => PropertyValidationResult::Error(
+                value.to_string(),
+                Error::PropertyValueOutOfBounds {
+                    property_name: name.to_string(),
+                    received: value.to_string(),
+                    expected: bound.to_string(),
+                    bound_kind,
+                },
+            ),
+            None => PropertyValidationResult::Valid(value.to_string()),
+        };
+        assert_eq!(result.get(name), Some(&expected));
+        Ok(())
+    }
+
+    #[test]
+    fn test_exclusive_bounds_resolved() -> ValidationResult<()> {
+        let manager =
+            ProductConfigManager::from_yaml_file("data/test_yamls/exclusive_bounds.yaml")?;
+
+        assert!(matches!(
+            &manager.config.properties[0].datatype,
+            Datatype::Integer {
+                min: Some(min),
+                min_bound_kind: Some(BoundKind::Exclusive),
+                exclusive_min: None,
+                ..
+            } if min == "0"
+        ));
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::min(
+        "exclusive_min_conflict.yaml",
+        "exclusive_min must not be combined with min or min_bound_kind"
     )]
-    #[case::expands_role_required_expandee_role_not_required_no_user_input(
+    #[case::max_override(
+        "exclusive_max_conflict.yaml",
+        "exclusive_max must not be combined with max or max_bound_kind"
+    )]
+    fn test_exclusive_bounds_conflict(#[case] file: &str, #[case] expected_reason: &str) {
+        let result = ProductConfigManager::from_yaml_file(format!("data/test_yamls/{file}"));
+
+        assert!(
+            matches!(result, Err(Error::YamlFileNotParsable { reason, .. }) if reason.contains(expected_reason))
+        );
+    }
+
+    #[rstest]
+    #[case::valid("0.1.0", "1", PropertyValidationResult::Valid("1".to_string()))]
+    #[case::single_error("0.1.0", "3", PropertyValidationResult::Error(
+        "3".to_string(),
+        Error::PropertyValueNotInAllowedValues { property_name: "ENV_LEVEL".to_string(), value: "3".to_string(), allowed_values: vec!["1".to_string(), "2".to_string()] }
+    ))]
+    #[case::bounds_and_allowed_values("0.1.0", "20", PropertyValidationResult::Errors(
+        "20".to_string(),
+        vec![
+            Error::PropertyValueOutOfBounds { property_name: "ENV_LEVEL".to_string(), received: "20".to_string(), expected: "10".to_string(), bound_kind: BoundKind::Inclusive },
+            Error::PropertyValueNotInAllowedValues { property_name: "ENV_LEVEL".to_string(), value: "20".to_string(), allowed_values: vec!["1".to_string(), "2".to_string()] },
+        ]
+    ))]
+    #[case::removed_and_allowed_values("0.5.0", "3", PropertyValidationResult::Errors(
+        "3".to_string(),
+        vec![
+            Error::PropertyValueNotInAllowedValues { property_name: "ENV_LEVEL".to_string(), value: "3".to_string(), allowed_values: vec!["1".to_string(), "2".to_string()] },
+            Error::VersionRemoved { property_name: "ENV_LEVEL".to_string(), product_version: "0.5.0".to_string(), removed_version: "0.5.0".to_string() },
+        ]
+    ))]
+    fn test_get_multiple_errors(
+        #[case] version: &str,
+        #[case] value: &str,
+        #[case] expected: PropertyValidationResult,
+    ) -> ValidationResult<()> {
+        let manager = ProductConfigManager::from_yaml_file("data/test_yamls/multiple_errors.yaml")?;
+        let result = manager.get(
+            version,
+            "role_1",
+            &PropertyNameKind::Env,
+            macro_to_hash_map(collection! { "ENV_LEVEL".to_string() => Some(value.to_string()) }),
+        )?;
+
+        assert_eq!(result.get("ENV_LEVEL"), Some(&expected));
+        Ok(())
+    }
+
+    #[test]
+    fn test_first_error() -> ValidationResult<()> {
+        let manager = ProductConfigManager::from_yaml_file("data/test_yamls/multiple_errors.yaml")?;
+        let result = manager.get(
+            "0.1.0",
+            "role_1",
+            &PropertyNameKind::Env,
+            macro_to_hash_map(collection! { "ENV_LEVEL".to_string() => Some("20".to_string()) }),
+        )?;
+
+        assert_eq!(
+            result
+                .get("ENV_LEVEL")
+                .and_then(PropertyValidationResult::first_error),
+            Some(&Error::PropertyValueOutOfBounds {
+                property_name: "ENV_LEVEL".to_string(),
+                received: "20".to_string(),
+                expected: "10".to_string(),
+                bound_kind: BoundKind::Inclusive
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_redundant_recommendations() -> ValidationResult<()> {
+        let manager =
+            ProductConfigManager::from_yaml_file("data/test_yamls/redundant_recommendations.yaml")?;
+
+        assert_eq!(
+            manager.redundant_recommendations("role_1", &PropertyNameKind::Env),
+            vec![(
+                PropertyName {
+                    name: "ENV_REDUNDANT".to_string(),
+                    kind: PropertyNameKind::Env,
+                    datatype: None,
+                },
+                "20".to_string()
+            )]
+        );
+        assert!(manager
+            .redundant_recommendations("role_2", &PropertyNameKind::Env)
+            .is_empty());
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("ENV_RESTART", ApplyMode::Restart)]
+    #[case("ENV_RELOAD", ApplyMode::Reload)]
+    #[case("ENV_NONE", ApplyMode::None)]
+    #[case("ENV_UNKNOWN", ApplyMode::None)]
+    fn test_get_with_apply_mode(
+        #[case] name: &str,
+        #[case] expected: ApplyMode,
+    ) -> ValidationResult<()> {
+        let manager = ProductConfigManager::from_yaml_file("data/test_yamls/apply_mode.yaml")?;
+        let result = manager.get_with_apply_mode(
+            "0.1.0",
+            "role_1",
+            &PropertyNameKind::Env,
+            &macro_to_hash_map(collection! { name.to_string() => Some("1".to_string()) }),
+        )?;
+
+        assert_eq!(
+            result.get(name).map(|(_, apply_mode)| *apply_mode),
+            Some(expected)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_transitive_expansion() -> ValidationResult<()> {
+        let manager = ProductConfigManager::from_yaml_file("data/test_yamls/expansion_chain.yaml")?;
+
+        let result = manager.get(
+            "0.1.0",
+            "role_1",
+            &PropertyNameKind::Env,
+            macro_to_hash_map(collection! {
+                "ENV_SECURITY_ENABLED".to_string() => Some("true".to_string())
+            }),
+        )?;
+
+        assert_eq!(
+            result,
+            collection! {
+                "ENV_KEYSTORE_PATH".to_string() => PropertyValidationResult::Valid("/stackable/tls/keystore.p12".to_string()),
+                "ENV_SECURITY_ENABLED".to_string() => PropertyValidationResult::Valid("true".to_string()),
+                "ENV_TLS_ENABLED".to_string() => PropertyValidationResult::Valid("true".to_string())
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_invalid_expansion_value() -> ValidationResult<()> {
+        let manager =
+            ProductConfigManager::from_yaml_file("data/test_yamls/expansion_invalid_value.yaml")?;
+
+        let result = manager.get(
+            "0.1.0",
+            "role_1",
+            &PropertyNameKind::Env,
+            macro_to_hash_map(collection! {
+                "ENV_CACHE_ENABLED".to_string() => Some("true".to_string())
+            }),
+        );
+
+        assert_eq!(
+            result?.get("ENV_CACHE_SIZE"),
+            Some(&PropertyValidationResult::Error(
+                "0".to_string(),
+                Error::PropertyValueOutOfBounds {
+                    property_name: "ENV_CACHE_SIZE".to_string(),
+                    received: "0".to_string(),
+                    expected: "1".to_string(),
+                    bound_kind: BoundKind::Inclusive,
+                }
+            ))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_expansion_cycle() -> ValidationResult<()> {
+        let manager = ProductConfigManager::from_yaml_file("data/test_yamls/expansion_cycle.yaml")?;
+
+        let result = manager.get(
+            "0.1.0",
+            "role_1",
+            &PropertyNameKind::Env,
+            macro_to_hash_map(collection! {
+                "ENV_A".to_string() => Some("a".to_string())
+            }),
+        );
+
+        assert_eq!(
+            result,
+            Err(Error::ExpansionCycle {
+                path: vec![
+                    "ENV_A".to_string(),
+                    "ENV_B".to_string(),
+                    "ENV_A".to_string()
+                ]
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_change_set() -> ValidationResult<()> {
+        let manager = ProductConfigManager::from_yaml_file("data/test_yamls/apply_mode.yaml")?;
+        let current = collection! {
+            "ENV_RESTART".to_string() => Some("10".to_string()),
+            "ENV_NONE".to_string() => Some("1".to_string())
+        };
+        let changes = ChangeSet {
+            added: collection! { "ENV_RELOAD".to_string() => Some("5".to_string()) },
+            updated: collection! { "ENV_RESTART".to_string() => Some("1000".to_string()) },
+            removed: collection! { "ENV_NONE".to_string() },
+        };
+
+        let validation = manager.validate_change_set(
+            "0.1.0",
+            "role_1",
+            &PropertyNameKind::Env,
+            &current,
+            &changes,
+        )?;
+
+        assert_eq!(
+            validation,
+            ChangeSetValidation {
+                results: collection! {
+                    "ENV_RELOAD".to_string() => PropertyValidationResult::Valid("5".to_string()),
+                    "ENV_RESTART".to_string() => PropertyValidationResult::Error(
+                        "1000".to_string(),
+                        Error::PropertyValueOutOfBounds {
+                            property_name: "ENV_RESTART".to_string(),
+                            received: "1000".to_string(),
+                            expected: "100".to_string(),
+                            bound_kind: BoundKind::Inclusive,
+                        }
+                    )
+                },
+                restart_required: collection! { "ENV_RESTART".to_string() },
+            }
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::same_value(collection! { "ENV_RESTART".to_string() => Some("10".to_string()) }, collection! {}, Ok(collection! {}))]
+    #[case::changed_value(collection! { "ENV_RESTART".to_string() => Some("20".to_string()) }, collection! {}, Ok(collection! { "ENV_RESTART".to_string() }))]
+    #[case::removed_and_updated(
+        collection! { "ENV_RESTART".to_string() => Some("20".to_string()) },
+        collection! { "ENV_RESTART".to_string() },
+        Err(Error::ChangeSetConflict { property_name: "ENV_RESTART".to_string() })
+    )]
+    fn test_validate_change_set_restart_required(
+        #[case] updated: BTreeMap<String, Option<String>>,
+        #[case] removed: BTreeSet<String>,
+        #[case] expected: ValidationResult<BTreeSet<String>>,
+    ) -> ValidationResult<()> {
+        let manager = ProductConfigManager::from_yaml_file("data/test_yamls/apply_mode.yaml")?;
+        let current = collection! {
+            "ENV_RESTART".to_string() => Some("10".to_string())
+        };
+        let changes = ChangeSet {
+            updated,
+            removed,
+            ..ChangeSet::default()
+        };
+
+        let validation = manager.validate_change_set(
+            "0.1.0",
+            "role_1",
+            &PropertyNameKind::Env,
+            &current,
+            &changes,
+        );
+
+        assert_eq!(
+            validation.map(|validation| validation.restart_required),
+            expected
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::not_migrated(
+        collection! { "ENV_OLD".to_string() => Some("1".to_string()) },
+        Error::DeprecatedNotMigrated { old: "ENV_OLD".to_string(), new: "ENV_NEW".to_string() }
+    )]
+    #[case::migrated(
+        collection! {
+            "ENV_OLD".to_string() => Some("1".to_string()),
+            "ENV_NEW".to_string() => Some("1".to_string())
+        },
+        Error::VersionDeprecated { property_name: "ENV_OLD".to_string(), product_version: "0.5.0".to_string(), deprecated_version: "0.5.0".to_string(), replacements: vec!["ENV_NEW".to_string()] }
+    )]
+    fn test_get_deprecated_not_migrated(
+        #[case] user_config: HashMap<String, Option<String>>,
+        #[case] expected: Error,
+    ) -> ValidationResult<()> {
+        let manager = ProductConfigManager::from_yaml_file("data/test_yamls/deprecated_for.yaml")?;
+
+        let result = manager.get("0.5.0", "role_1", &PropertyNameKind::Env, user_config)?;
+
+        assert_eq!(
+            result.get("ENV_OLD"),
+            Some(&PropertyValidationResult::Warn("1".to_string(), expected))
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::single_replacement(
         "0.5.0",
-        &PropertyNameKind::File("env.sh".to_string()),
-        "role_1",
-        "data/test_yamls/expands_role_required_expandee_role_not_required.yaml",
-        HashMap::new(),
-        macro_to_btree_map(collection!{
-            "ENV_PASSWORD".to_string() => None,
-            "ENV_ENABLE_PASSWORD".to_string() => Some("true".to_string())
-        }),
+        collection! { "ENV_OLD_SINGLE".to_string() => Some("1".to_string()) },
+        collection! { "ENV_NEW".to_string() => Some("1".to_string()) },
+        vec![Migration::Replaced { from: "ENV_OLD_SINGLE".to_string(), to: "ENV_NEW".to_string() }]
     )]
-    #[case::expands_role_not_required_expandee_role_not_required_no_user_input(
+    #[case::replacement_already_set(
         "0.5.0",
-        &PropertyNameKind::File("env.sh".to_string()),
-        "role_1",
-        "data/test_yamls/expands_role_not_required_expandee_role_not_required.yaml",
-        HashMap::new(),
-        BTreeMap::new(),
+        collection! { "ENV_OLD_SINGLE".to_string() => Some("1".to_string()), "ENV_NEW".to_string() => Some("2".to_string()) },
+        collection! { "ENV_OLD_SINGLE".to_string() => Some("1".to_string()), "ENV_NEW".to_string() => Some("2".to_string()) },
+        vec![Migration::ReplacementAlreadySet { from: "ENV_OLD_SINGLE".to_string(), to: "ENV_NEW".to_string() }]
     )]
-    #[case::expands_role_not_required_expandee_role_required_no_user_input(
+    #[case::multiple_replacements(
         "0.5.0",
-        &PropertyNameKind::File("env.sh".to_string()),
-        "role_1",
-        "data/test_yamls/expands_role_not_required_expandee_role_required.yaml",
-        HashMap::new(),
-        macro_to_btree_map(collection!{
-            "ENV_ENABLE_PASSWORD".to_string() => None,
-        }),
+        collection! { "ENV_OLD_MULTI".to_string() => Some("1".to_string()) },
+        collection! { "ENV_OLD_MULTI".to_string() => Some("1".to_string()) },
+        vec![Migration::AmbiguousReplacements { from: "ENV_OLD_MULTI".to_string(), replacements: vec!["ENV_NEW".to_string(), "ENV_OTHER".to_string()] }]
     )]
-    #[case::expands_role_not_required_expandee_role_required_with_user_input_1(
+    #[case::no_replacement(
         "0.5.0",
-        &PropertyNameKind::File("env.sh".to_string()),
-        "role_1",
-        "data/test_yamls/expands_role_not_required_expandee_role_required.yaml",
-        macro_to_hash_map(collection!{
-            "ENV_ENABLE_PASSWORD".to_string() => Some("true".to_string())
-        }),
-        macro_to_btree_map(collection!{
-            "ENV_ENABLE_PASSWORD".to_string() => Some("true".to_string()),
-        }),
+        collection! { "ENV_OLD_NONE".to_string() => Some("1".to_string()) },
+        collection! { "ENV_OLD_NONE".to_string() => Some("1".to_string()) },
+        vec![Migration::NoReplacement { from: "ENV_OLD_NONE".to_string() }]
+    )]
+    #[case::not_deprecated_yet(
+        "0.4.0",
+        collection! { "ENV_OLD_SINGLE".to_string() => Some("1".to_string()) },
+        collection! { "ENV_OLD_SINGLE".to_string() => Some("1".to_string()) },
+        vec![]
+    )]
+    fn test_migrate(
+        #[case] version: &str,
+        #[case] user_config: HashMap<String, Option<String>>,
+        #[case] expected_config: HashMap<String, Option<String>>,
+        #[case] expected_migrations: Vec<Migration>,
+    ) -> ValidationResult<()> {
+        let manager = ProductConfigManager::from_yaml_file("data/test_yamls/migrate.yaml")?;
+
+        let (migrated, migrations) =
+            manager.migrate(version, "role_1", &PropertyNameKind::Env, &user_config)?;
+
+        assert_eq!(migrated, expected_config);
+        assert_eq!(migrations, expected_migrations);
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_computed() -> ValidationResult<()> {
+        let manager = ProductConfigManager::from_yaml_file("data/test_yamls/computed.yaml")?;
+
+        let result = manager.get("0.1.0", "role_1", &PropertyNameKind::Env, HashMap::new())?;
+
+        assert_eq!(
+            result.get("ENV_TOTAL"),
+            Some(&PropertyValidationResult::Valid("1536".to_string()))
+        );
+        assert_eq!(
+            result.get("ENV_RATIO"),
+            Some(&PropertyValidationResult::Valid("2".to_string()))
+        );
+        assert_eq!(
+            result.get("ENV_BROKEN"),
+            Some(&PropertyValidationResult::Error(
+                String::new(),
+                Error::ComputedValueNotEvaluable {
+                    property_name: "ENV_BROKEN".to_string(),
+                    expression: "ENV_HEAP + ENV_UNKNOWN".to_string(),
+                    reason: "unknown reference 'ENV_UNKNOWN'".to_string(),
+                }
+            ))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_computed_errors() -> ValidationResult<()> {
+        let manager = ProductConfigManager::from_yaml_file("data/test_yamls/computed.yaml")?;
+
+        let result = manager.get(
+            "0.1.0",
+            "role_1",
+            &PropertyNameKind::Env,
+            macro_to_hash_map(collection! {
+                "ENV_HEAP".to_string() => Some("2048".to_string()),
+                "ENV_OFF_HEAP".to_string() => Some("0".to_string())
+            }),
+        )?;
+
+        // the computed value is validated against the bounds
+        assert_eq!(
+            result.get("ENV_TOTAL"),
+            Some(&PropertyValidationResult::Valid("2048".to_string()))
+        );
+        assert_eq!(
+            result.get("ENV_RATIO"),
+            Some(&PropertyValidationResult::Error(
+                String::new(),
+                Error::ComputedValueNotEvaluable {
+                    property_name: "ENV_RATIO".to_string(),
+                    expression: "ENV_HEAP / ENV_OFF_HEAP".to_string(),
+                    reason: "division by zero".to_string(),
+                }
+            ))
+        );
+
+        let result = manager.get(
+            "0.1.0",
+            "role_1",
+            &PropertyNameKind::Env,
+            macro_to_hash_map(collection! {
+                "ENV_HEAP".to_string() => Some("2048".to_string())
+            }),
+        )?;
+        assert!(matches!(
+            result.get("ENV_TOTAL"),
+            Some(PropertyValidationResult::Error(value, Error::PropertyValueOutOfBounds { .. })) if value == "2560"
+        ));
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::security("0.5.0", "security", vec!["ENV_TLS_ENABLED", "ENV_AUTH_ENABLED", "ENV_CACHE_SIZE"])]
+    #[case::version("0.1.0", "security", vec!["ENV_TLS_ENABLED", "ENV_AUTH_ENABLED"])]
+    #[case::performance("0.5.0", "performance", vec!["ENV_THREADS", "ENV_CACHE_SIZE"])]
+    #[case::unknown_tag("0.5.0", "unknown", vec![])]
+    fn test_properties_with_tag(
+        #[case] version: &str,
+        #[case] tag: &str,
+        #[case] expected: Vec<&str>,
+    ) -> ValidationResult<()> {
+        let manager = ProductConfigManager::from_yaml_file("data/test_yamls/tags.yaml")?;
+
+        assert_eq!(
+            manager.properties_with_tag(version, "role_1", &PropertyNameKind::Env, tag)?,
+            expected
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_with_tag() -> ValidationResult<()> {
+        let manager = ProductConfigManager::from_yaml_file("data/test_yamls/tags.yaml")?;
+        let user_config = macro_to_hash_map(collection! {
+            "ENV_TLS_ENABLED".to_string() => Some("true".to_string()),
+            "ENV_THREADS".to_string() => Some("4".to_string()),
+            "ENV_UNTAGGED".to_string() => Some("1".to_string()),
+            "ENV_OVERRIDE".to_string() => Some("override".to_string())
+        });
+
+        let result = manager.get_with_tag(
+            "0.5.0",
+            "role_1",
+            &PropertyNameKind::Env,
+            "security",
+            &user_config,
+        )?;
+
+        assert_eq!(
+            result,
+            collection! {
+                "ENV_TLS_ENABLED".to_string() => PropertyValidationResult::Valid("true".to_string()),
+                "ENV_AUTH_ENABLED".to_string() => PropertyValidationResult::Default("true".to_string())
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_for_target_kind() -> ValidationResult<()> {
+        let manager = ProductConfigManager::from_yaml_file("data/test_yamls/target_kind.yaml")?;
+        let user_config = macro_to_hash_map(collection! {
+            "ENV_PORT".to_string() => Some("8080".to_string()),
+            "ENV_ONLY".to_string() => Some("1".to_string()),
+            "ENV_OVERRIDE".to_string() => Some("override".to_string())
+        });
+
+        let (result, dropped) = manager.get_for_target_kind(
+            "0.1.0",
+            "role_1",
+            &PropertyNameKind::Env,
+            &PropertyNameKind::File("server.properties".to_string()),
+            &user_config,
+        )?;
+
+        assert_eq!(
+            result,
+            collection! {
+                "server.port".to_string() => PropertyValidationResult::Valid("8080".to_string()),
+                "ENV_OVERRIDE".to_string() => PropertyValidationResult::Unknown("override".to_string())
+            }
+        );
+        assert_eq!(
+            dropped,
+            vec![Error::PropertyNameNotFoundForKind {
+                property_name: "ENV_ONLY".to_string(),
+                kind: PropertyNameKind::File("server.properties".to_string()),
+            }]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_for_target_kind_cross_kind_lookup() -> ValidationResult<()> {
+        let manager = ProductConfigManager::from_yaml_file("data/test_yamls/target_kind.yaml")?
+            .with_cross_kind_lookup();
+        let user_config = macro_to_hash_map(collection! {
+            "ENV_PORT".to_string() => Some("8080".to_string())
+        });
+
+        let (result, dropped) = manager.get_for_target_kind(
+            "0.1.0",
+            "role_1",
+            &PropertyNameKind::File("server.properties".to_string()),
+            &PropertyNameKind::Cli,
+            &user_config,
+        )?;
+
+        assert_eq!(
+            result,
+            collection! {
+                "--port".to_string() => PropertyValidationResult::Valid("8080".to_string())
+            }
+        );
+        assert_eq!(dropped, vec![]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_for_target_kind_conflict() -> ValidationResult<()> {
+        let manager =
+            ProductConfigManager::from_yaml_file("data/test_yamls/target_kind_conflict.yaml")?;
+        let user_config = macro_to_hash_map(collection! {
+            "ENV_PORT".to_string() => Some("8080".to_string()),
+            "ENV_LEGACY_PORT".to_string() => Some("8081".to_string())
+        });
+
+        let result = manager.get_for_target_kind(
+            "0.1.0",
+            "role_1",
+            &PropertyNameKind::Env,
+            &PropertyNameKind::File("server.properties".to_string()),
+            &user_config,
+        );
+
+        assert_eq!(
+            result,
+            Err(Error::TargetPropertyNameConflict {
+                target_name: "server.port".to_string(),
+                kind: PropertyNameKind::File("server.properties".to_string()),
+                first: "ENV_LEGACY_PORT".to_string(),
+                second: "ENV_PORT".to_string(),
+            })
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::java_properties(OutputFormat::JavaProperties, "ENV_NONE=2\nENV_RESTART=1\n")]
+    #[case::env(OutputFormat::Env, "ENV_NONE='2'\nENV_RESTART='1'\n")]
+    #[case::yaml(OutputFormat::Yaml, "ENV_NONE: '2'\nENV_RESTART: '1'\n")]
+    fn test_render(#[case] format: OutputFormat, #[case] expected: &str) -> ValidationResult<()> {
+        let manager = ProductConfigManager::from_yaml_file("data/test_yamls/apply_mode.yaml")?;
+        let user_config = macro_to_hash_map(collection! {
+            "ENV_RESTART".to_string() => Some("1".to_string()),
+            "ENV_NONE".to_string() => Some("2".to_string())
+        });
+
+        let rendered = manager.render(
+            "0.1.0",
+            "role_1",
+            &PropertyNameKind::Env,
+            &user_config,
+            format,
+        );
+
+        assert_eq!(rendered, Ok(expected.to_string()));
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::java_properties(
+        OutputFormat::JavaProperties,
+        "listeners=PLAINTEXT\\://\\:9092\nlisteners=SSL\\://\\:9093\n"
     )]
-    #[case::expands_role_not_required_expandee_role_required_with_user_input_2(
-        "0.5.0",
-        &PropertyNameKind::File("env.sh".to_string()),
-        "role_1",
-        "data/test_yamls/expands_role_not_required_expandee_role_required.yaml",
-        macro_to_hash_map(collection!{
-            "ENV_PASSWORD".to_string() => Some("secret".to_string())
-        }),
-        macro_to_btree_map(collection!{
-            "ENV_PASSWORD".to_string() => Some("secret".to_string()),
-            "ENV_ENABLE_PASSWORD".to_string() => Some("true".to_string()),
-        }),
+    #[case::json(
+        OutputFormat::Json,
+        "{\n  \"listeners\": \"PLAINTEXT://:9092\\nSSL://:9093\"\n}"
     )]
-    #[case::expands_role_required_expandee_role_required_no_user_input(
-        "0.5.0",
-        &PropertyNameKind::File("env.sh".to_string()),
-        "role_1",
-        "data/test_yamls/expands_role_required_expandee_role_required.yaml",
-        HashMap::new(),
-        macro_to_btree_map(collection!{
-            "ENV_PASSWORD".to_string() => None,
-            "ENV_ENABLE_PASSWORD".to_string() => Some("true".to_string()),
-        }),
+    fn test_render_repeatable(
+        #[case] format: OutputFormat,
+        #[case] expected: &str,
+    ) -> ValidationResult<()> {
+        let manager = ProductConfigManager::from_yaml_file("data/test_yamls/repeatable.yaml")?;
+        let user_config = macro_to_hash_map(collection! {
+            "listeners".to_string() => Some("PLAINTEXT://:9092\nSSL://:9093".to_string())
+        });
+
+        let rendered = manager.render(
+            "0.5.0",
+            "role_1",
+            &PropertyNameKind::File("server.properties".to_string()),
+            &user_config,
+            format,
+        );
+
+        assert_eq!(rendered, Ok(expected.to_string()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_render_fails_on_error() -> ValidationResult<()> {
+        let manager = ProductConfigManager::from_yaml_file("data/test_yamls/apply_mode.yaml")?;
+        let user_config = macro_to_hash_map(collection! {
+            "ENV_RESTART".to_string() => Some("not_an_integer".to_string())
+        });
+
+        let result = manager.render(
+            "0.1.0",
+            "role_1",
+            &PropertyNameKind::Env,
+            &user_config,
+            OutputFormat::JavaProperties,
+        );
+
+        assert_eq!(
+            result,
+            Err(ConfigError::InvalidProperty {
+                property_name: "ENV_RESTART".to_string(),
+                source: Box::new(Error::DatatypeNotMatching {
+                    property_name: "ENV_RESTART".to_string(),
+                    value: "not_an_integer".to_string(),
+                    datatype: "i64".to_string()
+                })
+            })
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::enabled(Some("true"), Some(PropertyValidationResult::Valid("/stackable/certs/tls.crt".to_string())))]
+    #[case::disabled(Some("false"), None)]
+    #[case::default_disabled(None, None)]
+    fn test_get_expansion_trigger_value(
+        #[case] enable_ssl: Option<&str>,
+        #[case] expected: Option<PropertyValidationResult>,
+    ) -> ValidationResult<()> {
+        let manager =
+            ProductConfigManager::from_yaml_file("data/test_yamls/expansion_trigger.yaml")?;
+        let mut user_config = HashMap::new();
+        if let Some(enable_ssl) = enable_ssl {
+            user_config.insert("ENV_ENABLE_SSL".to_string(), Some(enable_ssl.to_string()));
+        }
+
+        let result = manager.get("0.1.0", "role_1", &PropertyNameKind::Env, user_config)?;
+
+        assert_eq!(result.get("ENV_SSL_CERTIFICATE_PATH"), expected.as_ref());
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::same_value("true", PropertyValidationResult::Valid("true".to_string()))]
+    #[case::overridden("false", PropertyValidationResult::Warn(
+        "false".to_string(),
+        Error::ExpansionOverriddenByUser {
+            source: "ENV_PASSWORD".to_string(),
+            target: "ENV_ENABLE_PASSWORD".to_string(),
+            expansion_value: "true".to_string(),
+            user_value: "false".to_string(),
+        }
+    ))]
+    fn test_get_expansion_overridden_by_user(
+        #[case] user_value: &str,
+        #[case] expected: PropertyValidationResult,
+    ) -> ValidationResult<()> {
+        let manager = ProductConfigManager::from_yaml_file(
+            "data/test_yamls/expands_role_required_expandee_role_not_required.yaml",
+        )?;
+        let result = manager.get(
+            "0.5.0",
+            "role_1",
+            &PropertyNameKind::File("env.sh".to_string()),
+            macro_to_hash_map(collection! {
+                "ENV_PASSWORD".to_string() => Some("secret".to_string()),
+                "ENV_ENABLE_PASSWORD".to_string() => Some(user_value.to_string())
+            }),
+        )?;
+
+        assert_eq!(result.get("ENV_ENABLE_PASSWORD"), Some(&expected));
+        assert_eq!(
+            result.get("ENV_PASSWORD"),
+            Some(&PropertyValidationResult::Valid("secret".to_string()))
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("0.1.0", PropertyValidationResult::RecommendedDefault("2".to_string()))]
+    #[case("0.5.0", PropertyValidationResult::Warn(
+        "2".to_string(),
+        Error::VersionDeprecated { property_name: "ENV_LEVEL".to_string(), product_version: "0.5.0".to_string(), deprecated_version: "0.5.0".to_string(), replacements: vec![] }
+    ))]
+    fn test_snake_case_keys(
+        #[case] version: &str,
+        #[case] expected: PropertyValidationResult,
+    ) -> ValidationResult<()> {
+        let manager = ProductConfigManager::from_yaml_file("data/test_yamls/snake_case.yaml")?;
+        let result = manager.get_with_apply_mode(
+            version,
+            "role_1",
+            &PropertyNameKind::Env,
+            &HashMap::new(),
+        )?;
+
+        assert_eq!(
+            result.get("ENV_LEVEL"),
+            Some(&(expected, ApplyMode::Restart))
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("2Gi", PropertyValidationResult::Valid("2048Mi".to_string()))]
+    #[case("256", PropertyValidationResult::Error(
+        "256".to_string(),
+        Error::PropertyValueOutOfBounds {
+            property_name: "ENV_MEMORY".to_string(),
+            received: "256".to_string(),
+            expected: "256".to_string(),
+            bound_kind: BoundKind::Exclusive
+        }
+    ))]
+    fn test_camel_case_datatype_keys(
+        #[case] value: &str,
+        #[case] expected: PropertyValidationResult,
+    ) -> ValidationResult<()> {
+        let manager =
+            ProductConfigManager::from_yaml_file("data/test_yamls/camel_case_datatype.yaml")?;
+        let result = manager.get(
+            "0.5.0",
+            "role_1",
+            &PropertyNameKind::Env,
+            macro_to_hash_map(collection! {
+                "ENV_MEMORY".to_string() => Some(value.to_string())
+            }),
+        )?;
+
+        assert_eq!(result.get("ENV_MEMORY"), Some(&expected));
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_all() -> ValidationResult<()> {
+        let manager =
+            ProductConfigManager::from_yaml_file("data/test_yamls/test_product_config.yaml")?;
+        let env_sh = PropertyNameKind::File("env.sh".to_string());
+        let my_config = PropertyNameKind::File("my.config".to_string());
+
+        let mut user_config_by_role_kind = HashMap::new();
+        for role in ["role_1", "role_2"] {
+            user_config_by_role_kind.insert(
+                (role.to_string(), env_sh.clone()),
+                macro_to_hash_map(collection! {
+                    "ENV_INTEGER_PORT_MIN_MAX".to_string() => Some("12345".to_string())
+                }),
+            );
+            user_config_by_role_kind.insert(
+                (role.to_string(), my_config.clone()),
+                macro_to_hash_map(collection! {
+                    "conf.float".to_string() => Some("50".to_string())
+                }),
+            );
+        }
+
+        let result = manager.get_all("0.5.0", &user_config_by_role_kind)?;
+
+        assert_eq!(result.len(), 2);
+        for ((role, kind), user_config) in user_config_by_role_kind {
+            assert_eq!(
+                result.get(&role).and_then(|kinds| kinds.get(&kind)),
+                Some(&manager.get_ref("0.5.0", &role, &kind, &user_config)?)
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_with_value_transformer() -> ValidationResult<()> {
+        let manager =
+            ProductConfigManager::from_yaml_file("data/test_yamls/test_product_config.yaml")?
+                .with_value_transformer(|_name, value| {
+                    value.strip_prefix("plain:").unwrap_or(value).to_string()
+                });
+
+        let result = manager.get(
+            "0.5.0",
+            "role_1",
+            &PropertyNameKind::File("env.sh".to_string()),
+            macro_to_hash_map(collection! {
+                "ENV_INTEGER_PORT_MIN_MAX".to_string() => Some("plain:12345".to_string())
+            }),
+        )?;
+
+        assert_eq!(
+            result.get("ENV_INTEGER_PORT_MIN_MAX"),
+            Some(&PropertyValidationResult::Valid("12345".to_string()))
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::documented("ENV_DESCRIBED", Some(PropertyDocs {
+        description: Some("The number of worker threads.".to_string()),
+        comment: Some("Defaults to the number of cores.".to_string()),
+        additional_doc: vec!["https://example.com/docs/workers".to_string()],
+    }))]
+    #[case::undocumented("ENV_UNDESCRIBED", Some(PropertyDocs::default()))]
+    #[case::unknown("ENV_UNKNOWN", None)]
+    fn test_property_docs(
+        #[case] name: &str,
+        #[case] expected: Option<PropertyDocs>,
+    ) -> ValidationResult<()> {
+        let manager = ProductConfigManager::from_yaml_file("data/test_yamls/descriptions.yaml")?;
+
+        assert_eq!(
+            manager.property_docs("0.5.0", "role_1", &PropertyNameKind::Env, name)?,
+            expected
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::valid(
+        "/opt/stackable/zookeeper-operator/pki",
+        PropertyValidationResult::Valid("/opt/stackable/zookeeper-operator/pki".to_string())
     )]
-    #[case::expands_role_required_expandee_role_required_with_user_input1(
-        "0.5.0",
-        &PropertyNameKind::File("env.sh".to_string()),
-        "role_1",
-        "data/test_yamls/expands_role_required_expandee_role_required.yaml",
-        macro_to_hash_map(collection!{
-            "ENV_PASSWORD".to_string() => Some("secret".to_string())
-        }),
-        macro_to_btree_map(collection!{
-            "ENV_PASSWORD".to_string() => Some("secret".to_string()),
-            "ENV_ENABLE_PASSWORD".to_string() => Some("true".to_string()),
-        }),
+    #[case::invalid(
+        "CAFE",
+        PropertyValidationResult::Error("CAFE".to_string(), Error::DatatypeRegexNotMatching { property_name: "ENV_SSL_CERTIFICATE_PATH".to_string(), value: "CAFE".to_string() })
     )]
-    #[case::test_product_config_no_user_input(
-        "0.5.0",
-        &PropertyNameKind::File("env.sh".to_string()),
-        "role_1",
-        "data/test_yamls/test_product_config.yaml",
-        HashMap::new(),
-        macro_to_btree_map(collection!{
-            "ENV_FLOAT".to_string() => Some("50.0".to_string()),
-            "ENV_INTEGER_PORT_MIN_MAX".to_string() => Some("20000".to_string()),
-            "ENV_PROPERTY_STRING_DEPRECATED".to_string() => None,
-            "ENV_PASSWORD".to_string() => None,
-            "ENV_ENABLE_PASSWORD".to_string() => Some("true".to_string()),
-    }),
+    fn test_get_cross_kind_lookup(
+        #[case] value: &str,
+        #[case] expected: PropertyValidationResult,
+    ) -> ValidationResult<()> {
+        let manager =
+            ProductConfigManager::from_yaml_file("data/test_yamls/validate_directory.yaml")?
+                .with_cross_kind_lookup();
+
+        let result = manager.get(
+            "0.5.0",
+            "role_1",
+            &PropertyNameKind::File("should_not_be_found_therefore_is_an_override".to_string()),
+            macro_to_hash_map(collection! {
+                "ENV_SSL_CERTIFICATE_PATH".to_string() => Some(value.to_string())
+            }),
+        )?;
+
+        assert_eq!(result.get("ENV_SSL_CERTIFICATE_PATH"), Some(&expected));
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::enabled(true, Some(PropertyValidationResult::Warn(String::new(), Error::NoValueResolved { property_name: "ENV_NO_VALUE".to_string() })))]
+    #[case::disabled(false, None)]
+    fn test_get_unresolved_value_warnings(
+        #[case] enabled: bool,
+        #[case] expected: Option<PropertyValidationResult>,
+    ) -> ValidationResult<()> {
+        let mut manager =
+            ProductConfigManager::from_yaml_file("data/test_yamls/unresolved_values.yaml")?;
+        if enabled {
+            manager = manager.with_unresolved_value_warnings();
+        }
+
+        let result = manager.get(
+            "0.1.0",
+            "role_1",
+            &PropertyNameKind::Env,
+            macro_to_hash_map(collection! {
+                "ENV_USER".to_string() => Some("user".to_string())
+            }),
+        )?;
+
+        assert_eq!(result.get("ENV_NO_VALUE"), expected.as_ref());
+        // optional properties with a default value or a user value are not reported
+        assert_eq!(result.get("ENV_DEFAULT"), None);
+        assert_eq!(
+            result.get("ENV_USER"),
+            Some(&PropertyValidationResult::Valid("user".to_string()))
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::unset_absent(ExplicitNone::Unset, "ENV_DEFAULT", false, None)]
+    #[case::unset_none(
+        ExplicitNone::Unset,
+        "ENV_DEFAULT",
+        true,
+        Some(PropertyValidationResult::Error(String::new(), Error::PropertyValueMissing { property_name: "ENV_DEFAULT".to_string() }))
     )]
-    #[case::expands_role_required_no_copy_no_user_input(
-        "0.5.0",
-        &PropertyNameKind::File("env.sh".to_string()),
-        "role_1",
-        "data/test_yamls/expands_role_required_no_copy.yaml",
-        HashMap::new(),
-        macro_to_btree_map(collection!{
-            "ENV_SSL_CERTIFICATE_PATH".to_string() => Some("path/to/certificates".to_string()),
-            "ENV_SSL_ENABLED".to_string() => Some("true".to_string()),
-    }),
+    #[case::use_default_absent(ExplicitNone::UseDefault, "ENV_DEFAULT", false, None)]
+    #[case::use_default_none(
+        ExplicitNone::UseDefault,
+        "ENV_DEFAULT",
+        true,
+        Some(PropertyValidationResult::Default("default".to_string()))
     )]
-    #[case::expands_role_not_required_no_copy_no_user_input(
-        "0.5.0",
-        &PropertyNameKind::File("env.sh".to_string()),
-        "role_1",
-        "data/test_yamls/expands_role_not_required_no_copy.yaml",
-        HashMap::new(),
-        BTreeMap::new(),
+    #[case::use_default_none_without_default(
+        ExplicitNone::UseDefault,
+        "ENV_NO_VALUE",
+        true,
+        Some(PropertyValidationResult::Error(String::new(), Error::PropertyValueMissing { property_name: "ENV_NO_VALUE".to_string() }))
     )]
-    fn test_get_and_expand_properties(
+    fn test_get_explicit_none(
+        #[case] explicit_none: ExplicitNone,
+        #[case] property_name: &str,
+        #[case] provided: bool,
+        #[case] expected: Option<PropertyValidationResult>,
+    ) -> ValidationResult<()> {
+        let manager =
+            ProductConfigManager::from_yaml_file("data/test_yamls/unresolved_values.yaml")?
+                .with_explicit_none(explicit_none);
+
+        let mut user_config = HashMap::new();
+        if provided {
+            user_config.insert(property_name.to_string(), None);
+        }
+
+        let result = manager.get("0.1.0", "role_1", &PropertyNameKind::Env, user_config)?;
+
+        assert_eq!(result.get(property_name), expected.as_ref());
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::caret("1.4.2", Some(PropertyValidationResult::Default("caret".to_string())))]
+    #[case::caret_excluded("1.1.0", Some(PropertyValidationResult::Error(String::new(), Error::PropertyValueMissing { property_name: "ENV_VERSIONED".to_string() })))]
+    #[case::tilde("2.1.7", Some(PropertyValidationResult::Default("tilde".to_string())))]
+    #[case::tilde_excluded("2.2.0", Some(PropertyValidationResult::Error(String::new(), Error::PropertyValueMissing { property_name: "ENV_VERSIONED".to_string() })))]
+    #[case::comma_separated("3.5.0", Some(PropertyValidationResult::Default("range".to_string())))]
+    #[case::comma_separated_excluded("4.0.0", Some(PropertyValidationResult::Default("from".to_string())))]
+    fn test_get_version_req(
         #[case] version: &str,
-        #[case] kind: &PropertyNameKind,
-        #[case] role: &str,
-        #[case] path: &str,
-        #[case] user_data: HashMap<String, Option<String>>,
-        #[case] expected: BTreeMap<String, Option<String>>,
+        #[case] expected: Option<PropertyValidationResult>,
+    ) -> ValidationResult<()> {
+        let manager = ProductConfigManager::from_yaml_file("data/test_yamls/version_req.yaml")?;
+
+        let result = manager.get(version, "role_1", &PropertyNameKind::Env, HashMap::new())?;
+
+        assert_eq!(result.get("ENV_VERSIONED"), expected.as_ref());
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_version_not_supported() -> ValidationResult<()> {
+        let manager = ProductConfigManager::from_yaml_file("data/test_yamls/prerelease.yaml")?;
+
+        let result = manager.get(
+            "1.1.0",
+            "role_1",
+            &PropertyNameKind::Env,
+            macro_to_hash_map(collection! {
+                "ENV_NEW".to_string() => Some("value".to_string()),
+                "ENV_OVERRIDE".to_string() => Some("override".to_string())
+            }),
+        )?;
+
+        assert_eq!(
+            result.get("ENV_NEW"),
+            Some(&PropertyValidationResult::Warn(
+                "value".to_string(),
+                Error::VersionNotSupported {
+                    property_name: PropertyName {
+                        name: "ENV_NEW".to_string(),
+                        kind: PropertyNameKind::Env,
+                        datatype: None,
+                    },
+                    product_version: "1.1.0".to_string(),
+                    required_version: "1.2.0".to_string(),
+                }
+            ))
+        );
+        assert_eq!(
+            result.get("ENV_OVERRIDE"),
+            Some(&PropertyValidationResult::Unknown("override".to_string()))
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::release("1.2.0", false, Some(PropertyValidationResult::Default("new".to_string())))]
+    #[case::rc("1.2.0-rc1", false, None)]
+    #[case::rc_as_release("1.2.0-rc1", true, Some(PropertyValidationResult::Default("new".to_string())))]
+    #[case::beta("1.2.0-beta.2", false, None)]
+    #[case::beta_as_release("1.2.0-beta.2", true, Some(PropertyValidationResult::Default("new".to_string())))]
+    #[case::build("1.2.0+build.5", false, Some(PropertyValidationResult::Default("new".to_string())))]
+    #[case::build_as_release("1.2.0+build.5", true, Some(PropertyValidationResult::Default("new".to_string())))]
+    #[case::older_rc_as_release("1.1.0-rc1", true, None)]
+    fn test_get_prerelease_as_release(
+        #[case] version: &str,
+        #[case] prerelease_as_release: bool,
+        #[case] expected: Option<PropertyValidationResult>,
+    ) -> ValidationResult<()> {
+        let mut manager = ProductConfigManager::from_yaml_file("data/test_yamls/prerelease.yaml")?;
+        if prerelease_as_release {
+            manager = manager.with_prerelease_as_release();
+        }
+
+        let result = manager.get(version, "role_1", &PropertyNameKind::Env, HashMap::new())?;
+
+        assert_eq!(result.get("ENV_NEW"), expected.as_ref());
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::two_components("1.5", true, Ok("1.5.0"))]
+    #[case::one_component("1", true, Ok("1.0.0"))]
+    #[case::semver("1.5.2", true, Ok("1.5.2"))]
+    #[case::strict("1.5", false, Err(()))]
+    #[case::invalid("1.x", true, Err(()))]
+    #[case::too_many_components("1.5.0.1", true, Err(()))]
+    #[case::empty_component("1.", true, Err(()))]
+    fn test_parse_version_lenient(
+        #[case] version: &str,
+        #[case] lenient: bool,
+        #[case] expected: Result<&str, ()>,
     ) {
-        let product_version = StackableVersion::parse(version).unwrap();
+        let mut manager =
+            ProductConfigManager::from_yaml_file("data/test_yamls/prerelease.yaml").unwrap();
+        if lenient {
+            manager = manager.with_lenient_versions();
+        }
+
+        let result = manager.parse_version(version);
+
+        match expected {
+            Ok(expected) => assert_eq!(result.unwrap().to_string(), expected),
+            Err(()) => assert!(
+                matches!(result, Err(Error::InvalidVersion { version: invalid, .. }) if invalid == version)
+            ),
+        }
+    }
+
+    #[rstest]
+    #[case::rc("1.2.0-rc1", true)]
+    #[case::beta("1.2.0-beta", true)]
+    #[case::build("1.2.0+build", true)]
+    #[case::older_rc("1.1.9-rc1", false)]
+    fn test_prerelease_as_release_supported(
+        #[case] version: &str,
+        #[case] expected: bool,
+    ) -> ValidationResult<()> {
+        let manager = ProductConfigManager::from_yaml_file("data/test_yamls/prerelease.yaml")?
+            .with_prerelease_as_release();
+        let property = &manager.config.properties[0];
+        let version = manager.parse_version(version)?;
+
+        assert_eq!(property.is_version_supported(&version)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_validated() {
+        let valid = std::fs::read_to_string("data/test_yamls/repeatable.yaml").unwrap();
+        assert!(ProductConfigManager::load_validated(&valid).is_ok());
+
+        let duplicates =
+            std::fs::read_to_string("data/test_yamls/self_check_duplicates.yaml").unwrap();
+        assert_eq!(
+            ProductConfigManager::load_validated(&duplicates).err(),
+            Some(Error::SelfCheckFailed {
+                errors: vec![Error::DuplicateFilePropertyName {
+                    property_name: "log.dirs".to_string(),
+                    file: "server.properties".to_string(),
+                }]
+            })
+        );
+
+        // warnings are treated as errors
+        let not_portable =
+            std::fs::read_to_string("data/test_yamls/self_check_regex.yaml").unwrap();
+        assert_eq!(
+            ProductConfigManager::load_validated(&not_portable).err(),
+            Some(Error::SelfCheckFailed {
+                errors: vec![Error::RegexNotPortable {
+                    unit: "lookahead".to_string(),
+                    regex: "^(?=[a-z])\\w+$".to_string(),
+                    features: vec!["look-around".to_string()],
+                }]
+            })
+        );
+    }
+
+    #[test]
+    fn test_from_yaml_file_includes() -> ValidationResult<()> {
+        let manager = ProductConfigManager::from_yaml_file("data/test_yamls/includes/main.yaml")?;
+
+        // the logging fragment is included twice, but merged once
+        assert_eq!(manager.config.properties.len(), 3);
+        assert!(manager.config.includes.is_empty());
+
+        let result = manager.get(
+            "0.1.0",
+            "role_1",
+            &PropertyNameKind::Env,
+            macro_to_hash_map(collection! {
+                "ENV_LOG_DIR".to_string() => Some("log".to_string())
+            }),
+        )?;
+        assert_eq!(
+            result,
+            collection! {
+                "ENV_CLUSTER_NAME".to_string() => PropertyValidationResult::Default("simple".to_string()),
+                "ENV_LOG_DIR".to_string() => PropertyValidationResult::Error("log".to_string(), Error::DatatypeRegexNotMatching {
+                    property_name: "ENV_LOG_DIR".to_string(),
+                    value: "log".to_string(),
+                }),
+                "ENV_TLS_ENABLED".to_string() => PropertyValidationResult::Default("false".to_string())
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_yaml_file_cyclic_includes() {
+        let result = ProductConfigManager::from_yaml_file("data/test_yamls/includes/cyclic.yaml");
+
+        assert_eq!(
+            result.err(),
+            Some(Error::CyclicInclude {
+                chain: vec![
+                    PathBuf::from("data/test_yamls/includes/cyclic.yaml"),
+                    PathBuf::from("data/test_yamls/includes/fragments/cyclic.yaml"),
+                    PathBuf::from("data/test_yamls/includes/fragments/../cyclic.yaml"),
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn test_from_str_includes_not_supported() {
+        let contents = std::fs::read_to_string("data/test_yamls/includes/main.yaml").unwrap();
+
+        assert_eq!(
+            ProductConfigManager::from_str(&contents).err(),
+            Some(Error::IncludesNotSupported {
+                includes: vec![
+                    "fragments/logging.yaml".to_string(),
+                    "fragments/tls.yaml".to_string()
+                ]
+            })
+        );
+    }
+
+    #[rstest]
+    #[case::required_empty("role_1", "", true, PropertyValidationResult::Error("".to_string(), Error::PropertyValueEmpty { property_name: "ENV_CLUSTER_NAME".to_string() }))]
+    #[case::required_blank("role_1", "  ", true, PropertyValidationResult::Error("  ".to_string(), Error::PropertyValueEmpty { property_name: "ENV_CLUSTER_NAME".to_string() }))]
+    #[case::required_non_empty("role_1", "simple", true, PropertyValidationResult::Valid("simple".to_string()))]
+    #[case::optional_empty("role_2", "", true, PropertyValidationResult::Valid("".to_string()))]
+    #[case::disabled("role_1", "", false, PropertyValidationResult::Valid("".to_string()))]
+    fn test_get_non_empty_if_required(
+        #[case] role: &str,
+        #[case] value: &str,
+        #[case] non_empty_if_required: bool,
+        #[case] expected: PropertyValidationResult,
+    ) -> ValidationResult<()> {
+        let mut manager =
+            ProductConfigManager::from_yaml_file("data/test_yamls/required_values.yaml")?;
+        if non_empty_if_required {
+            manager = manager.with_non_empty_if_required();
+        }
+
+        let result = manager.get(
+            "0.1.0",
+            role,
+            &PropertyNameKind::Env,
+            macro_to_hash_map(collection! {
+                "ENV_CLUSTER_NAME".to_string() => Some(value.to_string())
+            }),
+        )?;
+
+        assert_eq!(result.get("ENV_CLUSTER_NAME"), Some(&expected));
+        Ok(())
+    }
+
+    fn built_port_property(default_value: &str) -> ValidationResult<PropertySpec> {
+        let port = Unit {
+            name: "port".to_string(),
+            regex: StackableRegex::new("^[0-9]{1,5}$")?,
+            examples: Some(vec!["8080".to_string()]),
+            comment: None,
+        };
+        let mut property = PropertySpec::new(
+            vec![PropertyName {
+                name: "ENV_PORT".to_string(),
+                kind: PropertyNameKind::Env,
+                datatype: None,
+            }],
+            Datatype::String {
+                min: None,
+                max: None,
+                min_bound_kind: None,
+                max_bound_kind: None,
+                unit: Some(port),
+                accepted_units: None,
+                default_unit: None,
+                charset: None,
+                length_mode: None,
+                allow_newlines: None,
+                max_lines: None,
+                format: None,
+            },
+            vec![Role {
+                name: "role_1".to_string(),
+                required: true,
+                no_copy: None,
+            }],
+            StackableVersion::new(0, 1, 0),
+        );
+        property.default_values = Some(vec![PropertyValueSpec {
+            from_version: None,
+            to_version: None,
+            version_req: None,
+            value: default_value.to_string(),
+        }]);
+        Ok(property)
+    }
+
+    #[test]
+    fn test_product_config_builder_exclusive_bounds_conflict() -> ValidationResult<()> {
+        let mut property = built_port_property("8080")?;
+        property.datatype = Datatype::Integer {
+            min: Some("1".to_string()),
+            max: None,
+            min_bound_kind: None,
+            max_bound_kind: None,
+            exclusive_min: Some("0".to_string()),
+            exclusive_max: None,
+            unit: None,
+            accepted_units: None,
+            default_unit: None,
+            semantic: None,
+            allowed_numbers: None,
+        };
+
+        assert_eq!(
+            ProductConfigBuilder::new("0.1.0")
+                .with_property(property)
+                .build(),
+            Err(Error::SelfCheckFailed {
+                errors: vec![Error::ConflictingBounds {
+                    property_name: "ENV_PORT".to_string(),
+                    side: "min".to_string(),
+                }]
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_product_config_builder() -> ValidationResult<()> {
+        let config = ProductConfigBuilder::new("0.1.0")
+            .with_property(built_port_property("8080")?)
+            .build()?;
+        let manager = ProductConfigManager::from(config);
+
+        let result = manager.get(
+            "0.1.0",
+            "role_1",
+            &PropertyNameKind::Env,
+            macro_to_hash_map(collection! {}),
+        )?;
+        assert_eq!(
+            result.get("ENV_PORT"),
+            Some(&PropertyValidationResult::Default("8080".to_string()))
+        );
+
+        let result = manager.get(
+            "0.1.0",
+            "role_1",
+            &PropertyNameKind::Env,
+            macro_to_hash_map(collection! {
+                "ENV_PORT".to_string() => Some("http".to_string())
+            }),
+        )?;
+        assert_eq!(
+            result.get("ENV_PORT"),
+            Some(&PropertyValidationResult::Error(
+                "http".to_string(),
+                Error::DatatypeRegexNotMatching {
+                    property_name: "ENV_PORT".to_string(),
+                    value: "http".to_string(),
+                }
+            ))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_product_config_builder_self_check() -> ValidationResult<()> {
+        let result = ProductConfigBuilder::new("0.1.0")
+            .with_property(built_port_property("http")?)
+            .build();
+
+        assert!(matches!(
+            result,
+            Err(Error::SelfCheckFailed { errors }) if errors.len() == 1
+        ));
+        assert!(matches!(
+            StackableRegex::new("^[0-9"),
+            Err(Error::InvalidRegex { regex, .. }) if regex == "^[0-9"
+        ));
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::examples("directory", Some(vec!["/stackable/data".to_string()]))]
+    #[case::stale_examples("port", Some(vec!["8080".to_string(), "http".to_string()]))]
+    #[case::no_examples("password", None)]
+    #[case::unknown_unit("url", None)]
+    fn test_unit_examples(#[case] unit_name: &str, #[case] expected: Option<Vec<String>>) {
+        let manager =
+            ProductConfigManager::from_yaml_file("data/test_yamls/unit_examples.yaml").unwrap();
+
+        assert_eq!(manager.unit_examples(unit_name), expected);
+    }
+
+    #[test]
+    fn test_load_validated_unit_examples() {
+        let contents = std::fs::read_to_string("data/test_yamls/unit_examples.yaml").unwrap();
+        assert_eq!(
+            ProductConfigManager::load_validated(&contents).err(),
+            Some(Error::SelfCheckFailed {
+                errors: vec![Error::UnitExampleNotMatching {
+                    unit: "port".to_string(),
+                    regex: "^[0-9]{1,5}$".to_string(),
+                    example: "http".to_string(),
+                }]
+            })
+        );
+
+        let fixed = contents.replace("- \"http\"", "- \"443\"");
+        assert!(ProductConfigManager::load_validated(&fixed).is_ok());
+    }
+
+    #[test]
+    fn test_json_schema() {
+        let schema = ProductConfigManager::json_schema();
+        let properties = &schema.schema.object.as_ref().unwrap().properties;
+
+        for property in ["version", "spec", "properties"] {
+            assert!(properties.contains_key(property), "missing {property}");
+        }
+        assert!(schema.definitions.contains_key("StackableVersion"));
+        assert!(schema.definitions.contains_key("StackableRegex"));
+    }
+
+    #[rstest]
+    #[case::within_limit("true", "false", vec![])]
+    #[case::over_limit("true", "true", vec![
+        Error::CountConstraintAboveMax { constraint: "exclusive-modes".to_string(), count: 2, max: 1 },
+    ])]
+    #[case::below_limit("false", "false", vec![
+        Error::CountConstraintBelowMin { constraint: "exclusive-modes".to_string(), count: 0, min: 1 },
+    ])]
+    fn test_check_count_constraints(
+        #[case] fast: &str,
+        #[case] safe: &str,
+        #[case] expected: Vec<Error>,
+    ) -> ValidationResult<()> {
+        let manager =
+            ProductConfigManager::from_yaml_file("data/test_yamls/count_constraints.yaml")?;
+        let result = manager.get(
+            "0.1.0",
+            "role_1",
+            &PropertyNameKind::Env,
+            macro_to_hash_map(collection! {
+                "ENV_FAST_MODE".to_string() => Some(fast.to_string()),
+                "ENV_SAFE_MODE".to_string() => Some(safe.to_string())
+            }),
+        )?;
+
+        assert_eq!(manager.check_count_constraints(&result), expected);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::defaults(None, None, vec![])]
+    #[case::agreeing(Some("/data/log"), Some("/data/log"), vec![])]
+    #[case::one_side_only(Some("/data/log"), None, vec![
+        Error::MirroredValuesDiverge {
+            mirror: "log-directory".to_string(),
+            role: "role_1".to_string(),
+            values: collection! {
+                "ENV_LOG_DIR (env)".to_string() => "/data/log".to_string(),
+                "log.dirs (file 'server.properties')".to_string() => "/stackable/log".to_string()
+            },
+        },
+    ])]
+    #[case::conflicting(Some("/data/log"), Some("/var/log"), vec![
+        Error::MirroredValuesDiverge {
+            mirror: "log-directory".to_string(),
+            role: "role_1".to_string(),
+            values: collection! {
+                "ENV_LOG_DIR (env)".to_string() => "/data/log".to_string(),
+                "log.dirs (file 'server.properties')".to_string() => "/var/log".to_string()
+            },
+        },
+    ])]
+    fn test_check_mirrors(
+        #[case] env_value: Option<&str>,
+        #[case] file_value: Option<&str>,
+        #[case] expected: Vec<Error>,
+    ) -> ValidationResult<()> {
+        let manager = ProductConfigManager::from_yaml_file("data/test_yamls/mirrors.yaml")?;
+        let file_kind = PropertyNameKind::File("server.properties".to_string());
+
+        let mut user_config_by_role_kind = HashMap::new();
+        user_config_by_role_kind.insert(
+            ("role_1".to_string(), PropertyNameKind::Env),
+            env_value
+                .map(|value| ("ENV_LOG_DIR".to_string(), Some(value.to_string())))
+                .into_iter()
+                .collect(),
+        );
+        user_config_by_role_kind.insert(
+            ("role_1".to_string(), file_kind),
+            file_value
+                .map(|value| ("log.dirs".to_string(), Some(value.to_string())))
+                .into_iter()
+                .collect(),
+        );
+        let results = manager.get_all("0.1.0", &user_config_by_role_kind)?;
 
-        let manager = ProductConfigManager::from_yaml_file(path).unwrap();
+        assert_eq!(manager.check_mirrors(&results), expected);
+        Ok(())
+    }
 
-        let result = manager
-            .get_and_expand_properties(&product_version, role, kind, user_data)
-            .unwrap();
+    #[test]
+    fn test_check_mirrors_same_name() -> ValidationResult<()> {
+        let manager = ProductConfigManager::from_yaml_file("data/test_yamls/mirrors.yaml")?;
 
-        assert_eq!(result, expected);
+        let mut user_config_by_role_kind = HashMap::new();
+        user_config_by_role_kind.insert(
+            ("role_1".to_string(), PropertyNameKind::Env),
+            macro_to_hash_map(collection! {
+                "LOG_LEVEL".to_string() => Some("DEBUG".to_string())
+            }),
+        );
+        user_config_by_role_kind.insert(
+            (
+                "role_1".to_string(),
+                PropertyNameKind::File("server.properties".to_string()),
+            ),
+            HashMap::new(),
+        );
+        let results = manager.get_all("0.1.0", &user_config_by_role_kind)?;
+
+        assert_eq!(
+            manager.check_mirrors(&results),
+            vec![Error::MirroredValuesDiverge {
+                mirror: "log-level".to_string(),
+                role: "role_1".to_string(),
+                values: collection! {
+                    "LOG_LEVEL (env)".to_string() => "DEBUG".to_string(),
+                    "LOG_LEVEL (file 'server.properties')".to_string() => "INFO".to_string()
+                },
+            }]
+        );
+        Ok(())
     }
 
     #[rstest]
-    #[case::get_no_user_input(
-        &PropertyNameKind::File("env.sh".to_string()),
-        "role_1",
-        "data/test_yamls/validate.yaml",
-        HashMap::new(),
-        macro_to_get_result(collection!{
-            "ENV_FLOAT".to_string() => PropertyValidationResult::RecommendedDefault("50.0".to_string()),
-            "ENV_INTEGER_PORT_MIN_MAX".to_string() => PropertyValidationResult::RecommendedDefault("20000".to_string()),
-            "ENV_ENABLE_PASSWORD".to_string() => PropertyValidationResult::Valid("true".to_string()),
-            "ENV_PASSWORD".to_string() => PropertyValidationResult::Error("".to_string(), Error::PropertyValueMissing { property_name: "ENV_PASSWORD".to_string() }),
-            "ENV_ENABLE_PASSWORD".to_string() => PropertyValidationResult::Valid("true".to_string()),
-            "ENV_PROPERTY_STRING_DEPRECATED".to_string() => PropertyValidationResult::Warn("100mb".to_string(), Error::VersionDeprecated { property_name: "ENV_PROPERTY_STRING_DEPRECATED".to_string(), product_version: "0.5.0".to_string(), deprecated_version: "0.4.0".to_string() }),
-        })
-    )]
-    #[case::get_valid_float(
-        &PropertyNameKind::File("env.sh".to_string()),
-        "role_1",
-        "data/test_yamls/validate_float.yaml",
-        macro_to_hash_map(collection!{
-            "ENV_FLOAT".to_string() => Some("42.0".to_string())
-        }),
-        macro_to_get_result(collection!{
-            "ENV_FLOAT".to_string() => PropertyValidationResult::Valid("42.0".to_string()),
-        })
-    )]
-    #[case::get_recommended_float_no_user_input(
-        &PropertyNameKind::File("env.sh".to_string()),
-        "role_1",
-        "data/test_yamls/validate_float.yaml",
-        HashMap::new(),
-        macro_to_get_result(collection!{
-            "ENV_FLOAT".to_string() => PropertyValidationResult::RecommendedDefault("50.0".to_string()),
-        })
-    )]
-    #[case::get_invalid_float_bad_user_value(
-        &PropertyNameKind::File("env.sh".to_string()),
-        "role_1",
-        "data/test_yamls/validate_float.yaml",
-        macro_to_hash_map(collection!{
-            "ENV_FLOAT".to_string() => Some("CAFE".to_string())
-        }),
-        macro_to_get_result(collection!{
-            "ENV_FLOAT".to_string() => PropertyValidationResult::Error("CAFE".to_string(), Error::DatatypeNotMatching { property_name: "ENV_FLOAT".to_string(), value: "CAFE".to_string(), datatype: "f64".to_string() }),
-        })
-    )]
-    #[case::get_invalid_float_user_value_too_small(
-        &PropertyNameKind::File("env.sh".to_string()),
-        "role_1",
-        "data/test_yamls/validate_float.yaml",
-        macro_to_hash_map(collection!{
-            "ENV_FLOAT".to_string() => Some("-1".to_string())
-        }),
-        macro_to_get_result(collection!{
-            "ENV_FLOAT".to_string() => PropertyValidationResult::Error("-1".to_string(), Error::PropertyValueOutOfBounds { property_name: "ENV_FLOAT".to_string(), received: "-1".to_string(), expected: "0".to_string() }),
-        })
-    )]
-    #[case::get_invalid_float_user_value_too_high(
-        &PropertyNameKind::File("env.sh".to_string()),
-        "role_1",
-        "data/test_yamls/validate_float.yaml",
-        macro_to_hash_map(collection!{
-            "ENV_FLOAT".to_string() => Some("101".to_string())
-        }),
-        macro_to_get_result(collection!{
-        "ENV_FLOAT".to_string() => PropertyValidationResult::Error("101".to_string(), Error::PropertyValueOutOfBounds { property_name: "ENV_FLOAT".to_string(), received: "101".to_string(), expected: "100".to_string() }),
-        })
-    )]
-    #[case::get_invalid_ssl_certificate_path(
-        &PropertyNameKind::Env,
-        "role_1",
-        "data/test_yamls/validate_directory.yaml",
-        macro_to_hash_map(collection!{
-            "ENV_SSL_CERTIFICATE_PATH".to_string() => Some("CAFE".to_string())
-        }),
-        macro_to_get_result(collection!{
-            "ENV_SSL_CERTIFICATE_PATH".to_string() => PropertyValidationResult::Error("CAFE".to_string(), Error::DatatypeRegexNotMatching { property_name: "ENV_SSL_CERTIFICATE_PATH".to_string(), value: "CAFE".to_string() }),
-        })
-    )]
-    #[case::get_valid_default_certificate_path_no_user_input(
-        &PropertyNameKind::Env,
-        "role_1",
-        "data/test_yamls/validate_directory.yaml",
-        HashMap::new(),
-        macro_to_get_result(collection!{
-            "ENV_SSL_CERTIFICATE_PATH".to_string() => PropertyValidationResult::Default("path/to/certificates".to_string()),
-        })
-    )]
-    #[case::get_override_ssl_certificate_path(
-        &PropertyNameKind::File("should_not_be_found_therefore_is_an_override".to_string()),
-        "role_1",
-        "data/test_yamls/validate_directory.yaml",
-        macro_to_hash_map(collection!{
-            "ENV_SSL_CERTIFICATE_PATH".to_string() => Some("/opt/stackable/zookeeper-operator/pki".to_string())
-        }),
-        macro_to_get_result(collection!{
-            "ENV_SSL_CERTIFICATE_PATH".to_string() => PropertyValidationResult::Unknown("/opt/stackable/zookeeper-operator/pki".to_string()),
-        })
-    )]
-    #[case::get_override_ssl_certificate_path(
-        &PropertyNameKind::Env,
-        "role_1",
-        "data/test_yamls/validate_directory.yaml",
-        macro_to_hash_map(collection!{
-            "ENV_SSL_CERTIFICATE_PATH".to_string() => Some("/opt/stackable/zookeeper-operator/pki".to_string())
-        }),
-        macro_to_get_result(collection!{
-            "ENV_SSL_CERTIFICATE_PATH".to_string() => PropertyValidationResult::Valid("/opt/stackable/zookeeper-operator/pki".to_string()),
-        })
-    )]
-    #[case::get_recommended_port_no_user_input(
-        &PropertyNameKind::Env,
-        "role_1",
-        "data/test_yamls/validate_port.yaml",
-        HashMap::new(),
-        macro_to_get_result(collection!{
-            "ENV_INTEGER_PORT_MIN_MAX".to_string() => PropertyValidationResult::RecommendedDefault("20000".to_string()),
-        })
-    )]
-    #[case::get_port_user_value_too_small(
-        &PropertyNameKind::Env,
-        "role_1",
-        "data/test_yamls/validate_port.yaml",
-        macro_to_hash_map(collection!{
-            "ENV_INTEGER_PORT_MIN_MAX".to_string() => Some("42".to_string())
-        }),
-        macro_to_get_result(collection!{
-            "ENV_INTEGER_PORT_MIN_MAX".to_string() => PropertyValidationResult::Error("42".to_string(), Error::PropertyValueOutOfBounds { property_name: "ENV_INTEGER_PORT_MIN_MAX".to_string(), received: "42".to_string(), expected: "1024".to_string() })
-        })
-    )]
-    #[case::get_port_user_value_too_high(
-        &PropertyNameKind::Env,
-        "role_1",
-        "data/test_yamls/validate_port.yaml",
-        macro_to_hash_map(collection!{
-            "ENV_INTEGER_PORT_MIN_MAX".to_string() => Some("65536".to_string())
-        }),
-        macro_to_get_result(collection!{
-        "ENV_INTEGER_PORT_MIN_MAX".to_string() => PropertyValidationResult::Error("65536".to_string(), Error::PropertyValueOutOfBounds { property_name: "ENV_INTEGER_PORT_MIN_MAX".to_string(), received: "65536".to_string(), expected: "65535".to_string() })
-        })
-    )]
-    #[case::get_port_user_value_invalid(
-        &PropertyNameKind::Env,
-        "role_1",
-        "data/test_yamls/validate_port.yaml",
-        macro_to_hash_map(collection!{
-            "ENV_INTEGER_PORT_MIN_MAX".to_string() => Some("invalid".to_string())
-        }),
-        macro_to_get_result(collection!{
-            "ENV_INTEGER_PORT_MIN_MAX".to_string() => PropertyValidationResult::Error("invalid".to_string(), Error::DatatypeNotMatching { property_name: "ENV_INTEGER_PORT_MIN_MAX".to_string(), value: "invalid".to_string(), datatype: "i64".to_string() })
-        })
-    )]
-    #[case::get_port_user_value_valid(
-        &PropertyNameKind::Env,
-        "role_1",
-        "data/test_yamls/validate_port.yaml",
-        macro_to_hash_map(collection!{
-            "ENV_INTEGER_PORT_MIN_MAX".to_string() => Some("1024".to_string()),
-        }),
-        macro_to_get_result(collection!{
-            "ENV_INTEGER_PORT_MIN_MAX".to_string() => PropertyValidationResult::Valid("1024".to_string()),
-        })
-    )]
-    #[case::get_iso8601_duration_user_value_invalid(
-        &PropertyNameKind::Env,
-        "role_1",
-        "data/test_yamls/validate_iso8601_duration.yaml",
-        macro_to_hash_map(collection!{
-            "ENV_STARTUP_DELAY".to_string() => Some("invalid".to_string())
-        }),
-        macro_to_get_result(collection!{
-            "ENV_STARTUP_DELAY".to_string() => PropertyValidationResult::Error("invalid".to_string(), Error::DatatypeRegexNotMatching { property_name: "ENV_STARTUP_DELAY".to_string(), value: "invalid".to_string() })
-        })
-    )]
-    #[case::get_iso8601_duration_user_value_invalid(
-        &PropertyNameKind::Env,
-        "role_1",
-        "data/test_yamls/validate_iso8601_duration.yaml",
-        macro_to_hash_map(collection!{
-            "ENV_STARTUP_DELAY".to_string() => Some("PT300S".to_string())
-        }),
-        macro_to_get_result(collection!{
-            "ENV_STARTUP_DELAY".to_string() => PropertyValidationResult::Valid("PT300S".to_string())
-        })
+    #[case::defaults(collection! {}, vec![])]
+    #[case::over_limit(
+        collection! {
+            "ENV_THREADS".to_string() => Some("8".to_string()),
+            "ENV_MEMORY".to_string() => Some("512".to_string())
+        },
+        vec![Error::CountConstraintAboveMax { constraint: "tuning".to_string(), count: 2, max: 1 }]
     )]
-    fn test_get(
-        #[case] kind: &PropertyNameKind,
-        #[case] role: &str,
-        #[case] path: &str,
-        #[case] user_data: HashMap<String, Option<String>>,
-        #[case] expected: BTreeMap<String, PropertyValidationResult>,
+    fn test_check_count_constraints_non_default(
+        #[case] user_config: HashMap<String, Option<String>>,
+        #[case] expected: Vec<Error>,
     ) -> ValidationResult<()> {
-        let manager = ProductConfigManager::from_yaml_file(path).unwrap();
+        let manager =
+            ProductConfigManager::from_yaml_file("data/test_yamls/count_constraints.yaml")?;
+        let result = manager.get("0.1.0", "role_1", &PropertyNameKind::Env, user_config)?;
 
-        let result = manager.get("0.5.0", role, kind, user_data)?;
+        assert_eq!(
+            manager
+                .check_count_constraints(&result)
+                .into_iter()
+                .filter(|err| matches!(
+                    err,
+                    Error::CountConstraintBelowMin { constraint, .. }
+                        | Error::CountConstraintAboveMax { constraint, .. } if constraint == "tuning"
+                ))
+                .collect::<Vec<_>>(),
+            expected
+        );
+        Ok(())
+    }
 
-        assert_eq!(result, expected);
+    #[rstest]
+    #[case::recommended("ENV_INTEGER_PORT_MIN_MAX", "role_1")]
+    #[case::default("ENV_ALLOWED_VALUES", "role_2")]
+    fn test_default_for(#[case] name: &str, #[case] role: &str) -> ValidationResult<()> {
+        let manager =
+            ProductConfigManager::from_yaml_file("data/test_yamls/test_product_config.yaml")?;
+        let kind = PropertyNameKind::File("env.sh".to_string());
+
+        let defaults = manager.get("0.5.0", role, &kind, HashMap::new())?;
+
+        assert_eq!(
+            manager.default_for(name, role, &kind, "0.5.0")?,
+            Some(defaults.get(name).map(|result| result.value().to_string()))
+        );
+        assert_eq!(
+            manager.default_for("ENV_UNKNOWN", role, &kind, "0.5.0")?,
+            None
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_default_for_normalized() -> ValidationResult<()> {
+        let manager = ProductConfigManager::from_yaml_file("data/test_yamls/cpu.yaml")?;
+
+        assert_eq!(
+            manager.default_for("ENV_CPU", "role_1", &PropertyNameKind::Env, "0.5.0")?,
+            Some(Some("1000m".to_string()))
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::declaration_order("ENV_LOG_LEVEL", Some(vec!["TRACE", "DEBUG", "INFO", "WARN", "ERROR"]))]
+    #[case::no_allowed_values("ENV_FREE_TEXT", None)]
+    #[case::unknown("ENV_UNKNOWN", None)]
+    fn test_allowed_values_for(
+        #[case] name: &str,
+        #[case] expected: Option<Vec<&str>>,
+    ) -> ValidationResult<()> {
+        let manager =
+            ProductConfigManager::from_yaml_file("data/test_yamls/allowed_values_order.yaml")?;
+
+        assert_eq!(
+            manager.allowed_values_for(name, "role_1", &PropertyNameKind::Env, "0.1.0")?,
+            expected.map(|values| values.into_iter().map(String::from).collect())
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::provided("fast-ssd", PropertyValidationResult::Valid("fast-ssd".to_string()))]
+    #[case::not_provided("local", PropertyValidationResult::Error(
+        "local".to_string(),
+        Error::PropertyValueNotInAllowedValues { property_name: "ENV_STORAGE_CLASS".to_string(), value: "local".to_string(), allowed_values: vec!["standard".to_string(), "fast-ssd".to_string()] }
+    ))]
+    fn test_get_allowed_values_provider(
+        #[case] value: &str,
+        #[case] expected: PropertyValidationResult,
+    ) -> ValidationResult<()> {
+        let manager =
+            ProductConfigManager::from_yaml_file("data/test_yamls/allowed_values_provider.yaml")?
+                .with_allowed_values_provider("storage-classes", || {
+                    vec!["standard".to_string(), "fast-ssd".to_string()]
+                });
+
+        let result = manager.get(
+            "0.1.0",
+            "role_1",
+            &PropertyNameKind::Env,
+            macro_to_hash_map(collection! {
+                "ENV_STORAGE_CLASS".to_string() => Some(value.to_string())
+            }),
+        )?;
+
+        assert_eq!(result.get("ENV_STORAGE_CLASS"), Some(&expected));
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_allowed_values_provider_not_registered() -> ValidationResult<()> {
+        let manager =
+            ProductConfigManager::from_yaml_file("data/test_yamls/allowed_values_provider.yaml")?;
+
+        let result = manager.get(
+            "0.1.0",
+            "role_1",
+            &PropertyNameKind::Env,
+            macro_to_hash_map(collection! {
+                "ENV_STORAGE_CLASS".to_string() => Some("standard".to_string())
+            }),
+        )?;
+
+        assert_eq!(
+            result.get("ENV_STORAGE_CLASS"),
+            Some(&PropertyValidationResult::Error(
+                "standard".to_string(),
+                Error::AllowedValuesProviderNotFound {
+                    property_name: "ENV_STORAGE_CLASS".to_string(),
+                    provider: "storage-classes".to_string(),
+                }
+            ))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_file_kinds_for() -> ValidationResult<()> {
+        let manager = ProductConfigManager::from_yaml_file("data/test_yamls/file_kinds.yaml")?;
+
+        assert_eq!(
+            manager.file_kinds_for("role_1", "0.5.0")?,
+            BTreeSet::from(["core-site.xml".to_string(), "hdfs-site.xml".to_string()])
+        );
+        assert_eq!(
+            manager.file_kinds_for("role_1", "1.0.0")?,
+            BTreeSet::from([
+                "core-site.xml".to_string(),
+                "hdfs-site.xml".to_string(),
+                "log4j.properties".to_string()
+            ])
+        );
+        assert_eq!(
+            manager.file_kinds_for("role_2", "0.5.0")?,
+            BTreeSet::from(["core-site.xml".to_string()])
+        );
+        assert!(manager.file_kinds_for("role_3", "0.5.0")?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_error_descriptions() -> ValidationResult<()> {
+        let manager = ProductConfigManager::from_yaml_file("data/test_yamls/descriptions.yaml")?;
+        let kind = PropertyNameKind::Env;
+
+        let result = manager.get(
+            "0.5.0",
+            "role_1",
+            &kind,
+            macro_to_hash_map(collection! {
+                "ENV_DESCRIBED".to_string() => Some("invalid".to_string()),
+                "ENV_UNDESCRIBED".to_string() => Some("invalid".to_string()),
+                "ENV_VALID".to_string() => Some("1".to_string())
+            }),
+        )?;
+
+        assert_eq!(
+            manager.error_descriptions("0.5.0", "role_1", &kind, &result)?,
+            BTreeMap::from([(
+                "ENV_DESCRIBED".to_string(),
+                "The number of worker threads.".to_string()
+            )])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_internal_properties() -> ValidationResult<()> {
+        let manager = ProductConfigManager::from_yaml_file("data/test_yamls/internal.yaml")?;
+        let kind = PropertyNameKind::Env;
+
+        assert_eq!(
+            manager.list_properties("0.5.0", "role_1", &kind, false)?,
+            vec!["ENV_PUBLIC".to_string()]
+        );
+        assert_eq!(
+            manager.list_properties("0.5.0", "role_1", &kind, true)?,
+            vec!["ENV_PUBLIC".to_string(), "ENV_INTERNAL".to_string()]
+        );
+        assert!(manager
+            .describe("0.5.0", "role_1", &kind, "ENV_INTERNAL", false)?
+            .is_none());
+        assert!(manager
+            .describe("0.5.0", "role_1", &kind, "ENV_INTERNAL", true)?
+            .is_some());
+        assert!(manager
+            .describe("0.5.0", "role_1", &kind, "ENV_PUBLIC", false)?
+            .is_some());
+
+        // internal properties are still validated
+        let result = manager.get(
+            "0.5.0",
+            "role_1",
+            &kind,
+            macro_to_hash_map(collection! {
+                "ENV_INTERNAL".to_string() => Some("invalid".to_string())
+            }),
+        )?;
+        assert_eq!(
+            result.get("ENV_INTERNAL"),
+            Some(&PropertyValidationResult::Error(
+                "invalid".to_string(),
+                Error::DatatypeNotMatching {
+                    property_name: "ENV_INTERNAL".to_string(),
+                    value: "invalid".to_string(),
+                    datatype: "i64".to_string()
+                }
+            ))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_regex_backtrack_limit() -> ValidationResult<()> {
+        let manager =
+            ProductConfigManager::from_yaml_file("data/test_yamls/regex_backtrack_limit.yaml")?
+                .with_regex_backtrack_limit(1000)?;
+        let value = "a".repeat(30);
+
+        let result = manager.get(
+            "0.5.0",
+            "role_1",
+            &PropertyNameKind::Env,
+            macro_to_hash_map(
+                collection! { "ENV_PATHOLOGICAL".to_string() => Some(value.clone()) },
+            ),
+        )?;
+
+        assert!(matches!(
+            result.get("ENV_PATHOLOGICAL"),
+            Some(PropertyValidationResult::Error(
+                _,
+                Error::RegexNotEvaluable { .. }
+            ))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_access_tracking() -> ValidationResult<()> {
+        let manager = ProductConfigManager::from_yaml_file("data/test_yamls/validate_port.yaml")?
+            .with_access_tracking();
+
+        manager.get("0.5.0", "role_1", &PropertyNameKind::Env, HashMap::new())?;
+        manager.get(
+            "0.5.0",
+            "role_1",
+            &PropertyNameKind::File("my.config".to_string()),
+            macro_to_hash_map(collection! {
+                "unknown".to_string() => Some("override".to_string())
+            }),
+        )?;
+
+        let expected: BTreeSet<String> = collection! {
+            "ENV_INTEGER_PORT_MIN_MAX".to_string(),
+            "conf.integer.port.min.max".to_string(),
+        };
+        assert_eq!(manager.accessed_properties(), expected);
+        assert!(manager.unaccessed_properties().is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_access_tracking_disabled() -> ValidationResult<()> {
+        let manager = ProductConfigManager::from_yaml_file("data/test_yamls/validate_port.yaml")?;
+
+        manager.get("0.5.0", "role_1", &PropertyNameKind::Env, HashMap::new())?;
+
+        assert!(manager.accessed_properties().is_empty());
+        assert_eq!(manager.unaccessed_properties().len(), 2);
 
         Ok(())
     }
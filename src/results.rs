@@ -0,0 +1,387 @@
+//! Helpers to post-process the validation results returned by
+//! [`crate::ProductConfigManager::get`].
+//!
+//! # Example
+//!
+//! ```
+//! use product_config::results;
+//! use product_config::types::PropertyNameKind;
+//! use product_config::ProductConfigManager;
+//! use std::collections::HashMap;
+//!
+//! let config = ProductConfigManager::from_yaml_file("data/test_yamls/validate.yaml")
+//!     .unwrap();
+//!
+//! let env_sh = config
+//!     .get(
+//!         "0.5.0",
+//!         "role_1",
+//!         &PropertyNameKind::File("env.sh".to_string()),
+//!         HashMap::new(),
+//!     )
+//!     .unwrap();
+//!
+//! for (name, _value, error) in results::errors(&env_sh) {
+//!     println!("{name}: {error}");
+//! }
+//! ```
+use std::collections::BTreeMap;
+
+use crate::error::Error;
+use crate::PropertyValidationResult;
+
+/// Returns all properties that resulted in a [`PropertyValidationResult::Error`] or
+/// [`PropertyValidationResult::Errors`] with their (invalid) value and the error. Properties
+/// with multiple errors are returned once per error.
+pub fn errors(
+    results: &BTreeMap<String, PropertyValidationResult>,
+) -> impl Iterator<Item = (&String, &String, &Error)> {
+    results.iter().flat_map(|(name, result)| {
+        let (value, errors) = match result {
+            PropertyValidationResult::Error(value, err) => (value, std::slice::from_ref(err)),
+            PropertyValidationResult::Errors(value, errors) => (value, errors.as_slice()),
+            _ => return Vec::new(),
+        };
+        errors.iter().map(|err| (name, value, err)).collect()
+    })
+}
+
+/// Returns all properties that resulted in a [`PropertyValidationResult::Warn`] with their
+/// value and the warning.
+pub fn warnings(
+    results: &BTreeMap<String, PropertyValidationResult>,
+) -> impl Iterator<Item = (&String, &String, &Error)> {
+    results.iter().filter_map(|(name, result)| match result {
+        PropertyValidationResult::Warn(value, err) => Some((name, value, err)),
+        _ => None,
+    })
+}
+
+/// Returns all properties with a value that passed validation, i.e. that resulted in
+/// [`PropertyValidationResult::Default`], [`PropertyValidationResult::RecommendedDefault`] or
+/// [`PropertyValidationResult::Valid`].
+/// [`PropertyValidationResult::Unknown`] properties are not included because they were not
+/// validated at all.
+pub fn valid_values(
+    results: &BTreeMap<String, PropertyValidationResult>,
+) -> impl Iterator<Item = (&String, &String)> {
+    results.iter().filter_map(|(name, result)| match result {
+        PropertyValidationResult::Default(value)
+        | PropertyValidationResult::RecommendedDefault(value)
+        | PropertyValidationResult::Valid(value) => Some((name, value)),
+        _ => None,
+    })
+}
+
+/// Returns one human readable message per property, e.g. for CLI output:
+/// * "ENV_PORT: OK (recommended default 20000)" for passed values
+/// * "ENV_EXTRA: UNKNOWN (value, not validated)" for unknown properties
+/// * "ENV_FLOAT: WARNING ..." and "ENV_FLOAT: ERROR ..." with the [`Error`] display for
+///   failures (multiple errors separated by "; ")
+pub fn explain_results(results: &BTreeMap<String, PropertyValidationResult>) -> Vec<String> {
+    results
+        .iter()
+        .map(|(name, result)| {
+            let explanation = match result {
+                PropertyValidationResult::Default(value) => format!("OK (default {value})"),
+                PropertyValidationResult::RecommendedDefault(value) => {
+                    format!("OK (recommended default {value})")
+                }
+                PropertyValidationResult::Valid(value) => format!("OK ({value})"),
+                PropertyValidationResult::Unknown(value) => {
+                    format!("UNKNOWN ({value}, not validated)")
+                }
+                PropertyValidationResult::Warn(_, err) => format!("WARNING {err}"),
+                PropertyValidationResult::Error(_, err) => format!("ERROR {err}"),
+                PropertyValidationResult::Errors(_, errors) => format!(
+                    "ERROR {}",
+                    errors
+                        .iter()
+                        .map(Error::to_string)
+                        .collect::<Vec<_>>()
+                        .join("; ")
+                ),
+            };
+            format!("{name}: {explanation}")
+        })
+        .collect()
+}
+
+/// The class of a validation result, shared by [`summarize`] and [`stats`] so both agree.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum ResultClass {
+    Error,
+    Warning,
+    Defaulted,
+    Recommended,
+    Overridden,
+    Unknown,
+}
+
+impl ResultClass {
+    fn of(result: &PropertyValidationResult) -> Self {
+        match result {
+            PropertyValidationResult::Error(..) | PropertyValidationResult::Errors(..) => {
+                ResultClass::Error
+            }
+            PropertyValidationResult::Warn(..) => ResultClass::Warning,
+            PropertyValidationResult::Default(_) => ResultClass::Defaulted,
+            PropertyValidationResult::RecommendedDefault(_) => ResultClass::Recommended,
+            PropertyValidationResult::Valid(_) => ResultClass::Overridden,
+            PropertyValidationResult::Unknown(_) => ResultClass::Unknown,
+        }
+    }
+}
+
+/// The property names of validation results grouped by severity, e.g. for dashboards. Created
+/// via [`summarize`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ValidationSummary {
+    /// Properties with [`PropertyValidationResult::Error`] or [`PropertyValidationResult::Errors`]
+    pub errors: Vec<String>,
+    /// Properties with [`PropertyValidationResult::Warn`]
+    pub warnings: Vec<String>,
+    /// Properties with [`PropertyValidationResult::Default`],
+    /// [`PropertyValidationResult::RecommendedDefault`] or [`PropertyValidationResult::Valid`]
+    pub ok: Vec<String>,
+    /// Properties with [`PropertyValidationResult::Unknown`], which were not validated at all
+    pub unknown: Vec<String>,
+}
+
+impl ValidationSummary {
+    /// Returns the number of properties with errors (multiple errors of a property count once).
+    pub fn error_count(&self) -> usize {
+        self.errors.len()
+    }
+
+    /// Returns the number of properties with warnings.
+    pub fn warning_count(&self) -> usize {
+        self.warnings.len()
+    }
+
+    /// Returns the number of properties that passed validation.
+    pub fn ok_count(&self) -> usize {
+        self.ok.len()
+    }
+
+    /// Returns the number of unknown properties.
+    pub fn unknown_count(&self) -> usize {
+        self.unknown.len()
+    }
+}
+
+/// Groups the property names of the validation results by severity. The names are sorted
+/// within each group.
+pub fn summarize(results: &BTreeMap<String, PropertyValidationResult>) -> ValidationSummary {
+    let mut summary = ValidationSummary::default();
+    for (name, result) in results {
+        let group = match ResultClass::of(result) {
+            ResultClass::Error => &mut summary.errors,
+            ResultClass::Warning => &mut summary.warnings,
+            ResultClass::Defaulted | ResultClass::Recommended | ResultClass::Overridden => {
+                &mut summary.ok
+            }
+            ResultClass::Unknown => &mut summary.unknown,
+        };
+        group.push(name.clone());
+    }
+    summary
+}
+
+/// The number of properties per kind of validation result, e.g. to compare how many properties
+/// are overridden by users and how many use the defaults. Created via [`stats`].
+///
+/// Every property is counted exactly once, consistent with [`summarize`]: `errors` and
+/// `warnings` match the respective groups of [`ValidationSummary`] and `overridden`,
+/// `defaulted` and `recommended` add up to [`ValidationSummary::ok`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ConfigStats {
+    /// All properties of the validation results
+    pub total: usize,
+    /// Properties with [`PropertyValidationResult::Valid`], i.e. values that passed validation
+    /// and differ from the default. Values differing from the default that resulted in a
+    /// warning or an error are only counted in `warnings` or `errors`.
+    pub overridden: usize,
+    /// Properties with [`PropertyValidationResult::Default`]
+    pub defaulted: usize,
+    /// Properties with [`PropertyValidationResult::RecommendedDefault`]
+    pub recommended: usize,
+    /// Properties with [`PropertyValidationResult::Unknown`]
+    pub unknown: usize,
+    /// Properties with [`PropertyValidationResult::Error`] or [`PropertyValidationResult::Errors`]
+    /// (multiple errors of a property count once)
+    pub errors: usize,
+    /// Properties with [`PropertyValidationResult::Warn`]
+    pub warnings: usize,
+}
+
+/// Counts the properties of the validation results per kind of result.
+pub fn stats(results: &BTreeMap<String, PropertyValidationResult>) -> ConfigStats {
+    let mut stats = ConfigStats {
+        total: results.len(),
+        ..ConfigStats::default()
+    };
+    for result in results.values() {
+        let count = match ResultClass::of(result) {
+            ResultClass::Error => &mut stats.errors,
+            ResultClass::Warning => &mut stats.warnings,
+            ResultClass::Defaulted => &mut stats.defaulted,
+            ResultClass::Recommended => &mut stats.recommended,
+            ResultClass::Overridden => &mut stats.overridden,
+            ResultClass::Unknown => &mut stats.unknown,
+        };
+        *count += 1;
+    }
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn results() -> BTreeMap<String, PropertyValidationResult> {
+        let deprecated = Error::VersionDeprecated {
+            property_name: "deprecated".to_string(),
+            product_version: "0.5.0".to_string(),
+            deprecated_version: "0.4.0".to_string(),
+            replacements: vec![],
+        };
+        let missing = Error::PropertyValueMissing {
+            property_name: "missing".to_string(),
+        };
+
+        [
+            (
+                "default",
+                PropertyValidationResult::Default("1".to_string()),
+            ),
+            (
+                "recommended",
+                PropertyValidationResult::RecommendedDefault("2".to_string()),
+            ),
+            ("valid", PropertyValidationResult::Valid("3".to_string())),
+            (
+                "unknown",
+                PropertyValidationResult::Unknown("4".to_string()),
+            ),
+            (
+                "deprecated",
+                PropertyValidationResult::Warn("5".to_string(), deprecated),
+            ),
+            (
+                "missing",
+                PropertyValidationResult::Error("".to_string(), missing),
+            ),
+            (
+                "multiple",
+                PropertyValidationResult::Errors(
+                    "6".to_string(),
+                    vec![
+                        Error::PropertyValueOutOfBounds {
+                            property_name: "multiple".to_string(),
+                            received: "6".to_string(),
+                            expected: "5".to_string(),
+                            bound_kind: crate::types::BoundKind::Inclusive,
+                        },
+                        Error::PropertyValueNotInAllowedValues {
+                            property_name: "multiple".to_string(),
+                            value: "6".to_string(),
+                            allowed_values: vec!["1".to_string()],
+                        },
+                    ],
+                ),
+            ),
+        ]
+        .into_iter()
+        .map(|(name, result)| (name.to_string(), result))
+        .collect()
+    }
+
+    #[test]
+    fn test_errors() {
+        let results = results();
+        let errors: Vec<_> = errors(&results).map(|(name, _, _)| name.as_str()).collect();
+        assert_eq!(errors, vec!["missing", "multiple", "multiple"]);
+    }
+
+    #[test]
+    fn test_warnings() {
+        let results = results();
+        let warnings: Vec<_> = warnings(&results)
+            .map(|(name, value, _)| (name.as_str(), value.as_str()))
+            .collect();
+        assert_eq!(warnings, vec![("deprecated", "5")]);
+    }
+
+    #[test]
+    fn test_valid_values() {
+        let results = results();
+        let valid: Vec<_> = valid_values(&results)
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+            .collect();
+        assert_eq!(
+            valid,
+            vec![("default", "1"), ("recommended", "2"), ("valid", "3")]
+        );
+    }
+
+    #[test]
+    fn test_summarize() {
+        let summary = summarize(&results());
+
+        assert_eq!(summary.error_count(), 2);
+        assert_eq!(summary.warning_count(), 1);
+        assert_eq!(summary.ok_count(), 3);
+        assert_eq!(summary.unknown_count(), 1);
+        assert_eq!(summary.errors, vec!["missing", "multiple"]);
+        assert_eq!(summary.warnings, vec!["deprecated"]);
+        assert_eq!(summary.ok, vec!["default", "recommended", "valid"]);
+        assert_eq!(summary.unknown, vec!["unknown"]);
+    }
+
+    #[test]
+    fn test_explain_results() {
+        assert_eq!(
+            explain_results(&results()),
+            vec![
+                "default: OK (default 1)",
+                "deprecated: WARNING [deprecated]: current product version is '0.5.0' -> property deprecated since version '0.4.0'",
+                "missing: ERROR [missing]: config value missing for required property",
+                "multiple: ERROR [multiple]: provided value '6' violates inclusive min/max bound '5'; [multiple]: value '6' not in allowed values: [\"1\"]",
+                "recommended: OK (recommended default 2)",
+                "unknown: UNKNOWN (4, not validated)",
+                "valid: OK (3)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_stats() {
+        assert_eq!(
+            stats(&results()),
+            ConfigStats {
+                total: 7,
+                overridden: 1,
+                defaulted: 1,
+                recommended: 1,
+                unknown: 1,
+                errors: 2,
+                warnings: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_stats_agree_with_summary() {
+        let stats = stats(&results());
+        let summary = summarize(&results());
+
+        assert_eq!(stats.errors, summary.error_count());
+        assert_eq!(stats.warnings, summary.warning_count());
+        assert_eq!(
+            stats.overridden + stats.defaulted + stats.recommended,
+            summary.ok_count()
+        );
+        assert_eq!(stats.unknown, summary.unknown_count());
+    }
+}
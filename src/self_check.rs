@@ -0,0 +1,988 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use fancy_regex::{Assertion, Expr, Regex};
+
+use crate::error::Error;
+use crate::types::{
+    BoundKind, Datatype, ProductConfig, PropertyNameKind, PropertySpec, PropertyValueSpec,
+    StackableVersion, Unit,
+};
+use crate::validation::{
+    check_allowed_values_or_patterns, check_datatype, duration_unit_millis, parse, parse_duration,
+    unit_factor, Bounds, DEFAULT_DURATION_UNIT,
+};
+use std::ops::Deref;
+use std::str::FromStr;
+
+/// Runs all checks of [`crate::ProductConfigManager::self_check`] and returns all found
+/// problems.
+///
+/// # Arguments
+///
+/// * `config` - the product config to be checked
+///
+pub(crate) fn check_all(config: &ProductConfig) -> Vec<Error> {
+    let mut errors = check_value_specs_in_allowed_values(config);
+    errors.extend(check_allowed_values_unique(config));
+    errors.extend(check_value_specs_datatype(config));
+    errors.extend(check_datatype_bounds(config));
+    errors.extend(check_file_property_names_unique(config));
+    errors.extend(check_expansion_roles_defined(config));
+    errors.extend(check_expansion_values(config));
+    errors.extend(check_quantity_units_known(config));
+    errors.extend(check_unit_examples_match(config));
+    errors.extend(check_unit_regexes_anchored(config));
+    errors.extend(check_versions_ordered(config));
+    errors
+}
+
+/// Checks that every default and recommended value of a property is contained in its
+/// allowed values or matches its allowed value patterns (if provided), like user values are
+/// validated. Values of repeatable properties are checked per element.
+///
+/// # Arguments
+///
+/// * `config` - the product config to be checked
+///
+pub(crate) fn check_value_specs_in_allowed_values(config: &ProductConfig) -> Vec<Error> {
+    let mut errors = vec![];
+
+    for property in &config.properties {
+        let name = display_name(property);
+        for value_spec in value_specs(property) {
+            for element in property.value_elements(&value_spec.value) {
+                if let Err(allowed_errors) = check_allowed_values_or_patterns(
+                    &name,
+                    element,
+                    &property.allowed_values,
+                    &property.allowed_value_patterns,
+                    property.is_case_insensitive(),
+                ) {
+                    errors.extend(allowed_errors);
+                }
+            }
+        }
+    }
+
+    errors
+}
+
+/// Checks that the allowed values of a property contain no duplicates. Every duplicated
+/// value is reported once. Case-insensitive properties compare their allowed values
+/// case-insensitively as well.
+///
+/// # Arguments
+///
+/// * `config` - the product config to be checked
+///
+pub(crate) fn check_allowed_values_unique(config: &ProductConfig) -> Vec<Error> {
+    let mut errors = vec![];
+
+    for property in &config.properties {
+        let Some(allowed_values) = &property.allowed_values else {
+            continue;
+        };
+
+        let mut seen = BTreeSet::new();
+        let mut reported = BTreeSet::new();
+        for value in allowed_values {
+            let key = if property.is_case_insensitive() {
+                value.to_lowercase()
+            } else {
+                value.clone()
+            };
+            if !seen.insert(key.clone()) && reported.insert(key) {
+                errors.push(Error::DuplicateAllowedValue {
+                    property_name: display_name(property),
+                    value: value.clone(),
+                });
+            }
+        }
+    }
+
+    errors
+}
+
+/// Checks that every default and recommended value of a property matches its datatype,
+/// including the bounds. Values are checked against the datatype overrides of the property
+/// names as well, since they are validated against those for the respective kind. Values of
+/// repeatable properties are checked per element (see [`PropertySpec::value_elements`]).
+///
+/// # Arguments
+///
+/// * `config` - the product config to be checked
+///
+pub(crate) fn check_value_specs_datatype(config: &ProductConfig) -> Vec<Error> {
+    let mut errors = vec![];
+
+    for property in &config.properties {
+        let name = display_name(property);
+        for value_spec in value_specs(property) {
+            for element in property.value_elements(&value_spec.value) {
+                for datatype in property.all_datatypes() {
+                    if let Err(err) = check_datatype(datatype, &name, element) {
+                        errors.push(err);
+                    }
+                }
+            }
+        }
+    }
+
+    errors
+}
+
+/// Checks that every role referenced by an expansion (see [`PropertySpec::expands_to`],
+/// including nested expansions) is defined by at least one property of the config. Expansions
+/// for undefined roles never apply. Every dangling role is reported once per expansion.
+///
+/// # Arguments
+///
+/// * `config` - the product config to be checked
+///
+pub(crate) fn check_expansion_roles_defined(config: &ProductConfig) -> Vec<Error> {
+    let defined_roles: BTreeSet<&str> = config
+        .properties
+        .iter()
+        .flat_map(|property| &property.roles)
+        .map(|role| role.name.as_str())
+        .collect();
+
+    let mut errors = vec![];
+    for property in &config.properties {
+        collect_undefined_expansion_roles(property, &defined_roles, &mut errors);
+    }
+    errors
+}
+
+/// Recursively collects the undefined roles of the expansions of the property.
+fn collect_undefined_expansion_roles(
+    property: &PropertySpec,
+    defined_roles: &BTreeSet<&str>,
+    errors: &mut Vec<Error>,
+) {
+    for expansion in property.expands_to.iter().flatten() {
+        let undefined_roles: BTreeSet<&str> = expansion
+            .property
+            .roles
+            .iter()
+            .map(|role| role.name.as_str())
+            .filter(|role| !defined_roles.contains(role))
+            .collect();
+        for role in undefined_roles {
+            errors.push(Error::UndefinedExpansionRole {
+                property_name: display_name(property),
+                expansion: display_name(&expansion.property),
+                role: role.to_string(),
+            });
+        }
+        collect_undefined_expansion_roles(&expansion.property, defined_roles, errors);
+    }
+}
+
+/// Checks that every explicit expansion value (see [`PropertySpec::expands_to`], including
+/// nested expansions) is valid for the expanded property like default and recommended values:
+/// it must be an allowed value or match an allowed value pattern (if provided) and match the
+/// datatype. Values of repeatable properties are checked per element.
+///
+/// # Arguments
+///
+/// * `config` - the product config to be checked
+///
+pub(crate) fn check_expansion_values(config: &ProductConfig) -> Vec<Error> {
+    let mut errors = vec![];
+    for property in &config.properties {
+        collect_invalid_expansion_values(property, &mut errors);
+    }
+    errors
+}
+
+fn collect_invalid_expansion_values(property: &PropertySpec, errors: &mut Vec<Error>) {
+    for expansion in property.expands_to.iter().flatten() {
+        let expanded = &expansion.property;
+        if let Some(value) = &expansion.value {
+            let name = display_name(expanded);
+            for element in expanded.value_elements(value) {
+                let element = match check_allowed_values_or_patterns(
+                    &name,
+                    element,
+                    &expanded.allowed_values,
+                    &expanded.allowed_value_patterns,
+                    expanded.is_case_insensitive(),
+                ) {
+                    Ok(element) => element,
+                    Err(allowed_errors) => {
+                        errors.extend(allowed_errors);
+                        element.to_string()
+                    }
+                };
+                for datatype in expanded.all_datatypes() {
+                    if let Err(err) = check_datatype(datatype, &name, &element) {
+                        errors.push(err);
+                    }
+                }
+            }
+        }
+        collect_invalid_expansion_values(expanded, errors);
+    }
+}
+
+/// Checks that every accepted unit and default unit of a datatype is a known unit suffix
+/// (e.g. "Mi" or "G"), or a known duration unit (e.g. "s") for durations. Values with unknown
+/// units would always be rejected. Every unknown unit is reported once per property.
+///
+/// `accepted_units` and `default_unit` are unit suffixes used for unit conversion (see
+/// [`unit_factor`]) and are therefore not resolved against the units declared in the spec,
+/// which only provide a regex and no conversion factors.
+///
+/// # Arguments
+///
+/// * `config` - the product config to be checked
+///
+pub(crate) fn check_quantity_units_known(config: &ProductConfig) -> Vec<Error> {
+    let mut errors = vec![];
+
+    for property in &config.properties {
+        let mut unknown_units: BTreeSet<&String> = property
+            .all_datatypes()
+            .flat_map(Datatype::quantity_units)
+            .filter(|unit| unit_factor(unit).is_none())
+            .collect();
+        unknown_units.extend(
+            property
+                .all_datatypes()
+                .filter_map(|datatype| match datatype {
+                    Datatype::Duration {
+                        default_unit: Some(unit),
+                        ..
+                    } if duration_unit_millis(unit).is_none() => Some(unit),
+                    _ => None,
+                }),
+        );
+        for unit in unknown_units {
+            errors.push(Error::UnknownUnit {
+                property_name: display_name(property),
+                unit: unit.clone(),
+            });
+        }
+    }
+
+    errors
+}
+
+/// Checks that the `min` bound of every datatype is less than (or equal to, if both bounds are
+/// inclusive) its `max` bound (parsed according to the datatype). Bounds that cannot be parsed
+/// are reported as well.
+///
+/// # Arguments
+///
+/// * `config` - the product config to be checked
+///
+pub(crate) fn check_datatype_bounds(config: &ProductConfig) -> Vec<Error> {
+    let mut errors = vec![];
+
+    for property in &config.properties {
+        let name = display_name(property);
+        for datatype in property.all_datatypes() {
+            let bounds = Bounds::of(datatype);
+            let result = match datatype {
+                Datatype::Integer { .. } => check_bounds::<i64>(&name, &bounds),
+                Datatype::Float { .. } => check_bounds::<f64>(&name, &bounds),
+                // the bounds of strings refer to the length
+                Datatype::String { .. } => check_bounds::<usize>(&name, &bounds),
+                Datatype::Duration { default_unit, .. } => {
+                    check_duration_bounds(&name, &bounds, default_unit)
+                }
+                Datatype::Bool | Datatype::Array { .. } => Ok(()),
+            };
+            if let Err(err) = result {
+                errors.push(err);
+            }
+        }
+    }
+
+    errors
+}
+
+/// Parses both duration bounds (if both are provided) and checks that they can be satisfied.
+/// Duration bounds are always inclusive.
+fn check_duration_bounds(
+    name: &str,
+    bounds: &Bounds,
+    default_unit: &Option<String>,
+) -> Result<(), Error> {
+    let default_unit = default_unit.as_deref().unwrap_or(DEFAULT_DURATION_UNIT);
+    if let (Some(min), Some(max)) = (bounds.min, bounds.max) {
+        let parse = |bound: &str| {
+            parse_duration(bound, default_unit).map_err(|_| Error::DatatypeNotMatching {
+                property_name: name.to_string(),
+                value: bound.to_string(),
+                datatype: "duration".to_string(),
+            })
+        };
+        if parse(min)? > parse(max)? {
+            return Err(Error::PropertyBoundsInverted {
+                property_name: name.to_string(),
+                min: min.to_string(),
+                max: max.to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Parses both bounds (if both are provided) and checks that they can be satisfied.
+fn check_bounds<T>(name: &str, bounds: &Bounds) -> Result<(), Error>
+where
+    T: FromStr + PartialOrd,
+{
+    if let (Some(min), Some(max)) = (bounds.min, bounds.max) {
+        let (parsed_min, parsed_max) = (parse::<T>(name, min)?, parse::<T>(name, max)?);
+        let inverted = match (bounds.min_kind, bounds.max_kind) {
+            (BoundKind::Inclusive, BoundKind::Inclusive) => parsed_min > parsed_max,
+            _ => parsed_min >= parsed_max,
+        };
+        if inverted {
+            return Err(Error::PropertyBoundsInverted {
+                property_name: name.to_string(),
+                min: min.to_string(),
+                max: max.to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Checks that every example of a unit (declared in the spec or used in a datatype) matches the
+/// unit regex. Examples are used for documentation and should not be stale. Examples that can
+/// not be evaluated (e.g. because the backtrack limit is exceeded) count as not matching.
+///
+/// # Arguments
+///
+/// * `config` - the product config to be checked
+///
+pub(crate) fn check_unit_examples_match(config: &ProductConfig) -> Vec<Error> {
+    let mut errors = vec![];
+
+    for unit in all_units(config) {
+        for example in unit.examples.iter().flatten() {
+            if unit.regex.is_match(example).unwrap_or(false) {
+                continue;
+            }
+            let error = Error::UnitExampleNotMatching {
+                unit: unit.name.clone(),
+                regex: unit.regex.expression.clone(),
+                example: example.clone(),
+            };
+            if !errors.contains(&error) {
+                errors.push(error);
+            }
+        }
+    }
+
+    errors
+}
+
+/// Checks that every unit regex (declared in the spec or used in a datatype) only uses
+/// features that are also supported by the `regex` crate. Features like look-around or
+/// backreferences are only supported by `fancy_regex`.
+///
+/// # Arguments
+///
+/// * `config` - the product config to be checked
+///
+pub(crate) fn check_unit_regexes_portable(config: &ProductConfig) -> Vec<Error> {
+    let mut errors = vec![];
+
+    for (unit, regex) in unit_regexes(config) {
+        // unit regexes are compiled with `fancy_regex` on deserialization already
+        if let Ok(tree) = Expr::parse_tree(regex) {
+            let mut features = BTreeSet::new();
+            collect_fancy_features(&tree.expr, &mut features);
+            if !features.is_empty() {
+                errors.push(Error::RegexNotPortable {
+                    unit: unit.to_string(),
+                    regex: regex.to_string(),
+                    features: features.into_iter().map(str::to_string).collect(),
+                });
+            }
+        }
+    }
+
+    errors
+}
+
+/// Checks that every unit regex (declared in the spec or used in a datatype) is anchored at
+/// the start and the end of the value (e.g. "^[0-9]+$"). Values are matched partially, so an
+/// unanchored regex like "[0-9]+" accepts "abc1" as well.
+///
+/// # Arguments
+///
+/// * `config` - the product config to be checked
+///
+pub(crate) fn check_unit_regexes_anchored(config: &ProductConfig) -> Vec<Error> {
+    let mut errors = vec![];
+
+    for (unit, regex) in unit_regexes(config) {
+        // unit regexes are compiled with `fancy_regex` on deserialization already
+        if let Ok(tree) = Expr::parse_tree(regex) {
+            if !is_anchored(&tree.expr, true) || !is_anchored(&tree.expr, false) {
+                errors.push(Error::UnanchoredUnitRegex {
+                    unit: unit.to_string(),
+                    regex: regex.to_string(),
+                });
+            }
+        }
+    }
+
+    errors
+}
+
+/// Checks that the versions of every property are in order: `asOfVersion` must not be after
+/// `deprecatedSince` or `removedIn`, `deprecatedSince` must not be after `removedIn` and the
+/// `fromVersion` of default and recommended values must not be after their `toVersion`.
+///
+/// # Arguments
+///
+/// * `config` - the product config to be checked
+///
+pub(crate) fn check_versions_ordered(config: &ProductConfig) -> Vec<Error> {
+    let mut errors = vec![];
+
+    for property in &config.properties {
+        let name = display_name(property);
+        let mut ordered = |first: &str,
+                           first_version: Option<&StackableVersion>,
+                           second: &str,
+                           second_version: Option<&StackableVersion>| {
+            if let (Some(first_version), Some(second_version)) = (first_version, second_version) {
+                if first_version.deref() > second_version.deref() {
+                    errors.push(Error::VersionOrderInvalid {
+                        property_name: name.clone(),
+                        first: first.to_string(),
+                        first_version: first_version.deref().to_string(),
+                        second: second.to_string(),
+                        second_version: second_version.deref().to_string(),
+                    });
+                }
+            }
+        };
+
+        let as_of_version = Some(&property.as_of_version);
+        let deprecated_since = property.deprecated_since.as_ref();
+        let removed_in = property.removed_in.as_ref();
+        ordered(
+            "asOfVersion",
+            as_of_version,
+            "deprecatedSince",
+            deprecated_since,
+        );
+        ordered("asOfVersion", as_of_version, "removedIn", removed_in);
+        ordered("deprecatedSince", deprecated_since, "removedIn", removed_in);
+        for value_spec in value_specs(property) {
+            ordered(
+                "fromVersion",
+                value_spec.from_version.as_ref(),
+                "toVersion",
+                value_spec.to_version.as_ref(),
+            );
+        }
+    }
+
+    errors
+}
+
+/// Checks that every property name of a config file matches the naming convention (regex)
+/// provided for that file. Files without a naming convention are not checked.
+///
+/// # Arguments
+///
+/// * `config` - the product config to be checked
+/// * `conventions` - map with file name and respective regular expression for property names
+///
+pub(crate) fn check_file_property_names(
+    config: &ProductConfig,
+    conventions: &BTreeMap<String, String>,
+) -> Vec<Error> {
+    let mut errors = vec![];
+
+    for (file, regex) in conventions {
+        let compiled = match Regex::new(regex) {
+            Ok(compiled) => compiled,
+            Err(_) => {
+                errors.push(Error::InvalidNamingConventionPattern {
+                    file: file.clone(),
+                    regex: regex.clone(),
+                });
+                continue;
+            }
+        };
+
+        for property in &config.properties {
+            if let Some(name) = property.name_from_kind(&PropertyNameKind::File(file.clone())) {
+                if !compiled.is_match(&name).unwrap_or(false) {
+                    errors.push(Error::PropertyNameNotMatchingConvention {
+                        property_name: name,
+                        file: file.clone(),
+                        regex: regex.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    errors
+}
+
+/// Checks that no two properties write the same property name to a config file
+/// ([`PropertyNameKind::File`]). Properties that never apply together (no shared role or
+/// no overlapping version range) may use the same name. Every duplicate name is reported
+/// once per file.
+///
+/// # Arguments
+///
+/// * `config` - the product config to be checked
+///
+pub(crate) fn check_file_property_names_unique(config: &ProductConfig) -> Vec<Error> {
+    // file -> property name -> properties using that name
+    let mut names_per_file: BTreeMap<&str, BTreeMap<&str, Vec<&PropertySpec>>> = BTreeMap::new();
+
+    for property in &config.properties {
+        for property_name in &property.property_names {
+            if let PropertyNameKind::File(file) = &property_name.kind {
+                names_per_file
+                    .entry(file)
+                    .or_default()
+                    .entry(&property_name.name)
+                    .or_default()
+                    .push(property);
+            }
+        }
+    }
+
+    names_per_file
+        .into_iter()
+        .flat_map(|(file, names)| {
+            names
+                .into_iter()
+                .filter(|(_, properties)| {
+                    properties
+                        .iter()
+                        .enumerate()
+                        .any(|(i, a)| properties[i + 1..].iter().any(|b| apply_together(a, b)))
+                })
+                .map(move |(name, _)| Error::DuplicateFilePropertyName {
+                    property_name: name.to_string(),
+                    file: file.to_string(),
+                })
+        })
+        .collect()
+}
+
+/// Returns true if both properties may apply to the same role and product version, i.e. they
+/// share a role and their version ranges (from `as_of_version` until `removed_in`) overlap.
+fn apply_together(a: &PropertySpec, b: &PropertySpec) -> bool {
+    let shares_role = a.roles.iter().any(|role| b.has_role(&role.name));
+    // each property must be introduced before the other one is removed
+    let starts_before_removed =
+        |first: &PropertySpec, second: &PropertySpec| match &second.removed_in {
+            Some(removed_in) => *first.as_of_version < **removed_in,
+            None => true,
+        };
+    shares_role && starts_before_removed(a, b) && starts_before_removed(b, a)
+}
+
+/// Collects all features of the regex expression that are only supported by `fancy_regex`.
+fn collect_fancy_features(expr: &Expr, features: &mut BTreeSet<&'static str>) {
+    match expr {
+        Expr::Concat(children) | Expr::Alt(children) => {
+            for child in children {
+                collect_fancy_features(child, features);
+            }
+        }
+        Expr::Group(child) | Expr::Repeat { child, .. } => collect_fancy_features(child, features),
+        Expr::LookAround(child, _) => {
+            features.insert("look-around");
+            collect_fancy_features(child, features);
+        }
+        Expr::AtomicGroup(child) => {
+            features.insert("atomic group");
+            collect_fancy_features(child, features);
+        }
+        Expr::Backref(_) => {
+            features.insert("backreference");
+        }
+        Expr::KeepOut => {
+            features.insert("keep out");
+        }
+        Expr::ContinueFromPreviousMatchEnd => {
+            features.insert("continue from previous match end");
+        }
+        Expr::BackrefExistsCondition(_) | Expr::Conditional { .. } => {
+            features.insert("conditional");
+        }
+        _ => {}
+    }
+}
+
+/// Returns true if the regex expression is anchored at the start (or end) of the text or
+/// line, e.g. "^a|^b" or "(^a)" are anchored at the start.
+fn is_anchored(expr: &Expr, start: bool) -> bool {
+    match expr {
+        Expr::Assertion(Assertion::StartText | Assertion::StartLine { .. }) => start,
+        Expr::Assertion(Assertion::EndText | Assertion::EndLine { .. }) => !start,
+        Expr::Concat(children) => {
+            // skip zero width word boundaries like in "(^a)\b$"
+            let mut children = children.iter().filter(|child| {
+                !matches!(
+                    child,
+                    Expr::Assertion(
+                        Assertion::WordBoundary
+                            | Assertion::LeftWordBoundary
+                            | Assertion::RightWordBoundary
+                            | Assertion::NotWordBoundary
+                    )
+                )
+            });
+            let child = if start {
+                children.next()
+            } else {
+                children.next_back()
+            };
+            child.is_some_and(|child| is_anchored(child, start))
+        }
+        Expr::Alt(children) => children.iter().all(|child| is_anchored(child, start)),
+        Expr::Group(child) | Expr::AtomicGroup(child) => is_anchored(child, start),
+        _ => false,
+    }
+}
+
+/// Returns all units declared in the spec or used in property datatypes (spec units first).
+/// Units used in multiple places are returned multiple times.
+pub(crate) fn all_units(config: &ProductConfig) -> impl Iterator<Item = &Unit> {
+    let datatype_units = config
+        .properties
+        .iter()
+        .flat_map(|property| property.all_datatypes())
+        .filter_map(Datatype::unit);
+
+    config.spec.units().chain(datatype_units)
+}
+
+/// Returns all distinct unit names and regex expressions declared in the spec or used in
+/// property datatypes.
+fn unit_regexes(config: &ProductConfig) -> BTreeSet<(&str, &str)> {
+    all_units(config)
+        .map(|unit| (unit.name.as_str(), unit.regex.expression.as_str()))
+        .collect()
+}
+
+/// Returns all default and recommended value specs of a property.
+fn value_specs(property: &PropertySpec) -> impl Iterator<Item = &PropertyValueSpec> {
+    property
+        .default_values
+        .iter()
+        .chain(property.recommended_values.iter())
+        .flatten()
+}
+
+/// Returns a name for a property spec to be used in error messages, consisting of all
+/// its property names.
+fn display_name(property: &PropertySpec) -> String {
+    property.all_property_names().join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ProductConfigManager;
+
+    #[test]
+    fn test_check_value_specs_in_allowed_values() {
+        let manager =
+            ProductConfigManager::from_yaml_file("data/test_yamls/self_check_allowed_values.yaml")
+                .unwrap();
+
+        let result = check_value_specs_in_allowed_values(&manager.config);
+
+        assert_eq!(
+            result,
+            vec![
+                Error::PropertyValueNotInAllowedValues {
+                    property_name: "ENV_MODE".to_string(),
+                    value: "C".to_string(),
+                    allowed_values: vec!["A".to_string(), "B".to_string()],
+                },
+                Error::PropertyValueNotMatchingAllowedPatterns {
+                    property_name: "ENV_LOG_FILE".to_string(),
+                    value: "relative.log".to_string(),
+                    patterns: vec!["^/.+\\.log$".to_string()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_allowed_values_unique() {
+        let manager =
+            ProductConfigManager::from_yaml_file("data/test_yamls/self_check_allowed_values.yaml")
+                .unwrap();
+
+        let result = check_allowed_values_unique(&manager.config);
+
+        assert_eq!(
+            result,
+            vec![
+                Error::DuplicateAllowedValue {
+                    property_name: "ENV_COLOR".to_string(),
+                    value: "red".to_string(),
+                },
+                Error::DuplicateAllowedValue {
+                    property_name: "ENV_SHAPE".to_string(),
+                    value: "Circle".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_value_specs_datatype() {
+        let manager =
+            ProductConfigManager::from_yaml_file("data/test_yamls/self_check_value_datatypes.yaml")
+                .unwrap();
+
+        let result = check_value_specs_datatype(&manager.config);
+
+        assert_eq!(
+            result,
+            vec![
+                Error::PropertyValueOutOfBounds {
+                    property_name: "ENV_HEAP".to_string(),
+                    received: "2048".to_string(),
+                    expected: "1024".to_string(),
+                    bound_kind: BoundKind::Inclusive,
+                },
+                Error::DatatypeNotMatching {
+                    property_name: "ENV_RATIO".to_string(),
+                    value: "high".to_string(),
+                    datatype: "f64".to_string(),
+                },
+                Error::PropertyValueOutOfBounds {
+                    property_name: "ENV_SIZES".to_string(),
+                    received: "20".to_string(),
+                    expected: "10".to_string(),
+                    bound_kind: BoundKind::Inclusive,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_expansion_roles_defined() {
+        let manager =
+            ProductConfigManager::from_yaml_file("data/test_yamls/self_check_expansion_roles.yaml")
+                .unwrap();
+
+        let result = check_expansion_roles_defined(&manager.config);
+
+        assert_eq!(
+            result,
+            vec![Error::UndefinedExpansionRole {
+                property_name: "ENV_PASSWORD".to_string(),
+                expansion: "ENV_ENABLE_PASSWORD".to_string(),
+                role: "role_undefined".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_check_expansion_values() {
+        let manager =
+            ProductConfigManager::from_yaml_file("data/test_yamls/expansion_invalid_value.yaml")
+                .unwrap();
+
+        let result = check_expansion_values(&manager.config);
+
+        assert_eq!(
+            result,
+            vec![Error::PropertyValueOutOfBounds {
+                property_name: "ENV_CACHE_SIZE".to_string(),
+                received: "0".to_string(),
+                expected: "1".to_string(),
+                bound_kind: BoundKind::Inclusive,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_check_quantity_units_known() {
+        let manager =
+            ProductConfigManager::from_yaml_file("data/test_yamls/self_check_units.yaml").unwrap();
+
+        let result = check_quantity_units_known(&manager.config);
+
+        assert_eq!(
+            result,
+            vec![Error::UnknownUnit {
+                property_name: "ENV_HEAP".to_string(),
+                unit: "Mb".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_check_file_property_names_unique() {
+        let manager =
+            ProductConfigManager::from_yaml_file("data/test_yamls/self_check_duplicates.yaml")
+                .unwrap();
+
+        assert_eq!(
+            check_file_property_names_unique(&manager.config),
+            vec![Error::DuplicateFilePropertyName {
+                property_name: "log.dirs".to_string(),
+                file: "server.properties".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_check_datatype_bounds() {
+        let manager =
+            ProductConfigManager::from_yaml_file("data/test_yamls/self_check_bounds.yaml").unwrap();
+
+        assert_eq!(
+            check_datatype_bounds(&manager.config),
+            vec![
+                Error::PropertyBoundsInverted {
+                    property_name: "ENV_INTEGER_INVERTED".to_string(),
+                    min: "100".to_string(),
+                    max: "10".to_string(),
+                },
+                Error::PropertyBoundsInverted {
+                    property_name: "ENV_FLOAT_INVERTED".to_string(),
+                    min: "1.5".to_string(),
+                    max: "0.5".to_string(),
+                },
+                Error::DatatypeNotMatching {
+                    property_name: "ENV_INTEGER_NOT_PARSABLE".to_string(),
+                    value: "1.5".to_string(),
+                    datatype: "i64".to_string(),
+                },
+                Error::PropertyBoundsInverted {
+                    property_name: "ENV_FLOAT_EXCLUSIVE_EMPTY".to_string(),
+                    min: "1.0".to_string(),
+                    max: "1.0".to_string(),
+                },
+                Error::PropertyBoundsInverted {
+                    property_name: "ENV_DURATION_INVERTED".to_string(),
+                    min: "1h".to_string(),
+                    max: "30m".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_unit_regexes() {
+        let manager =
+            ProductConfigManager::from_yaml_file("data/test_yamls/self_check_regex.yaml").unwrap();
+
+        assert_eq!(
+            check_unit_regexes_portable(&manager.config),
+            vec![Error::RegexNotPortable {
+                unit: "lookahead".to_string(),
+                regex: "^(?=[a-z])\\w+$".to_string(),
+                features: vec!["look-around".to_string()],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_check_unit_regexes_anchored() {
+        let manager =
+            ProductConfigManager::from_yaml_file("data/test_yamls/self_check_unanchored.yaml")
+                .unwrap();
+
+        assert_eq!(
+            check_unit_regexes_anchored(&manager.config),
+            vec![
+                Error::UnanchoredUnitRegex {
+                    unit: "alternatives".to_string(),
+                    regex: "^/|(/[\\w-]+)+$".to_string(),
+                },
+                Error::UnanchoredUnitRegex {
+                    unit: "start_only".to_string(),
+                    regex: "^[0-9]+".to_string(),
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_versions_ordered() {
+        let manager =
+            ProductConfigManager::from_yaml_file("data/test_yamls/self_check_version_order.yaml")
+                .unwrap();
+
+        let unordered = |first: &str, first_version: &str, second: &str, second_version: &str| {
+            Error::VersionOrderInvalid {
+                property_name: "ENV_UNORDERED".to_string(),
+                first: first.to_string(),
+                first_version: first_version.to_string(),
+                second: second.to_string(),
+                second_version: second_version.to_string(),
+            }
+        };
+        assert_eq!(
+            check_versions_ordered(&manager.config),
+            vec![
+                unordered("asOfVersion", "0.5.0", "deprecatedSince", "0.4.0"),
+                unordered("asOfVersion", "0.5.0", "removedIn", "0.3.0"),
+                unordered("deprecatedSince", "0.4.0", "removedIn", "0.3.0"),
+                unordered("fromVersion", "0.6.0", "toVersion", "0.5.0"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_unit_examples_match() {
+        let manager =
+            ProductConfigManager::from_yaml_file("data/test_yamls/unit_examples.yaml").unwrap();
+
+        assert_eq!(
+            check_unit_examples_match(&manager.config),
+            vec![Error::UnitExampleNotMatching {
+                unit: "port".to_string(),
+                regex: "^[0-9]{1,5}$".to_string(),
+                example: "http".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_check_file_property_names() {
+        let manager =
+            ProductConfigManager::from_yaml_file("data/test_yamls/self_check_naming.yaml").unwrap();
+        let conventions = BTreeMap::from([
+            (
+                "server.properties".to_string(),
+                "^[a-z0-9]+(\\.[a-z0-9]+)*$".to_string(),
+            ),
+            ("other.properties".to_string(), "(".to_string()),
+        ]);
+
+        assert_eq!(
+            check_file_property_names(&manager.config, &conventions),
+            vec![
+                Error::InvalidNamingConventionPattern {
+                    file: "other.properties".to_string(),
+                    regex: "(".to_string(),
+                },
+                Error::PropertyNameNotMatchingConvention {
+                    property_name: "log.Retention_Hours".to_string(),
+                    file: "server.properties".to_string(),
+                    regex: "^[a-z0-9]+(\\.[a-z0-9]+)*$".to_string(),
+                }
+            ]
+        );
+    }
+}
@@ -1,5 +1,6 @@
 //! This module provides a serde [`serde::ser::Serializer`] to convert a (more or less)
-//! arbitrary struct into a [`HashMap`].
+//! arbitrary struct into a [`HashMap`] or a [`BTreeMap`] (sorted by key, e.g. to generate
+//! config files deterministically).
 //!
 //! This can be used in products using this library to provide a strongly typed struct with all configuration parameters which can then be converted into a HashMap as required by this library.
 //!
@@ -24,9 +25,11 @@
 //! let config_map = ser::to_hash_map(&config).unwrap();
 //!
 //! ```
+use base64::prelude::{Engine, BASE64_STANDARD};
 use serde::de;
 use serde::ser::{self, Serialize};
-use std::collections::HashMap;
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt::{self, Display};
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -35,6 +38,20 @@ pub type Result<T> = std::result::Result<T, Error>;
 pub enum Error {
     Message(String),
     UnsupportedType,
+    /// Two values were serialized to the same key, e.g. a field renamed to "foo.bar" and a
+    /// field "foo" containing a nested field "bar".
+    DuplicateKey(String),
+    /// Sequences are nested deeper than [`SerOptions::nested_separators`] provides separators
+    /// for. Contains the nesting depth of the sequence that could not be joined.
+    NestingTooDeep(usize),
+    /// A required field is missing (see [`crate::de`]). Contains the dotted key of the field.
+    MissingField(String),
+    /// A value could not be parsed into the requested type (see [`crate::de`]).
+    InvalidValue {
+        key: String,
+        value: String,
+        expected: String,
+    },
 }
 
 impl ser::Error for Error {
@@ -47,6 +64,10 @@ impl de::Error for Error {
     fn custom<T: Display>(msg: T) -> Self {
         Error::Message(msg.to_string())
     }
+
+    fn missing_field(field: &'static str) -> Self {
+        Error::MissingField(field.to_string())
+    }
 }
 
 impl Display for Error {
@@ -54,6 +75,20 @@ impl Display for Error {
         match self {
             Error::Message(msg) => write!(f, "{}", msg),
             Error::UnsupportedType => f.write_str("unsupported type"),
+            Error::DuplicateKey(key) => write!(f, "duplicate key '{key}'"),
+            Error::NestingTooDeep(depth) => write!(
+                f,
+                "no nested separator configured for sequences nested {depth} level(s) deep"
+            ),
+            Error::MissingField(key) => write!(f, "missing field `{key}`"),
+            Error::InvalidValue {
+                key,
+                value,
+                expected,
+            } => write!(
+                f,
+                "invalid value '{value}' for '{key}': expected {expected}"
+            ),
         }
     }
 }
@@ -64,8 +99,8 @@ impl std::error::Error for Error {}
 /// Other types (e.g. tuples, sequences etc.) are not supported
 ///
 /// NOTE: There will be edge-cases that this method does not support.
-/// One example being conflicts. Two things can map to the same key.
-/// We don't currently check for that.
+/// One example being conflicts. Two things can map to the same key, which results in
+/// [`Error::DuplicateKey`].
 ///
 /// Field names of structs will be the keys of the resulting map.
 /// These field types are supported:
@@ -75,7 +110,7 @@ impl std::error::Error for Error {}
 /// * floating-point types
 /// * char
 /// * String/str
-/// * Option: Will be serialized as just the contained value if it's Some. None will be omitted entirely.
+/// * Option: Will be serialized as just the contained value if it's Some. None will be omitted entirely (or serialized as empty string, see [`SerOptions::keep_none_as_empty`]).
 /// * Unit: Will be omitted
 /// * Unit struct: Will be omitted
 /// * Enum
@@ -85,27 +120,104 @@ impl std::error::Error for Error {}
 /// * structs: See Map
 /// * struct variant: See Map
 ///
-/// These are supported with some limitations:
-/// * sequences (e.g. Vec)
-/// * tuple
-/// * tuple struct
-/// * tuple variant (see sequence)
+/// * sequences (e.g. Vec): The elements will be joined using a separator (see [`SerOptions`])
+/// * tuple: See sequences
+/// * tuple struct: See sequences
+/// * tuple variant: See sequences
 ///
-/// The limitation being that currently we do not support any of these in a nested fashion (e.g. a vector of tuples).
-/// There will be no error but the result will be undefined.
-/// This is an implementation limitation that can be lifted later if needed.
+/// Sequences can be nested (e.g. a vector of tuples). Every nesting level uses its own
+/// separator, e.g. `vec![(1, 2), (3, 4)]` is serialized as "1,2;3,4" by default.
 ///
-/// These are not supported:
-/// * bytes
+/// Bytes are encoded as base64 by default (see [`BytesEncoding`]). Note that e.g. `Vec<u8>` is
+/// serialized as a sequence unless it is annotated (e.g. using `serde_bytes`).
 pub fn to_hash_map<T>(value: &T) -> Result<HashMap<String, String>>
+where
+    T: Serialize,
+{
+    to_hash_map_with_options(value, &SerOptions::default())
+}
+
+/// Same as [`to_hash_map`] but returns a [`BTreeMap`], which iterates the keys in sorted
+/// order. Prefer this if the output is written e.g. into files that should be reproducible.
+pub fn to_btree_map<T>(value: &T) -> Result<BTreeMap<String, String>>
+where
+    T: Serialize,
+{
+    to_btree_map_with_options(value, &SerOptions::default())
+}
+
+/// Options for [`to_hash_map_with_options`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SerOptions {
+    /// The separator used to join the elements of sequences and tuples, defaults to `,`.
+    pub separator: String,
+    /// The separators used to join the elements of nested sequences, starting with the
+    /// sequences containing one level of nested sequences. Defaults to `;` and `|`. Every
+    /// nesting level needs its own separator, deeper nesting fails with
+    /// [`Error::NestingTooDeep`].
+    pub nested_separators: Vec<String>,
+    /// If set, separators (and backslashes) within elements are escaped with a backslash so
+    /// the joined value can be split unambiguously. Defaults to `false`.
+    pub escape_separator: bool,
+    /// The encoding of bytes, defaults to [`BytesEncoding::Base64`].
+    pub bytes_encoding: BytesEncoding,
+    /// If set, `None` values are serialized as empty strings instead of being omitted (this
+    /// applies to elements of sequences as well). Defaults to `false`.
+    pub keep_none_as_empty: bool,
+}
+
+/// The encoding used to serialize bytes into a string.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum BytesEncoding {
+    /// Standard base64 with padding.
+    #[default]
+    Base64,
+    /// Lower case hexadecimal.
+    Hex,
+}
+
+impl Default for SerOptions {
+    fn default() -> Self {
+        SerOptions {
+            separator: ",".to_string(),
+            nested_separators: vec![";".to_string(), "|".to_string()],
+            escape_separator: false,
+            bytes_encoding: BytesEncoding::default(),
+            keep_none_as_empty: false,
+        }
+    }
+}
+
+/// Same as [`to_hash_map`] but joins the elements of sequences and tuples according to the
+/// given [`SerOptions`].
+pub fn to_hash_map_with_options<T>(
+    value: &T,
+    options: &SerOptions,
+) -> Result<HashMap<String, String>>
+where
+    T: Serialize,
+{
+    Ok(to_btree_map_with_options(value, options)?
+        .into_iter()
+        .collect())
+}
+
+/// Same as [`to_btree_map`] but joins the elements of sequences and tuples according to the
+/// given [`SerOptions`].
+pub fn to_btree_map_with_options<T>(
+    value: &T,
+    options: &SerOptions,
+) -> Result<BTreeMap<String, String>>
 where
     T: Serialize,
 {
     let mut serializer = Serializer {
-        output: HashMap::new(),
+        output: BTreeMap::new(),
         current_field: None,
-        sequence: None,
+        sequences: Vec::new(),
         value: None,
+        value_depth: 0,
+        options: options.clone(),
     };
     value.serialize(&mut serializer)?;
     Ok(serializer.output)
@@ -115,19 +227,104 @@ where
 /// It is used to collect intermediate data while we walk the source object.
 // TODO: We need to detect when we're being called on something that is not a Map, Struct or Struct Variant
 struct Serializer {
-    output: HashMap<String, String>,
+    output: BTreeMap<String, String>,
 
     // This stores the current field name which includes all its parents.
     // The parents will be concatenated using dots (".", e.g. "foo.bar")
     current_field: Option<String>,
 
-    // Here we're collecting a sequence of values before we can move it to the `value` field
-    // TODO: Nested sequences will break this. It'll require a better design.
-    sequence: Option<String>,
+    // Here we're collecting the elements of sequences before we can join them into the `value`
+    // field. Nested sequences push a new entry which is completed before the outer one.
+    sequences: Vec<PartialSequence>,
 
     // Due to the way serde works we need a way to also store the intermediate results of each field
     // after conversion to a String
     value: Option<String>,
+
+    // The nesting depth of sequences contained in `value` (0 for plain values), used to pick the
+    // separator of the enclosing sequence
+    value_depth: usize,
+
+    // Determines how the elements of sequences are joined
+    options: SerOptions,
+}
+
+// A sequence whose elements are still being serialized
+#[derive(Default)]
+struct PartialSequence {
+    elements: Vec<String>,
+    // The maximum nesting depth of the elements
+    depth: usize,
+}
+
+impl Serializer {
+    // Adds the key to the output, failing instead of overwriting an already serialized key
+    fn insert_output(&mut self, key: String, value: String) -> Result<()> {
+        match self.output.entry(key) {
+            Entry::Occupied(entry) => Err(Error::DuplicateKey(entry.key().clone())),
+            Entry::Vacant(entry) => {
+                entry.insert(value);
+                Ok(())
+            }
+        }
+    }
+
+    // Takes the current value, resetting its nesting depth
+    fn take_value(&mut self) -> Option<String> {
+        self.value_depth = 0;
+        self.value.take()
+    }
+
+    fn start_sequence(&mut self) {
+        self.sequences.push(PartialSequence::default());
+    }
+
+    // Appends the current value (if any) to the innermost sequence
+    fn push_sequence_element(&mut self) {
+        let depth = self.value_depth;
+        if let (Some(value), Some(sequence)) = (self.take_value(), self.sequences.last_mut()) {
+            sequence.elements.push(value);
+            sequence.depth = sequence.depth.max(depth);
+        }
+    }
+
+    // Joins the elements of the innermost sequence into the current value, using the separator
+    // of its nesting depth
+    fn end_sequence(&mut self) -> Result<()> {
+        let Some(sequence) = self.sequences.pop() else {
+            return Ok(());
+        };
+        let separator = self.separator(sequence.depth)?;
+        let elements = sequence
+            .elements
+            .iter()
+            .map(|element| {
+                if self.options.escape_separator {
+                    element
+                        .replace('\\', "\\\\")
+                        .replace(separator, &format!("\\{separator}"))
+                } else {
+                    element.clone()
+                }
+            })
+            .collect::<Vec<_>>();
+
+        self.value = (!elements.is_empty()).then(|| elements.join(separator));
+        self.value_depth = sequence.depth + 1;
+        Ok(())
+    }
+
+    fn separator(&self, depth: usize) -> Result<&str> {
+        match depth {
+            0 => Ok(&self.options.separator),
+            _ => self
+                .options
+                .nested_separators
+                .get(depth - 1)
+                .map(String::as_str)
+                .ok_or(Error::NestingTooDeep(depth)),
+        }
+    }
 }
 
 impl<'a> ser::Serializer for &'a mut Serializer {
@@ -147,10 +344,15 @@ impl<'a> ser::Serializer for &'a mut Serializer {
     type SerializeStruct = Self;
     type SerializeStructVariant = Self;
 
-    // Not sure what to make out of a byte array.
-    // Could be converted into a String but for now we don't support it.
-    fn serialize_bytes(self, _: &[u8]) -> Result<()> {
-        Err(Error::UnsupportedType)
+    // Bytes are encoded into a String according to the configured encoding.
+    fn serialize_bytes(self, v: &[u8]) -> Result<()> {
+        let value = match self.options.bytes_encoding {
+            BytesEncoding::Base64 => BASE64_STANDARD.encode(v),
+            BytesEncoding::Hex => v.iter().map(|byte| format!("{byte:02x}")).collect(),
+        };
+
+        self.value = Some(value);
+        Ok(())
     }
 
     fn serialize_bool(self, v: bool) -> Result<()> {
@@ -229,7 +431,7 @@ impl<'a> ser::Serializer for &'a mut Serializer {
     }
 
     fn serialize_none(self) -> Result<()> {
-        self.value = None;
+        self.value = self.options.keep_none_as_empty.then(String::new);
         Ok(())
     }
 
@@ -284,7 +486,7 @@ impl<'a> ser::Serializer for &'a mut Serializer {
     }
 
     fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq> {
-        self.sequence = None;
+        self.start_sequence();
         Ok(self)
     }
 
@@ -319,6 +521,7 @@ impl<'a> ser::Serializer for &'a mut Serializer {
         _: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeTupleVariant> {
+        self.start_sequence();
         Ok(self)
     }
 
@@ -357,16 +560,16 @@ impl<'a> ser::SerializeMap for &'a mut Serializer {
         V: Serialize,
     {
         key.serialize(&mut **self)?;
-        let key = self.value.take();
+        let key = self.take_value();
 
         value.serialize(&mut **self)?;
-        let value = self.value.take();
+        let value = self.take_value();
 
         if let (Some(key), Some(value)) = (key, value) {
-            self.output.insert(
+            self.insert_output(
                 format!("{}.{}", self.current_field.as_ref().unwrap(), key),
                 value,
-            );
+            )?;
         }
 
         Ok(())
@@ -397,10 +600,9 @@ impl<'a> ser::SerializeStruct for &'a mut Serializer {
         }
 
         value.serialize(&mut **self)?;
-        let value = self.value.take();
+        let value = self.take_value();
         if let Some(value) = value {
-            self.output
-                .insert(self.current_field.as_ref().unwrap().to_string(), value);
+            self.insert_output(self.current_field.as_ref().unwrap().to_string(), value)?;
         }
 
         self.current_field = original_field;
@@ -422,24 +624,13 @@ impl<'a> ser::SerializeSeq for &'a mut Serializer {
         T: Serialize,
     {
         value.serialize(&mut **self)?;
-        if let Some(ref value) = self.value {
-            // If our sequence already contains Some we need to append a comma (TODO: Make configurable)
-            // At this point we're certain that the current value serializes to something
-            if let Some(current_sequence) = self.sequence.as_mut() {
-                current_sequence.push(',');
-            }
-
-            self.sequence
-                .get_or_insert_with(String::new)
-                .push_str(value);
-        }
+        self.push_sequence_element();
 
         Ok(())
     }
 
     fn end(self) -> Result<Self::Ok> {
-        self.value = self.sequence.take();
-        Ok(())
+        self.end_sequence()
     }
 }
 
@@ -452,24 +643,13 @@ impl<'a> ser::SerializeTuple for &'a mut Serializer {
         T: ?Sized + Serialize,
     {
         value.serialize(&mut **self)?;
-        if let Some(ref value) = self.value {
-            // If our sequence already contains Some we need to append a comma (TODO: Make configurable)
-            // At this point we're certain that the current value serializes to something
-            if let Some(current_sequence) = self.sequence.as_mut() {
-                current_sequence.push(',');
-            }
-
-            self.sequence
-                .get_or_insert_with(String::new)
-                .push_str(value);
-        }
+        self.push_sequence_element();
 
         Ok(())
     }
 
     fn end(self) -> Result<()> {
-        self.value = self.sequence.take();
-        Ok(())
+        self.end_sequence()
     }
 }
 
@@ -482,24 +662,13 @@ impl<'a> ser::SerializeTupleStruct for &'a mut Serializer {
         T: ?Sized + Serialize,
     {
         value.serialize(&mut **self)?;
-        if let Some(ref value) = self.value {
-            // If our sequence already contains Some we need to append a comma (TODO: Make configurable)
-            // At this point we're certain that the current value serializes to something
-            if let Some(current_sequence) = self.sequence.as_mut() {
-                current_sequence.push(',');
-            }
-
-            self.sequence
-                .get_or_insert_with(String::new)
-                .push_str(value);
-        }
+        self.push_sequence_element();
 
         Ok(())
     }
 
     fn end(self) -> Result<()> {
-        self.value = self.sequence.take();
-        Ok(())
+        self.end_sequence()
     }
 }
 
@@ -512,24 +681,13 @@ impl<'a> ser::SerializeTupleVariant for &'a mut Serializer {
         T: ?Sized + Serialize,
     {
         value.serialize(&mut **self)?;
-        if let Some(ref value) = self.value {
-            // If our sequence already contains Some we need to append a comma (TODO: Make configurable)
-            // At this point we're certain that the current value serializes to something
-            if let Some(current_sequence) = self.sequence.as_mut() {
-                current_sequence.push(',');
-            }
-
-            self.sequence
-                .get_or_insert_with(String::new)
-                .push_str(value);
-        }
+        self.push_sequence_element();
 
         Ok(())
     }
 
     fn end(self) -> Result<()> {
-        self.value = self.sequence.take();
-        Ok(())
+        self.end_sequence()
     }
 }
 
@@ -551,10 +709,9 @@ impl<'a> ser::SerializeStructVariant for &'a mut Serializer {
         }
 
         value.serialize(&mut **self)?;
-        let value = self.value.take();
+        let value = self.take_value();
         if let Some(value) = value {
-            self.output
-                .insert(self.current_field.as_ref().unwrap().to_string(), value);
+            self.insert_output(self.current_field.as_ref().unwrap().to_string(), value)?;
         }
 
         self.current_field = original_field;
@@ -571,7 +728,10 @@ impl<'a> ser::SerializeStructVariant for &'a mut Serializer {
 
 #[cfg(test)]
 mod tests {
-    use super::to_hash_map;
+    use super::{
+        to_btree_map, to_hash_map, to_hash_map_with_options, BytesEncoding, Error, SerOptions,
+    };
+    use rstest::rstest;
     use serde::Serialize;
     use std::collections::HashMap;
 
@@ -604,8 +764,6 @@ mod tests {
         test_map.insert("foo".to_string(), 123);
         test_map.insert("bar".to_string(), 456);
 
-        // TODO: Doesn't work: nested_sequence: Vec<(i16, u8)>,
-        //  This fails: nested_sequence: vec![(1, 2), (3, 4)],
         #[derive(Serialize)]
         struct Test {
             bool_test: bool,
@@ -639,6 +797,7 @@ mod tests {
 
             sequence_test: Vec<String>,
             tuple_test: (String, i8),
+            nested_sequence: Vec<(i16, u8)>,
 
             newtype_struct_test: NewtypeStruct,
             struct_test: TestStruct,
@@ -678,6 +837,7 @@ mod tests {
 
             sequence_test: vec!["one".to_string(), "two".to_string(), "three".to_string()],
             tuple_test: ("first_tuple_thing".to_string(), 123),
+            nested_sequence: vec![(1, 2), (3, 4)],
 
             newtype_struct_test: NewtypeStruct("foobar".to_string()),
             struct_test: TestStruct {
@@ -721,6 +881,7 @@ mod tests {
 
         assert_eq!(map.remove("sequence_test").unwrap(), "one,two,three");
         assert_eq!(map.remove("tuple_test").unwrap(), "first_tuple_thing,123");
+        assert_eq!(map.remove("nested_sequence").unwrap(), "1,2;3,4");
 
         assert_eq!(map.remove("newtype_struct_test").unwrap(), "foobar");
         assert_eq!(map.remove("struct_test.nested_value").unwrap(), "1234");
@@ -729,4 +890,204 @@ mod tests {
 
         assert!(map.is_empty());
     }
+
+    #[rstest]
+    #[case::default(SerOptions::default(), "a,b,c")]
+    #[case::separator(SerOptions { separator: ";".to_string(), ..SerOptions::default() }, "a,b;c")]
+    #[case::escaped(SerOptions { escape_separator: true, ..SerOptions::default() }, "a\\,b,c")]
+    fn test_sequence_options(#[case] options: SerOptions, #[case] expected: &str) {
+        #[derive(Serialize)]
+        struct Test {
+            sequence: Vec<String>,
+            tuple: (String, String),
+        }
+
+        let test = Test {
+            sequence: vec!["a,b".to_string(), "c".to_string()],
+            tuple: ("a,b".to_string(), "c".to_string()),
+        };
+
+        let map = to_hash_map_with_options(&test, &options).unwrap();
+        assert_eq!(map.get("sequence").map(String::as_str), Some(expected));
+        assert_eq!(map.get("tuple").map(String::as_str), Some(expected));
+    }
+
+    #[test]
+    fn test_sequence_escape_backslash() {
+        #[derive(Serialize)]
+        struct Test {
+            sequence: Vec<String>,
+        }
+
+        let test = Test {
+            sequence: vec!["C:\\temp".to_string(), "a;b".to_string()],
+        };
+        let options = SerOptions {
+            separator: ";".to_string(),
+            escape_separator: true,
+            ..SerOptions::default()
+        };
+
+        let map = to_hash_map_with_options(&test, &options).unwrap();
+        assert_eq!(map["sequence"], "C:\\\\temp;a\\;b");
+    }
+
+    #[rstest]
+    #[case::default(SerOptions::default(), Ok("a,b;c|d|e"))]
+    #[case::nested_separators(
+        SerOptions { nested_separators: vec![";".to_string(), "/".to_string()], ..SerOptions::default() },
+        Ok("a,b;c|d/e")
+    )]
+    #[case::too_few_nested_separators(
+        SerOptions { nested_separators: vec![";".to_string()], ..SerOptions::default() },
+        Err(Error::NestingTooDeep(2))
+    )]
+    #[case::no_nested_separators(
+        SerOptions { nested_separators: Vec::new(), ..SerOptions::default() },
+        Err(Error::NestingTooDeep(1))
+    )]
+    // the element "c|d" contains the separator of the outermost level, so it has to be escaped
+    #[case::escaped(
+        SerOptions { escape_separator: true, ..SerOptions::default() },
+        Ok("a,b;c\\|d|e")
+    )]
+    fn test_nested_sequence(#[case] options: SerOptions, #[case] expected: super::Result<&str>) {
+        #[derive(Serialize)]
+        struct Test {
+            nested: Vec<Vec<Vec<String>>>,
+        }
+
+        let test = Test {
+            nested: vec![
+                vec![
+                    vec!["a".to_string(), "b".to_string()],
+                    vec!["c|d".to_string()],
+                ],
+                vec![vec!["e".to_string()], vec![]],
+            ],
+        };
+
+        let result = to_hash_map_with_options(&test, &options);
+        assert_eq!(
+            result.map(|map| map["nested"].clone()),
+            expected.map(str::to_string)
+        );
+    }
+
+    #[test]
+    fn test_duplicate_key() {
+        #[derive(Serialize)]
+        struct Nested {
+            bar: String,
+        }
+
+        #[derive(Serialize)]
+        struct Test {
+            #[serde(rename = "foo.bar")]
+            foo_bar: String,
+            foo: Nested,
+        }
+
+        let test = Test {
+            foo_bar: "first".to_string(),
+            foo: Nested {
+                bar: "second".to_string(),
+            },
+        };
+
+        assert_eq!(
+            to_hash_map(&test),
+            Err(Error::DuplicateKey("foo.bar".to_string()))
+        );
+    }
+
+    #[rstest]
+    #[case::base64(BytesEncoding::Base64, "c2VjcmV0AP8=")]
+    #[case::hex(BytesEncoding::Hex, "73656372657400ff")]
+    fn test_bytes(#[case] bytes_encoding: BytesEncoding, #[case] expected: &str) {
+        struct Bytes(Vec<u8>);
+
+        impl Serialize for Bytes {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_bytes(&self.0)
+            }
+        }
+
+        #[derive(Serialize)]
+        struct Test {
+            secret: Bytes,
+        }
+
+        let test = Test {
+            secret: Bytes(b"secret\x00\xff".to_vec()),
+        };
+        let options = SerOptions {
+            bytes_encoding,
+            ..SerOptions::default()
+        };
+
+        let map = to_hash_map_with_options(&test, &options).unwrap();
+        assert_eq!(map["secret"], expected);
+    }
+
+    #[rstest]
+    #[case::omitted(false, None)]
+    #[case::empty(true, Some(""))]
+    fn test_keep_none_as_empty(#[case] keep_none_as_empty: bool, #[case] expected: Option<&str>) {
+        #[derive(Serialize)]
+        struct Test {
+            none: Option<String>,
+            some: Option<String>,
+        }
+
+        let test = Test {
+            none: None,
+            some: Some("value".to_string()),
+        };
+        let options = SerOptions {
+            keep_none_as_empty,
+            ..SerOptions::default()
+        };
+
+        let map = to_hash_map_with_options(&test, &options).unwrap();
+        assert_eq!(map.get("none").map(String::as_str), expected);
+        assert_eq!(map.get("some").map(String::as_str), Some("value"));
+    }
+
+    #[test]
+    fn test_btree_map_order() {
+        #[derive(Serialize)]
+        struct Nested {
+            zeta: u8,
+            alpha: u8,
+        }
+
+        #[derive(Serialize)]
+        struct Test {
+            zulu: String,
+            bravo: Nested,
+            alpha: bool,
+            mike: Vec<u8>,
+        }
+
+        let test = Test {
+            zulu: "z".to_string(),
+            bravo: Nested { zeta: 1, alpha: 2 },
+            alpha: true,
+            mike: vec![1, 2],
+        };
+
+        let expected = vec!["alpha", "bravo.alpha", "bravo.zeta", "mike", "zulu"];
+        for _ in 0..10 {
+            let map = to_btree_map(&test).unwrap();
+            assert_eq!(map.keys().collect::<Vec<_>>(), expected);
+        }
+        assert_eq!(
+            to_btree_map(&test)
+                .unwrap()
+                .into_iter()
+                .collect::<HashMap<_, _>>(),
+            to_hash_map(&test).unwrap()
+        );
+    }
 }
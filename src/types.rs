@@ -1,40 +1,227 @@
 use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::ops::RangeInclusive;
 use std::{fmt, ops};
 
-use fancy_regex::Regex;
+use fancy_regex::{Regex, RegexBuilder};
 use schemars::gen::SchemaGenerator;
 use schemars::schema::Schema;
 use schemars::JsonSchema;
-use semver::Version;
-use serde::{de, Deserialize, Deserializer, Serializer};
+use semver::{Version, VersionReq};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use unicode_segmentation::UnicodeSegmentation;
 
 use crate::error;
+use crate::self_check;
 use crate::validation::ValidationResult;
 use std::ops::Deref;
 
-#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialOrd, PartialEq)]
+/// Separates the elements of a value provided for a repeatable property
+/// (see [`PropertySpec::repeatable`]), e.g. "PLAINTEXT://:9092\nSSL://:9093".
+pub const REPEATED_VALUE_SEPARATOR: char = '\n';
+
+/// Separates the elements of array values if no separator is provided in
+/// [`Datatype::Array`], e.g. "a,b,c".
+pub const DEFAULT_ARRAY_SEPARATOR: &str = ",";
+
+#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialOrd, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ProductConfig {
     pub version: String,
     pub spec: Spec,
     pub properties: Vec<PropertyAnchor>,
+    /// Constraints on the number of properties of a group that may be set together, see
+    /// [`crate::ProductConfigManager::check_count_constraints`].
+    #[serde(default, alias = "count_constraints")]
+    pub count_constraints: Vec<CountConstraint>,
+    /// Groups of property names that configure the same setting and must agree, see
+    /// [`crate::ProductConfigManager::check_mirrors`].
+    #[serde(default)]
+    pub mirrors: Vec<Mirror>,
+    /// Paths of product config fragments (relative to this file) whose units and properties
+    /// are merged into this config, see [`crate::ProductConfigManager::from_yaml_file`].
+    /// Resolved includes are removed.
+    #[serde(default)]
+    pub includes: Vec<String>,
+}
+
+/// Builds a [`ProductConfig`] programmatically instead of deserializing it from YAML, e.g. for
+/// tests or small embedded product configs.
+///
+/// # Example
+///
+/// ```
+/// use product_config::types::{
+///     Datatype, ProductConfigBuilder, PropertyName, PropertyNameKind, PropertySpec, Role,
+///     StackableVersion,
+/// };
+/// use product_config::ProductConfigManager;
+///
+/// let property = PropertySpec::new(
+///     vec![PropertyName {
+///         name: "ENV_DEBUG".to_string(),
+///         kind: PropertyNameKind::Env,
+///         datatype: None,
+///     }],
+///     Datatype::Bool,
+///     vec![Role {
+///         name: "role_1".to_string(),
+///         required: false,
+///         no_copy: None,
+///     }],
+///     StackableVersion::new(0, 1, 0),
+/// );
+///
+/// let config = ProductConfigBuilder::new("0.1.0")
+///     .with_property(property)
+///     .build()
+///     .unwrap();
+/// let manager = ProductConfigManager::from(config);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ProductConfigBuilder {
+    version: String,
+    units: Vec<Unit>,
+    properties: Vec<PropertySpec>,
+}
+
+impl ProductConfigBuilder {
+    /// Creates a builder for a product config with the given config version and neither units
+    /// nor properties.
+    pub fn new(version: impl Into<String>) -> Self {
+        ProductConfigBuilder {
+            version: version.into(),
+            units: vec![],
+            properties: vec![],
+        }
+    }
+
+    /// Adds a unit to the spec, which properties may refer to in their datatype.
+    pub fn with_unit(mut self, unit: Unit) -> Self {
+        self.units.push(unit);
+        self
+    }
+
+    /// Adds a property.
+    pub fn with_property(mut self, property: PropertySpec) -> Self {
+        self.properties.push(property);
+        self
+    }
+
+    /// Returns the product config if it passes all checks of
+    /// [`crate::ProductConfigManager::self_check`]. Exclusive bound shorthands are resolved
+    /// like when loading (see [`Datatype::resolve_exclusive_bounds`]). All found problems are
+    /// returned via [`error::Error::SelfCheckFailed`].
+    pub fn build(mut self) -> ValidationResult<ProductConfig> {
+        let mut errors = vec![];
+        for property in &mut self.properties {
+            let property_name = property.all_property_names().join(", ");
+            let datatypes = std::iter::once(&mut property.datatype).chain(
+                property
+                    .property_names
+                    .iter_mut()
+                    .filter_map(|name| name.datatype.as_deref_mut()),
+            );
+            for datatype in datatypes {
+                if let Err(side) = datatype.resolve_exclusive_bounds() {
+                    errors.push(error::Error::ConflictingBounds {
+                        property_name: property_name.clone(),
+                        side: side.to_string(),
+                    });
+                }
+            }
+        }
+
+        let config = ProductConfig {
+            version: self.version,
+            spec: Spec {
+                units: self
+                    .units
+                    .into_iter()
+                    .map(|unit| UnitAnchor { unit })
+                    .collect(),
+            },
+            properties: self
+                .properties
+                .into_iter()
+                .map(|property| PropertyAnchor { property })
+                .collect(),
+            count_constraints: vec![],
+            mirrors: vec![],
+            includes: vec![],
+        };
+
+        errors.extend(self_check::check_all(&config));
+        if !errors.is_empty() {
+            return Err(error::Error::SelfCheckFailed { errors });
+        }
+        Ok(config)
+    }
+}
+
+/// Limits how many of the given properties may be set at the same time, e.g. at most one of
+/// multiple mutually exclusive feature flags.
+#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialOrd, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CountConstraint {
+    /// The name of the constraint used in error messages
+    pub name: String,
+    /// The names of the properties the constraint applies to
+    pub properties: Vec<String>,
+    /// A property counts if its value equals this value (e.g. "true" for feature flags). If not
+    /// provided, a property counts if it has a non default value.
+    pub value: Option<String>,
+    pub min: Option<usize>,
+    pub max: Option<usize>,
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialOrd, PartialEq)]
+/// Links property names of different kinds (e.g. a config file property and an environment
+/// variable) that configure the same logical setting and therefore must have the same value.
+#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialOrd, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Mirror {
+    /// The name of the mirror used in error messages
+    pub name: String,
+    /// The mirrored property names and their kinds
+    pub properties: Vec<PropertyName>,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialOrd, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Spec {
     units: Vec<UnitAnchor>,
 }
 
+impl Spec {
+    /// Returns all units declared in the spec.
+    pub fn units(&self) -> impl Iterator<Item = &Unit> {
+        self.units.iter().map(|anchor| &anchor.unit)
+    }
+
+    /// Appends the units of the other spec.
+    pub(crate) fn merge(&mut self, other: Spec) {
+        self.units.extend(other.units);
+    }
+
+    /// Recompiles all unit regexes with the given backtrack limit.
+    pub(crate) fn set_regex_backtrack_limit(&mut self, limit: usize) -> ValidationResult<()> {
+        for anchor in &mut self.units {
+            anchor.unit.set_regex_backtrack_limit(limit)?;
+        }
+        Ok(())
+    }
+}
+
 /// This is a workaround to use yaml anchors with serde
-#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialOrd, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialOrd, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UnitAnchor {
     pub unit: Unit,
 }
 
 /// This is a workaround to use yaml anchors with serde
-#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialOrd, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialOrd, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PropertyAnchor {
     pub property: PropertySpec,
@@ -48,32 +235,132 @@ impl ops::Deref for PropertyAnchor {
 }
 
 /// Represents one property spec entry for a given property
-#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialOrd, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialOrd, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PropertySpec {
+    #[serde(alias = "property_names")]
     pub property_names: Vec<PropertyName>,
+    #[serde(deserialize_with = "datatype_from_yaml")]
     pub datatype: Datatype,
     pub roles: Vec<Role>,
     #[serde(deserialize_with = "version_from_string")]
     #[serde(serialize_with = "version_to_string")]
+    #[serde(alias = "as_of_version")]
     pub as_of_version: StackableVersion,
+    #[serde(default)]
+    #[serde(deserialize_with = "optional_value_specs_from_yaml")]
+    #[serde(alias = "default_values")]
     pub default_values: Option<Vec<PropertyValueSpec>>,
+    #[serde(default)]
+    #[serde(deserialize_with = "optional_value_specs_from_yaml")]
+    #[serde(alias = "recommended_values")]
     pub recommended_values: Option<Vec<PropertyValueSpec>>,
+    #[serde(alias = "allowed_values")]
     pub allowed_values: Option<Vec<String>>,
+    /// Regular expressions for values that can not be enumerated in `allowed_values`, e.g.
+    /// "^[a-z][a-z0-9-]*$". A value is allowed if it matches any pattern. If `allowed_values`
+    /// are provided as well, values must either be an allowed value or match a pattern.
+    #[serde(default)]
+    #[serde(deserialize_with = "optional_regexes_from_strings")]
+    #[serde(serialize_with = "optional_regexes_to_strings")]
+    #[serde(alias = "allowed_value_patterns")]
+    pub allowed_value_patterns: Option<Vec<StackableRegex>>,
+    /// Matches values case-insensitively against the allowed values and normalizes them to
+    /// the casing of the allowed value, e.g. "TRUE" to "true". Defaults to case-sensitive.
+    #[serde(alias = "case_insensitive")]
+    pub case_insensitive: Option<bool>,
+    /// References a provider of dynamic allowed values registered via
+    /// [`crate::ProductConfigManager::with_allowed_values_provider`].
+    #[serde(alias = "allowed_values_provider")]
+    pub allowed_values_provider: Option<String>,
     #[serde(default)]
     #[serde(deserialize_with = "optional_version_from_string")]
     #[serde(serialize_with = "optional_version_to_string")]
+    #[serde(alias = "deprecated_since")]
     pub deprecated_since: Option<StackableVersion>,
+    /// Properties are rejected as of this version. Between `deprecated_since` and this version
+    /// the property is only deprecated.
+    #[serde(default)]
+    #[serde(deserialize_with = "optional_version_from_string")]
+    #[serde(serialize_with = "optional_version_to_string")]
+    #[serde(alias = "removed_in")]
+    pub removed_in: Option<StackableVersion>,
+    /// The properties replacing this property. If the property is used while deprecated and a
+    /// replacement is not set, [`crate::error::Error::DeprecatedNotMigrated`] is reported.
+    #[serde(alias = "deprecated_for")]
     pub deprecated_for: Option<Vec<String>>,
+    #[serde(alias = "expands_to")]
     pub expands_to: Option<Vec<PropertyExpansion>>,
+    /// Computes the value from an arithmetic expression (`+`, `-`, `*`, `/`, parentheses and
+    /// numeric literals) over other numeric properties of the merged config, e.g.
+    /// "HEAP + OFF_HEAP". The computed value is validated like a user value. Explicit user
+    /// values take precedence.
+    #[serde(alias = "computed_from")]
+    pub computed_from: Option<String>,
+    #[serde(alias = "restart_required")]
     pub restart_required: Option<bool>,
+    /// Categorizes the property (e.g. "security" or "performance"), see
+    /// [`crate::ProductConfigManager::properties_with_tag`].
     pub tags: Option<Vec<String>>,
+    /// Repeatable properties may appear multiple times in a config file. The user value
+    /// contains all elements separated by [`REPEATED_VALUE_SEPARATOR`].
+    pub repeatable: Option<bool>,
+    /// Internal properties are set by the operator and are not exposed to users in listings.
+    /// They are validated like any other property though.
+    pub internal: Option<bool>,
+    /// Feature gated properties only apply if the product was built with this feature, see
+    /// [`crate::ProductConfigManager::with_features`].
+    #[serde(alias = "feature_gate")]
+    pub feature_gate: Option<String>,
+    #[serde(alias = "additional_doc")]
     pub additional_doc: Option<Vec<String>>,
     pub comment: Option<String>,
     pub description: Option<String>,
 }
 
 impl PropertySpec {
+    /// Creates a property spec with the mandatory fields. All optional fields are unset and
+    /// can be set afterwards, e.g. the default values.
+    ///
+    /// # Arguments
+    ///
+    /// * `property_names` - the names of the property per kind
+    /// * `datatype` - the datatype of the property
+    /// * `roles` - the roles the property applies to
+    /// * `as_of_version` - the first product version supporting the property
+    pub fn new(
+        property_names: Vec<PropertyName>,
+        datatype: Datatype,
+        roles: Vec<Role>,
+        as_of_version: StackableVersion,
+    ) -> Self {
+        PropertySpec {
+            property_names,
+            datatype,
+            roles,
+            as_of_version,
+            default_values: None,
+            recommended_values: None,
+            allowed_values: None,
+            allowed_value_patterns: None,
+            case_insensitive: None,
+            allowed_values_provider: None,
+            deprecated_since: None,
+            removed_in: None,
+            deprecated_for: None,
+            expands_to: None,
+            computed_from: None,
+            restart_required: None,
+            tags: None,
+            repeatable: None,
+            internal: None,
+            feature_gate: None,
+            additional_doc: None,
+            comment: None,
+            description: None,
+        }
+    }
+
     /// Extract the (preferred) recommended or default value from the property that matches
     /// the provided version.
     pub fn recommended_or_default(
@@ -96,9 +383,16 @@ impl PropertySpec {
     }
 
     /// Filters a recommended or default [`PropertyValueSpec`] to match the provided version
-    /// via its to and from range.
+    /// via its version requirement or (if no requirement is provided) its to and from range.
     pub fn filter_value(&self, version: &Version, values: &[PropertyValueSpec]) -> Option<String> {
         for value in values {
+            if let Some(version_req) = &value.version_req {
+                if version_req.matches(version) {
+                    return Some(value.value.clone());
+                }
+                continue;
+            }
+
             if let Some(from) = &value.from_version {
                 let from_version = from.deref();
 
@@ -131,6 +425,25 @@ impl PropertySpec {
         None
     }
 
+    /// Returns the datatype for the provided kind: the datatype override of the matching
+    /// property name (if any) or the datatype of the property.
+    pub fn datatype_for_kind(&self, kind: &PropertyNameKind) -> &Datatype {
+        self.property_names
+            .iter()
+            .find(|name| name.kind == *kind)
+            .and_then(|name| name.datatype.as_deref())
+            .unwrap_or(&self.datatype)
+    }
+
+    /// Returns the datatype of the property and all datatype overrides of its property names.
+    pub fn all_datatypes(&self) -> impl Iterator<Item = &Datatype> {
+        std::iter::once(&self.datatype).chain(
+            self.property_names
+                .iter()
+                .filter_map(|name| name.datatype.as_deref()),
+        )
+    }
+
     /// Returns true if the role matches and no_copy is set to true.
     pub fn has_role_no_copy(&self, user_role: &str) -> bool {
         for role in &self.roles {
@@ -174,6 +487,70 @@ impl PropertySpec {
         Ok(false)
     }
 
+    /// Returns true if the product_version is greater or equal the removed_in of the property.
+    pub fn is_version_removed(&self, product_version: &Version) -> ValidationResult<bool> {
+        if let Some(removed_in) = &self.removed_in {
+            return Ok(removed_in.deref() <= product_version);
+        }
+        Ok(false)
+    }
+
+    /// Returns true if the property may appear multiple times.
+    pub fn is_repeatable(&self) -> bool {
+        self.repeatable == Some(true)
+    }
+
+    /// Returns true if the property is categorized with the given tag (see `tags`).
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().flatten().any(|t| t == tag)
+    }
+
+    /// Returns true if values are matched case-insensitively against the allowed values.
+    pub fn is_case_insensitive(&self) -> bool {
+        self.case_insensitive == Some(true)
+    }
+
+    /// Returns true if the property is internal and should not be exposed to users.
+    pub fn is_internal(&self) -> bool {
+        self.internal == Some(true)
+    }
+
+    /// Returns how changes of the property are applied, derived from `restart_required`.
+    pub fn apply_mode(&self) -> ApplyMode {
+        match self.restart_required {
+            Some(true) => ApplyMode::Restart,
+            Some(false) => ApplyMode::Reload,
+            None => ApplyMode::None,
+        }
+    }
+
+    /// Returns the elements of a value. Only repeatable properties may consist of multiple
+    /// elements, other values are returned as is.
+    pub fn value_elements<'a>(&self, value: &'a str) -> Vec<&'a str> {
+        if self.is_repeatable() {
+            value.split(REPEATED_VALUE_SEPARATOR).collect()
+        } else {
+            vec![value]
+        }
+    }
+
+    /// Recompiles all unit regexes of the property (including expanded properties) with the
+    /// given backtrack limit.
+    pub(crate) fn set_regex_backtrack_limit(&mut self, limit: usize) -> ValidationResult<()> {
+        if let Some(unit) = self.datatype.unit_mut() {
+            unit.set_regex_backtrack_limit(limit)?;
+        }
+        for name in &mut self.property_names {
+            if let Some(unit) = name.datatype.as_deref_mut().and_then(Datatype::unit_mut) {
+                unit.set_regex_backtrack_limit(limit)?;
+            }
+        }
+        for expansion in self.expands_to.iter_mut().flatten() {
+            expansion.property.set_regex_backtrack_limit(limit)?;
+        }
+        Ok(())
+    }
+
     /// Returns all known property names.
     pub fn all_property_names(&self) -> Vec<String> {
         self.property_names
@@ -184,11 +561,16 @@ impl PropertySpec {
 }
 
 /// Represents (one of multiple) unique identifier for a property name depending on the type
-#[derive(Clone, Debug, Deserialize, Eq, Hash, JsonSchema, PartialOrd, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Eq, Hash, JsonSchema, PartialOrd, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PropertyName {
     pub name: String,
     pub kind: PropertyNameKind,
+    /// Overrides the datatype of the property for this kind, e.g. if a property is a string
+    /// on the command line but an integer in an environment variable.
+    #[serde(default)]
+    #[serde(deserialize_with = "optional_datatype_from_yaml")]
+    pub datatype: Option<Box<Datatype>>,
 }
 
 impl fmt::Display for PropertyName {
@@ -198,7 +580,9 @@ impl fmt::Display for PropertyName {
 }
 
 /// Represents different config identifier types like config file, environment variable, command line parameter etc.
-#[derive(Clone, Debug, Deserialize, Eq, Hash, JsonSchema, PartialOrd, PartialEq)]
+#[derive(
+    Clone, Debug, Deserialize, Eq, Hash, JsonSchema, Ord, PartialOrd, PartialEq, Serialize,
+)]
 #[serde(tag = "type", content = "file", rename_all = "camelCase")]
 pub enum PropertyNameKind {
     File(String),
@@ -206,6 +590,16 @@ pub enum PropertyNameKind {
     Cli,
 }
 
+impl fmt::Display for PropertyNameKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PropertyNameKind::File(file) => write!(f, "file '{}'", file),
+            PropertyNameKind::Env => write!(f, "env"),
+            PropertyNameKind::Cli => write!(f, "cli"),
+        }
+    }
+}
+
 impl PropertyNameKind {
     pub fn get_file_name(&self) -> String {
         match self {
@@ -216,16 +610,31 @@ impl PropertyNameKind {
 }
 
 /// Represents the config unit (name corresponds to the unit type like password and a given regex)
-#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialOrd, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Eq, Hash, JsonSchema, PartialOrd, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Unit {
     pub name: String,
     #[serde(deserialize_with = "regex_from_string")]
+    #[serde(serialize_with = "regex_to_string")]
     pub regex: StackableRegex,
     pub examples: Option<Vec<String>>,
     pub comment: Option<String>,
 }
 
+impl Unit {
+    /// Recompiles the unit regex with the given backtrack limit.
+    pub(crate) fn set_regex_backtrack_limit(&mut self, limit: usize) -> ValidationResult<()> {
+        self.regex =
+            StackableRegex::compile(&self.regex.expression, Some(limit)).map_err(|_| {
+                error::Error::InvalidRegexPattern {
+                    unit: self.name.clone(),
+                    regex: self.regex.expression.clone(),
+                }
+            })?;
+        Ok(())
+    }
+}
+
 /// This is a workaround to deserialize a string directly into a parsed SemVer version and to
 /// wrap SemVer in case of using another library.
 #[derive(Clone, Debug, Eq, PartialOrd, PartialEq)]
@@ -234,6 +643,13 @@ pub struct StackableVersion {
 }
 
 impl StackableVersion {
+    /// Creates the release version "major.minor.patch".
+    pub fn new(major: u64, minor: u64, patch: u64) -> Self {
+        StackableVersion {
+            version: Version::new(major, minor, patch),
+        }
+    }
+
     pub fn parse(version: &str) -> ValidationResult<Self> {
         Ok(StackableVersion {
             version: Version::parse(version).map_err(|err| error::Error::InvalidVersion {
@@ -242,6 +658,37 @@ impl StackableVersion {
             })?,
         })
     }
+
+    /// Parses the version like [`StackableVersion::parse`], but coerces versions with only one
+    /// or two numeric components (e.g. "1" or "1.5" of legacy products) into SemVer by
+    /// appending zero components ("1.0.0", "1.5.0"). Other invalid versions are still rejected
+    /// with the error of the original version.
+    pub fn parse_lenient(version: &str) -> ValidationResult<Self> {
+        Self::parse(version).or_else(|err| {
+            let components: Vec<&str> = version.split('.').collect();
+            let numeric = components.iter().all(|component| {
+                !component.is_empty() && component.bytes().all(|b| b.is_ascii_digit())
+            });
+            if !numeric || components.len() > 2 {
+                return Err(err);
+            }
+            let coerced = components
+                .into_iter()
+                .chain(std::iter::repeat("0"))
+                .take(3)
+                .collect::<Vec<_>>()
+                .join(".");
+            Self::parse(&coerced).map_err(|_| err)
+        })
+    }
+
+    /// Returns the release of this version, i.e. without pre-release and build metadata
+    /// (e.g. "1.2.0" for "1.2.0-rc1+build").
+    pub fn release(&self) -> Self {
+        StackableVersion {
+            version: Version::new(self.major, self.minor, self.patch),
+        }
+    }
 }
 
 impl ops::Deref for StackableVersion {
@@ -296,10 +743,140 @@ where
 
 impl JsonSchema for StackableVersion {
     fn schema_name() -> String {
-        todo!()
+        "StackableVersion".to_string()
+    }
+    /// Versions are (de)serialized as SemVer strings.
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        String::json_schema(gen)
+    }
+}
+
+/// A SemVer version requirement like ">=1.0, <2.0" or "^1.2", deserialized from its string
+/// representation. Like [`StackableRegex`], this keeps the expression because VersionReq does
+/// not implement PartialOrd and JsonSchema.
+#[derive(Clone, Debug)]
+pub struct StackableVersionReq {
+    pub expression: String,
+    requirement: VersionReq,
+}
+
+impl StackableVersionReq {
+    pub fn parse(expression: &str) -> ValidationResult<Self> {
+        Ok(StackableVersionReq {
+            expression: expression.to_string(),
+            requirement: VersionReq::parse(expression).map_err(|err| {
+                error::Error::InvalidVersionRequirement {
+                    requirement: expression.to_string(),
+                    reason: err.to_string(),
+                }
+            })?,
+        })
+    }
+}
+
+fn optional_version_req_from_string<'de, D>(
+    deserializer: D,
+) -> Result<Option<StackableVersionReq>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s: Option<String> = Option::deserialize(deserializer)?;
+    s.map(|val| StackableVersionReq::parse(&val).map_err(de::Error::custom))
+        .transpose()
+}
+
+/// Deserializes default or recommended values, rejecting values that provide a version
+/// requirement and a from/to version range since it is ambiguous which one applies.
+fn optional_value_specs_from_yaml<'de, D>(
+    deserializer: D,
+) -> Result<Option<Vec<PropertyValueSpec>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value_specs: Option<Vec<PropertyValueSpec>> = Option::deserialize(deserializer)?;
+    for value_spec in value_specs.iter().flatten() {
+        if value_spec.version_req.is_some()
+            && (value_spec.from_version.is_some() || value_spec.to_version.is_some())
+        {
+            return Err(de::Error::custom(format!(
+                "value '{}' provides a version requirement and a from/to version range, only one is allowed",
+                value_spec.value
+            )));
+        }
+    }
+    Ok(value_specs)
+}
+
+/// Deserializes a datatype, resolving the exclusive bound shorthands (see
+/// [`Datatype::resolve_exclusive_bounds`]).
+fn datatype_from_yaml<'de, D>(deserializer: D) -> Result<Datatype, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let mut datatype = Datatype::deserialize(deserializer)?;
+    resolve_exclusive_bounds(&mut datatype)?;
+    Ok(datatype)
+}
+
+fn optional_datatype_from_yaml<'de, D>(deserializer: D) -> Result<Option<Box<Datatype>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let mut datatype: Option<Box<Datatype>> = Option::deserialize(deserializer)?;
+    if let Some(datatype) = &mut datatype {
+        resolve_exclusive_bounds(datatype)?;
+    }
+    Ok(datatype)
+}
+
+fn resolve_exclusive_bounds<E: de::Error>(datatype: &mut Datatype) -> Result<(), E> {
+    datatype.resolve_exclusive_bounds().map_err(|side| {
+        E::custom(format!(
+            "exclusive_{side} must not be combined with {side} or {side}_bound_kind"
+        ))
+    })
+}
+
+fn optional_version_req_to_string<S>(
+    version_req: &Option<StackableVersionReq>,
+    s: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match version_req {
+        Some(version_req) => s.serialize_str(&version_req.expression),
+        None => s.serialize_none(),
+    }
+}
+
+impl ops::Deref for StackableVersionReq {
+    type Target = VersionReq;
+    fn deref(&self) -> &VersionReq {
+        &self.requirement
+    }
+}
+
+impl Eq for StackableVersionReq {}
+impl PartialOrd for StackableVersionReq {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.expression.partial_cmp(&other.expression)
+    }
+}
+
+impl PartialEq for StackableVersionReq {
+    fn eq(&self, other: &Self) -> bool {
+        self.expression == other.expression
+    }
+}
+
+impl JsonSchema for StackableVersionReq {
+    fn schema_name() -> String {
+        "StackableVersionReq".to_string()
     }
-    fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
-        todo!()
+    /// Version requirements are (de)serialized as their expression string.
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        String::json_schema(gen)
     }
 }
 
@@ -315,16 +892,76 @@ pub struct StackableRegex {
     compiled: Regex,
 }
 
+impl StackableRegex {
+    /// Compiles the expression with the default backtrack limit of `fancy_regex`.
+    pub fn new(expression: &str) -> ValidationResult<Self> {
+        Self::compile(expression, None).map_err(|reason| error::Error::InvalidRegex {
+            regex: expression.to_string(),
+            reason,
+        })
+    }
+
+    /// Compiles the expression and returns the compilation error message on failure.
+    /// If no backtrack limit is provided, the default limit of `fancy_regex` is used.
+    fn compile(expression: &str, backtrack_limit: Option<usize>) -> Result<Self, String> {
+        let mut builder = RegexBuilder::new(expression);
+        if let Some(limit) = backtrack_limit {
+            builder.backtrack_limit(limit);
+        }
+        Ok(StackableRegex {
+            expression: expression.to_string(),
+            compiled: builder.build().map_err(|err| err.to_string())?,
+        })
+    }
+}
+
 fn regex_from_string<'de, D>(deserializer: D) -> Result<StackableRegex, D::Error>
 where
     D: Deserializer<'de>,
 {
     let s = String::deserialize(deserializer)?;
-    let r = Regex::new(&s).map_err(de::Error::custom)?;
-    Ok(StackableRegex {
-        expression: s,
-        compiled: r,
-    })
+    StackableRegex::compile(&s, None).map_err(de::Error::custom)
+}
+
+fn optional_regexes_from_strings<'de, D>(
+    deserializer: D,
+) -> Result<Option<Vec<StackableRegex>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let expressions: Option<Vec<String>> = Option::deserialize(deserializer)?;
+    expressions
+        .map(|expressions| {
+            expressions
+                .iter()
+                .map(|expression| {
+                    StackableRegex::compile(expression, None).map_err(|reason| {
+                        de::Error::custom(format!("invalid pattern '{expression}': {reason}"))
+                    })
+                })
+                .collect()
+        })
+        .transpose()
+}
+
+fn optional_regexes_to_strings<S>(
+    regexes: &Option<Vec<StackableRegex>>,
+    s: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match regexes {
+        Some(regexes) => s.collect_seq(regexes.iter().map(|regex| &regex.expression)),
+        None => s.serialize_none(),
+    }
+}
+
+fn regex_to_string<S>(regex: &StackableRegex, s: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    s.serialize_str(&regex.expression)
 }
 
 impl ops::Deref for StackableRegex {
@@ -347,78 +984,527 @@ impl PartialEq for StackableRegex {
     }
 }
 
+impl Hash for StackableRegex {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.expression.hash(state);
+    }
+}
+
 impl JsonSchema for StackableRegex {
     fn schema_name() -> String {
-        todo!()
+        "StackableRegex".to_string()
     }
-    fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
-        todo!()
+    /// Regexes are (de)serialized as their expression string.
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        String::json_schema(gen)
     }
 }
 
 /// Represents the default or recommended values a property may have: since default values
-/// may change with different releases, optional from and to version parameters can be provided
-#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialOrd, PartialEq)]
+/// may change with different releases, optional from and to version parameters or a version
+/// requirement can be provided
+#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialOrd, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PropertyValueSpec {
     #[serde(default)]
     #[serde(deserialize_with = "optional_version_from_string")]
     #[serde(serialize_with = "optional_version_to_string")]
+    #[serde(alias = "from_version")]
     pub from_version: Option<StackableVersion>,
     #[serde(default)]
     #[serde(deserialize_with = "optional_version_from_string")]
     #[serde(serialize_with = "optional_version_to_string")]
+    #[serde(alias = "to_version")]
     pub to_version: Option<StackableVersion>,
+    /// SemVer version requirement (e.g. ">=1.0, <2.0"), used instead of `from_version` and
+    /// `to_version`. Deserializing the property fails if they are provided as well.
+    #[serde(default)]
+    #[serde(deserialize_with = "optional_version_req_from_string")]
+    #[serde(serialize_with = "optional_version_req_to_string")]
+    #[serde(alias = "version_req")]
+    pub version_req: Option<StackableVersionReq>,
     pub value: String,
 }
 
 /// Represents all supported data types
-#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialOrd, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Eq, Hash, JsonSchema, PartialOrd, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase", tag = "type")]
 pub enum Datatype {
     Bool,
     Integer {
         min: Option<String>,
         max: Option<String>,
+        #[serde(alias = "minBoundKind")]
+        min_bound_kind: Option<BoundKind>,
+        #[serde(alias = "maxBoundKind")]
+        max_bound_kind: Option<BoundKind>,
+        /// Shorthand for an exclusive `min` bound, must not be combined with `min` or
+        /// `min_bound_kind`. Resolved into `min` and `min_bound_kind` when loading, see
+        /// [`Datatype::resolve_exclusive_bounds`].
+        #[serde(alias = "exclusiveMin")]
+        exclusive_min: Option<String>,
+        /// Shorthand for an exclusive `max` bound, must not be combined with `max` or
+        /// `max_bound_kind`. Resolved into `max` and `max_bound_kind` when loading, see
+        /// [`Datatype::resolve_exclusive_bounds`].
+        #[serde(alias = "exclusiveMax")]
+        exclusive_max: Option<String>,
         unit: Option<Unit>,
+        #[serde(alias = "acceptedUnits")]
         accepted_units: Option<Vec<String>>,
+        #[serde(alias = "defaultUnit")]
         default_unit: Option<String>,
+        semantic: Option<IntegerSemantic>,
+        /// The only allowed values, compared numerically (e.g. "03" matches "3"). With a
+        /// `semantic` or units, values are compared normalized (e.g. "2" CPUs matches "2000m").
+        #[serde(alias = "allowedNumbers")]
+        allowed_numbers: Option<Vec<String>>,
     },
     Float {
         min: Option<String>,
         max: Option<String>,
+        #[serde(alias = "minBoundKind")]
+        min_bound_kind: Option<BoundKind>,
+        #[serde(alias = "maxBoundKind")]
+        max_bound_kind: Option<BoundKind>,
+        /// Shorthand for an exclusive `min` bound, must not be combined with `min` or
+        /// `min_bound_kind`. Resolved into `min` and `min_bound_kind` when loading, see
+        /// [`Datatype::resolve_exclusive_bounds`].
+        #[serde(alias = "exclusiveMin")]
+        exclusive_min: Option<String>,
+        /// Shorthand for an exclusive `max` bound, must not be combined with `max` or
+        /// `max_bound_kind`. Resolved into `max` and `max_bound_kind` when loading, see
+        /// [`Datatype::resolve_exclusive_bounds`].
+        #[serde(alias = "exclusiveMax")]
+        exclusive_max: Option<String>,
         unit: Option<Unit>,
+        #[serde(alias = "acceptedUnits")]
         accepted_units: Option<Vec<String>>,
+        #[serde(alias = "defaultUnit")]
         default_unit: Option<String>,
+        /// The only allowed values, compared numerically (e.g. "1.50" matches "1.5").
+        #[serde(alias = "allowedNumbers")]
+        allowed_numbers: Option<Vec<String>>,
     },
     String {
         min: Option<String>,
         max: Option<String>,
+        #[serde(alias = "minBoundKind")]
+        min_bound_kind: Option<BoundKind>,
+        #[serde(alias = "maxBoundKind")]
+        max_bound_kind: Option<BoundKind>,
         unit: Option<Unit>,
+        #[serde(alias = "acceptedUnits")]
         accepted_units: Option<Vec<String>>,
+        #[serde(alias = "defaultUnit")]
         default_unit: Option<String>,
+        charset: Option<CharsetClass>,
+        /// How the length is measured for the `min` and `max` bounds, defaults to
+        /// [`LengthMode::Chars`].
+        #[serde(alias = "lengthMode")]
+        length_mode: Option<LengthMode>,
+        /// Whether the value may contain line breaks, defaults to `true`. Should be disabled
+        /// for properties written to single-line formats.
+        #[serde(alias = "allowNewlines")]
+        allow_newlines: Option<bool>,
+        /// The maximum number of lines of the value, e.g. for embedded certificates or scripts.
+        #[serde(alias = "maxLines")]
+        max_lines: Option<usize>,
+        /// A built-in structural format like IP addresses, which is parsed instead of matched
+        /// against a regex.
+        format: Option<StringFormat>,
     },
     Array {
         unit: Option<Unit>,
+        #[serde(alias = "acceptedUnits")]
         accepted_units: Option<Vec<String>>,
+        #[serde(alias = "defaultUnit")]
+        default_unit: Option<String>,
+        /// Separates the elements of array values, defaults to [`DEFAULT_ARRAY_SEPARATOR`].
+        /// JSON style values (e.g. `["a","b"]`) are supported as well.
+        separator: Option<String>,
+    },
+    /// Go/Prometheus style durations like "30s", "5m" or "1h30m" (units "ms", "s", "m", "h"
+    /// and "d"), normalized to a number in the `default_unit` (milliseconds if not provided).
+    /// Numbers without unit are in the `default_unit`. The `min` and `max` bounds are
+    /// durations as well and are compared against the normalized value.
+    Duration {
+        min: Option<String>,
+        max: Option<String>,
+        #[serde(alias = "defaultUnit")]
         default_unit: Option<String>,
     },
 }
 
+impl Datatype {
+    /// Returns the unit of the datatype (if any).
+    pub fn unit(&self) -> Option<&Unit> {
+        match self {
+            Datatype::Bool | Datatype::Duration { .. } => None,
+            Datatype::Integer { unit, .. }
+            | Datatype::Float { unit, .. }
+            | Datatype::String { unit, .. }
+            | Datatype::Array { unit, .. } => unit.as_ref(),
+        }
+    }
+
+    /// Resolves the `exclusive_min` and `exclusive_max` shorthands of numeric datatypes into
+    /// `min`/`max` with an exclusive bound kind, so bounds are only read from `min`/`max`.
+    /// Returns the side ("min" or "max") if a shorthand is combined with the respective bound
+    /// or bound kind.
+    pub(crate) fn resolve_exclusive_bounds(&mut self) -> Result<(), &'static str> {
+        let (Datatype::Integer {
+            min,
+            max,
+            min_bound_kind,
+            max_bound_kind,
+            exclusive_min,
+            exclusive_max,
+            ..
+        }
+        | Datatype::Float {
+            min,
+            max,
+            min_bound_kind,
+            max_bound_kind,
+            exclusive_min,
+            exclusive_max,
+            ..
+        }) = self
+        else {
+            return Ok(());
+        };
+
+        for (side, exclusive, bound, bound_kind) in [
+            ("min", exclusive_min, min, min_bound_kind),
+            ("max", exclusive_max, max, max_bound_kind),
+        ] {
+            let Some(value) = exclusive.take() else {
+                continue;
+            };
+            if bound.is_some() || bound_kind.is_some() {
+                return Err(side);
+            }
+            *bound = Some(value);
+            *bound_kind = Some(BoundKind::Exclusive);
+        }
+        Ok(())
+    }
+
+    /// Returns true if the datatype describes network ports, i.e. it has the port semantic
+    /// ([`IntegerSemantic::Port`]) or the port unit (see [`crate::PORT_UNIT_NAME`]).
+    pub fn is_port(&self) -> bool {
+        matches!(
+            self,
+            Datatype::Integer {
+                semantic: Some(IntegerSemantic::Port),
+                ..
+            }
+        ) || self
+            .unit()
+            .is_some_and(|unit| unit.name == crate::PORT_UNIT_NAME)
+    }
+
+    /// Returns the accepted unit suffixes and the default unit suffix of the datatype. The
+    /// units of [`Datatype::Duration`] are not quantity units and are not included.
+    pub fn quantity_units(&self) -> impl Iterator<Item = &String> {
+        let (accepted_units, default_unit) = match self {
+            Datatype::Bool | Datatype::Duration { .. } => (&None, &None),
+            Datatype::Integer {
+                accepted_units,
+                default_unit,
+                ..
+            }
+            | Datatype::Float {
+                accepted_units,
+                default_unit,
+                ..
+            }
+            | Datatype::String {
+                accepted_units,
+                default_unit,
+                ..
+            }
+            | Datatype::Array {
+                accepted_units,
+                default_unit,
+                ..
+            } => (accepted_units, default_unit),
+        };
+        accepted_units.iter().flatten().chain(default_unit)
+    }
+
+    /// Returns the mutable unit of the datatype (if any).
+    pub(crate) fn unit_mut(&mut self) -> Option<&mut Unit> {
+        match self {
+            Datatype::Bool | Datatype::Duration { .. } => None,
+            Datatype::Integer { unit, .. }
+            | Datatype::Float { unit, .. }
+            | Datatype::String { unit, .. }
+            | Datatype::Array { unit, .. } => unit.as_mut(),
+        }
+    }
+}
+
+/// Represents whether a min or max bound of a datatype is inclusive or exclusive.
+#[derive(
+    Clone, Copy, Debug, Default, Deserialize, Eq, Hash, JsonSchema, PartialOrd, PartialEq, Serialize,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum BoundKind {
+    /// The bound itself is a valid value
+    #[default]
+    Inclusive,
+    /// The bound itself is not a valid value
+    Exclusive,
+}
+
+impl fmt::Display for BoundKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            BoundKind::Inclusive => "inclusive",
+            BoundKind::Exclusive => "exclusive",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Represents how a changed property value is applied by the product.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialOrd, PartialEq)]
+pub enum ApplyMode {
+    /// The product has to be restarted (`restart_required: true`)
+    Restart,
+    /// The product reloads the value without restart (`restart_required: false`)
+    Reload,
+    /// Unknown, `restart_required` is not set or the property is unknown
+    None,
+}
+
+/// Represents how a user config key that is present without a value (`None`) is handled, see
+/// [`crate::ProductConfigManager::with_explicit_none`].
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialOrd, PartialEq)]
+pub enum ExplicitNone {
+    /// The property is forced unset: no recommended or default value is used and the property
+    /// is reported as [`crate::error::Error::PropertyValueMissing`]. This is the default.
+    #[default]
+    Unset,
+    /// The recommended or default value is used and reported as
+    /// [`crate::PropertyValidationResult::Default`]. This applies to optional properties as
+    /// well, which are not added at all if the key is absent. Without any recommended or
+    /// default value the property is still reported as missing.
+    UseDefault,
+}
+
+/// Represents how the length of string values is measured.
+#[derive(
+    Clone, Copy, Debug, Default, Deserialize, Eq, Hash, JsonSchema, PartialOrd, PartialEq, Serialize,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum LengthMode {
+    /// The number of UTF-8 bytes
+    Bytes,
+    /// The number of Unicode scalar values
+    #[default]
+    Chars,
+    /// The number of extended grapheme clusters (user perceived characters)
+    Graphemes,
+}
+
+impl LengthMode {
+    /// Returns the length of the value measured in this mode.
+    pub fn len(&self, value: &str) -> usize {
+        match self {
+            LengthMode::Bytes => value.len(),
+            LengthMode::Chars => value.chars().count(),
+            LengthMode::Graphemes => value.graphemes(true).count(),
+        }
+    }
+}
+
+/// Represents simple character set constraints for string values as a shorthand for common
+/// regular expressions.
+#[derive(
+    Clone, Copy, Debug, Deserialize, Eq, Hash, JsonSchema, PartialOrd, PartialEq, Serialize,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum CharsetClass {
+    /// Only ASCII letters and digits
+    Alnum,
+    /// Only ASCII letters, digits and dashes ("-")
+    AlnumDash,
+    /// Any characters except whitespace
+    NoWhitespace,
+    /// Only ASCII characters
+    Ascii,
+}
+
+impl CharsetClass {
+    /// Returns true if all characters of the value belong to this charset class.
+    pub fn is_match(&self, value: &str) -> bool {
+        match self {
+            CharsetClass::Alnum => value.chars().all(|c| c.is_ascii_alphanumeric()),
+            CharsetClass::AlnumDash => value.chars().all(|c| c.is_ascii_alphanumeric() || c == '-'),
+            CharsetClass::NoWhitespace => !value.chars().any(char::is_whitespace),
+            CharsetClass::Ascii => value.is_ascii(),
+        }
+    }
+}
+
+impl fmt::Display for CharsetClass {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            CharsetClass::Alnum => "alnum",
+            CharsetClass::AlnumDash => "alnumDash",
+            CharsetClass::NoWhitespace => "noWhitespace",
+            CharsetClass::Ascii => "ascii",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Represents built-in structural formats of string values, which are validated by parsing
+/// (e.g. "999.0.0.1" is no valid IPv4 address) rather than by regular expressions.
+#[derive(
+    Clone, Copy, Debug, Deserialize, Eq, Hash, JsonSchema, PartialOrd, PartialEq, Serialize,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum StringFormat {
+    /// IPv4 addresses, e.g. "192.168.0.1"
+    Ipv4,
+    /// IPv6 addresses, e.g. "fe80::1"
+    Ipv6,
+    /// IPv4 or IPv6 addresses
+    Ip,
+    /// IPv4 or IPv6 CIDR blocks, e.g. "10.0.0.0/8" or "fd00::/64"
+    Cidr,
+}
+
+impl StringFormat {
+    /// Returns true if the value is valid in this format.
+    pub fn is_match(&self, value: &str) -> bool {
+        match self {
+            StringFormat::Ipv4 => value.parse::<Ipv4Addr>().is_ok(),
+            StringFormat::Ipv6 => value.parse::<Ipv6Addr>().is_ok(),
+            StringFormat::Ip => value.parse::<IpAddr>().is_ok(),
+            StringFormat::Cidr => {
+                let Some((address, prefix)) = value.split_once('/') else {
+                    return false;
+                };
+                // u8::from_str accepts a leading "+"
+                let Some(prefix) = prefix
+                    .bytes()
+                    .all(|b| b.is_ascii_digit())
+                    .then(|| prefix.parse::<u8>().ok())
+                    .flatten()
+                else {
+                    return false;
+                };
+                match address.parse::<IpAddr>() {
+                    Ok(IpAddr::V4(_)) => prefix <= 32,
+                    Ok(IpAddr::V6(_)) => prefix <= 128,
+                    Err(_) => false,
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for StringFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            StringFormat::Ipv4 => "ipv4",
+            StringFormat::Ipv6 => "ipv6",
+            StringFormat::Ip => "ip",
+            StringFormat::Cidr => "cidr",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Represents quantities with a special notation that are parsed and normalized before
+/// validating integer values. The `min` and `max` bounds refer to the normalized value.
+#[derive(
+    Clone, Copy, Debug, Deserialize, Eq, Hash, JsonSchema, PartialOrd, PartialEq, Serialize,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum IntegerSemantic {
+    /// Kubernetes style CPU quantities in cores (e.g. "2") or millicores (e.g. "500m"),
+    /// normalized to millicores (e.g. "2000m").
+    Cpu,
+    /// Network ports, which must be in the range 1 to 65535 regardless of the bounds.
+    /// Privileged ports (below 1024) are reported as warning.
+    Port,
+}
+
+impl IntegerSemantic {
+    /// Parses the value into the normalized integer quantity. Returns None if the value does
+    /// not match the notation.
+    pub fn parse(&self, value: &str) -> Option<i64> {
+        match self {
+            IntegerSemantic::Cpu => match value.strip_suffix('m') {
+                Some(millicores) => millicores.parse().ok(),
+                None => value.parse::<i64>().ok()?.checked_mul(1000),
+            },
+            IntegerSemantic::Port => value.parse().ok(),
+        }
+    }
+
+    /// Returns the range valid normalized quantities must be in, regardless of the bounds.
+    pub fn valid_range(&self) -> Option<RangeInclusive<i64>> {
+        match self {
+            IntegerSemantic::Cpu => None,
+            IntegerSemantic::Port => Some(1..=65535),
+        }
+    }
+
+    /// Formats the normalized integer quantity.
+    pub fn format(&self, quantity: i64) -> String {
+        match self {
+            IntegerSemantic::Cpu => format!("{}m", quantity),
+            IntegerSemantic::Port => quantity.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for IntegerSemantic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            IntegerSemantic::Cpu => "cpu",
+            IntegerSemantic::Port => "port",
+        };
+        write!(f, "{}", name)
+    }
+}
+
 /// Represents an expansion on another config property and (if available) a required value
 /// e.g. to set ssl certificates one has to set some property use_ssl to true
-#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialOrd, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialOrd, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PropertyExpansion {
     pub property: PropertySpec,
     pub value: Option<String>,
+    /// Only expand if the effective value of the expanding property equals this value, e.g.
+    /// "true" to only add the certificate paths if SSL is enabled. Always expands if not set.
+    #[serde(alias = "trigger_value")]
+    pub trigger_value: Option<String>,
+}
+
+impl PropertyExpansion {
+    /// Returns true if the expansion applies for the (effective) value of the expanding
+    /// property, see `trigger_value`.
+    pub fn is_triggered_by(&self, value: Option<&str>) -> bool {
+        match &self.trigger_value {
+            Some(trigger_value) => value == Some(trigger_value.as_str()),
+            None => true,
+        }
+    }
 }
 
 /// Represents a role in the cluster, e.g. Server / Client and if the property is required
-#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialOrd, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialOrd, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Role {
     pub name: String,
     pub required: bool,
+    #[serde(alias = "no_copy")]
     pub no_copy: Option<bool>,
 }
@@ -1,3 +1,4 @@
+use crate::error::Error;
 use crate::types::{PropertyNameKind, PropertySpec};
 use crate::validation::ValidationResult;
 use semver::Version;
@@ -17,42 +18,117 @@ where
     false
 }
 
+/// Returns the effective value of a property: the user value (provided via any of its property
+/// names) or the recommended or default value matching the version.
+pub(crate) fn effective_value(
+    property: &PropertySpec,
+    version: &Version,
+    kind: &PropertyNameKind,
+    user_config: &HashMap<String, Option<String>>,
+) -> Option<String> {
+    property
+        .all_property_names()
+        .iter()
+        .find_map(|name| user_config.get(name))
+        .cloned()
+        .flatten()
+        .or_else(|| {
+            property
+                .recommended_or_default(version, kind)
+                .and_then(|(_, value)| value)
+        })
+}
+
 /// Checks if the provided property has other properties which need to be expanded / added in
 /// order to work correctly. If any expanded properties are available, they are checked for
-/// a fitting role and version and added to the result if role and version are matching.
+/// a fitting role, version and trigger value and added to the result if all of them match.
+/// Expansions are transitive: the expanded properties are expanded as well (triggered by their
+/// expanded value). A property expanding to itself (directly or via other properties) is
+/// rejected with [`Error::ExpansionCycle`]. Explicit expansion values are validated like all
+/// other values of the expanded property (and checked by the self check of the product config).
 ///
 /// # Arguments
 /// * `property` - the property that may have other properties to expand to
 /// * `version` - the current product version
 /// * `role` - property role provided by the user
 /// * `kind` - property name kind provided by the user
+/// * `value` - the effective value of the property, see [`effective_value`]
 pub(crate) fn expand_properties(
     property: &PropertySpec,
     version: &Version,
     role: &str,
     kind: &PropertyNameKind,
+    value: Option<&str>,
 ) -> ValidationResult<BTreeMap<String, Option<String>>> {
     let mut result = BTreeMap::new();
-    if let Some(expands_to) = &property.expands_to {
-        for to_expand in expands_to {
-            if !to_expand.property.has_role(role) {
-                continue;
-            }
-
-            if !to_expand.property.is_version_supported(version)? {
-                continue;
-            }
-
-            if let Some(name) = to_expand.property.name_from_kind(kind) {
-                if to_expand.value.is_some() {
-                    result.insert(name, to_expand.value.clone());
-                } else if let Some((_, value)) =
-                    to_expand.property.recommended_or_default(version, kind)
-                {
-                    result.insert(name, value);
-                }
-            }
+    let mut path = vec![expansion_name(property, kind)];
+    collect_expansions(property, version, role, kind, value, &mut path, &mut result)?;
+    Ok(result)
+}
+
+/// Adds the expansions of the property to the result and recurses into the expanded
+/// properties. The path contains the names of the properties expanded so far.
+fn collect_expansions(
+    property: &PropertySpec,
+    version: &Version,
+    role: &str,
+    kind: &PropertyNameKind,
+    value: Option<&str>,
+    path: &mut Vec<String>,
+    result: &mut BTreeMap<String, Option<String>>,
+) -> ValidationResult<()> {
+    let Some(expands_to) = &property.expands_to else {
+        return Ok(());
+    };
+
+    for to_expand in expands_to {
+        if !to_expand.property.has_role(role) {
+            continue;
         }
+
+        if !to_expand.is_triggered_by(value) {
+            continue;
+        }
+
+        if !to_expand.property.is_version_supported(version)? {
+            continue;
+        }
+
+        let name = expansion_name(&to_expand.property, kind);
+        if path.contains(&name) {
+            path.push(name);
+            return Err(Error::ExpansionCycle { path: path.clone() });
+        }
+
+        let expanded_value = match &to_expand.value {
+            Some(value) => Some(value.clone()),
+            None => to_expand
+                .property
+                .recommended_or_default(version, kind)
+                .and_then(|(_, value)| value),
+        };
+        if let Some(name) = to_expand.property.name_from_kind(kind) {
+            result.insert(name, expanded_value.clone());
+        }
+
+        path.push(name);
+        collect_expansions(
+            &to_expand.property,
+            version,
+            role,
+            kind,
+            expanded_value.as_deref(),
+            path,
+            result,
+        )?;
+        path.pop();
     }
-    Ok(result)
+    Ok(())
+}
+
+/// Returns the name of the property for the kind, or all its names if it has none for the kind.
+fn expansion_name(property: &PropertySpec, kind: &PropertyNameKind) -> String {
+    property
+        .name_from_kind(kind)
+        .unwrap_or_else(|| property.all_property_names().join(", "))
 }
@@ -1,66 +1,263 @@
 use crate::error::Error;
-use crate::types::{Datatype, PropertySpec, Unit};
+use crate::types::{
+    BoundKind, CharsetClass, Datatype, IntegerSemantic, LengthMode, StackableRegex, StringFormat,
+    Unit, DEFAULT_ARRAY_SEPARATOR,
+};
 use std::fmt::Display;
 use std::str::FromStr;
 
 pub type ValidationResult<T> = Result<T, Error>;
 
-/// Check if property value is in allowed values
+/// Check if property value is in allowed values and return the value in the casing of the
+/// matching allowed value (the property value itself if no allowed values are provided)
 /// # Arguments
 ///
 /// * `property_name` - name of the property
 /// * `property_value` - property value to be validated
 /// * `allowed_values` - vector of allowed values
+/// * `case_insensitive` - compare case-folded values
 ///
 pub(crate) fn check_allowed_values(
     property_name: &str,
     property_value: &str,
     allowed_values: &Option<Vec<String>>,
+    case_insensitive: bool,
+) -> ValidationResult<String> {
+    let allowed_values = match allowed_values {
+        Some(allowed_values) if !allowed_values.is_empty() => allowed_values,
+        _ => return Ok(property_value.to_string()),
+    };
+
+    let folded_value = property_value.to_lowercase();
+    allowed_values
+        .iter()
+        .find(|allowed| {
+            *allowed == property_value
+                || (case_insensitive && allowed.to_lowercase() == folded_value)
+        })
+        .cloned()
+        .ok_or_else(|| Error::PropertyValueNotInAllowedValues {
+            property_name: property_name.to_string(),
+            value: property_value.to_string(),
+            allowed_values: allowed_values.clone(),
+        })
+}
+
+/// Check if property value matches any of the allowed value patterns (if provided). Patterns
+/// that can not be evaluated (e.g. exceeding the backtrack limit) do not match.
+/// # Arguments
+///
+/// * `property_name` - name of the property
+/// * `property_value` - property value to be validated
+/// * `patterns` - the allowed value patterns
+///
+pub(crate) fn check_allowed_value_patterns(
+    property_name: &str,
+    property_value: &str,
+    patterns: &Option<Vec<StackableRegex>>,
 ) -> ValidationResult<()> {
-    if allowed_values.is_some() {
-        let allowed_values = allowed_values.clone().unwrap();
-        if !allowed_values.is_empty() && !allowed_values.contains(&property_value.to_string()) {
-            return Err(Error::PropertyValueNotInAllowedValues {
-                property_name: property_name.to_string(),
-                value: property_value.to_string(),
-                allowed_values,
-            });
-        }
+    let Some(patterns) = patterns else {
+        return Ok(());
+    };
+    if patterns
+        .iter()
+        .any(|pattern| pattern.is_match(property_value).unwrap_or(false))
+    {
+        return Ok(());
     }
-    Ok(())
+    Err(Error::PropertyValueNotMatchingAllowedPatterns {
+        property_name: property_name.to_string(),
+        value: property_value.to_string(),
+        patterns: patterns
+            .iter()
+            .map(|pattern| pattern.expression.clone())
+            .collect(),
+    })
 }
 
-/// Check if property value fits the provided datatype
+/// Check if property value is an allowed value or matches an allowed value pattern. If both
+/// are provided, values must only satisfy one of them. Returns the value in the casing of the
+/// matching allowed value (see [`check_allowed_values`]) or the errors of all failing checks.
 /// # Arguments
 ///
-/// * `config_spec_units` - map with unit name and respective regular expression to evaluate the datatype
 /// * `property_name` - name of the property
 /// * `property_value` - property value to be validated
+/// * `allowed_values` - vector of allowed values
+/// * `patterns` - the allowed value patterns
+/// * `case_insensitive` - compare case-folded values
+///
+pub(crate) fn check_allowed_values_or_patterns(
+    property_name: &str,
+    property_value: &str,
+    allowed_values: &Option<Vec<String>>,
+    patterns: &Option<Vec<StackableRegex>>,
+    case_insensitive: bool,
+) -> Result<String, Vec<Error>> {
+    let allowed = check_allowed_values(
+        property_name,
+        property_value,
+        allowed_values,
+        case_insensitive,
+    );
+    let allowed_by_pattern = check_allowed_value_patterns(property_name, property_value, patterns);
+
+    match (allowed, allowed_by_pattern) {
+        (Ok(value), Ok(())) => Ok(value),
+        (Ok(value), Err(_))
+            if allowed_values
+                .as_ref()
+                .is_some_and(|values| !values.is_empty()) =>
+        {
+            Ok(value)
+        }
+        (Err(_), Ok(())) if patterns.is_some() => Ok(property_value.to_string()),
+        (allowed, allowed_by_pattern) => Err(allowed
+            .err()
+            .into_iter()
+            .chain(allowed_by_pattern.err())
+            .collect()),
+    }
+}
+
+/// Check if property value fits the provided datatype and return the (normalized) value
+/// # Arguments
+///
 /// * `datatype` - property datatype containing min/max bounds, units etc.
+/// * `name` - name of the property
+/// * `value` - property value to be validated
 ///
 pub(crate) fn check_datatype(
-    property: &PropertySpec,
+    datatype: &Datatype,
     name: &str,
     value: &str,
-) -> ValidationResult<()> {
-    match &property.datatype {
+) -> ValidationResult<String> {
+    let bounds = Bounds::of(datatype);
+    match datatype {
         Datatype::Bool => {
-            check_datatype_scalar::<bool>(name, value, &None, &None)?;
+            check_datatype_scalar::<bool>(name, value, &bounds)?;
+        }
+        Datatype::Integer {
+            semantic: Some(semantic),
+            allowed_numbers,
+            ..
+        } => {
+            let normalized = check_datatype_semantic(name, value, &bounds, semantic)?;
+            check_allowed_normalized_numbers(
+                name,
+                value,
+                &normalized,
+                allowed_numbers,
+                |allowed| check_datatype_semantic(name, allowed, &Bounds::default(), semantic),
+            )?;
+            return Ok(normalized);
+        }
+        Datatype::Integer {
+            accepted_units,
+            default_unit,
+            allowed_numbers,
+            ..
+        } if accepted_units.is_some() || default_unit.is_some() => {
+            let normalized =
+                check_datatype_quantity(name, value, &bounds, accepted_units, default_unit)?;
+            check_allowed_normalized_numbers(
+                name,
+                value,
+                &normalized,
+                allowed_numbers,
+                |allowed| {
+                    check_datatype_quantity(
+                        name,
+                        allowed,
+                        &Bounds::default(),
+                        accepted_units,
+                        default_unit,
+                    )
+                },
+            )?;
+            return Ok(normalized);
+        }
+        Datatype::Integer {
+            allowed_numbers, ..
+        } => {
+            let val = check_datatype_scalar::<i64>(name, value, &bounds)?;
+            check_allowed_numbers(name, value, val, allowed_numbers)?;
+        }
+        Datatype::Float {
+            allowed_numbers, ..
+        } => {
+            let val = check_datatype_float(name, value, &bounds)?;
+            check_allowed_numbers(name, value, val, allowed_numbers)?;
         }
-        Datatype::Integer { min, max, .. } => {
-            check_datatype_scalar::<i64>(name, value, min, max)?;
+        Datatype::String {
+            unit,
+            charset,
+            length_mode,
+            allow_newlines,
+            max_lines,
+            format,
+            ..
+        } => {
+            check_datatype_string(
+                name,
+                value,
+                &bounds,
+                unit,
+                charset,
+                length_mode.unwrap_or_default(),
+                LineLimits {
+                    allow_newlines: *allow_newlines,
+                    max_lines: *max_lines,
+                },
+            )?;
+            if let Some(format) = format {
+                check_string_format(name, value, *format)?;
+            }
         }
-        Datatype::Float { min, max, .. } => {
-            check_datatype_scalar::<f64>(name, value, min, max)?;
+        Datatype::Array {
+            unit,
+            accepted_units,
+            default_unit,
+            separator,
+        } => {
+            return check_datatype_array(
+                name,
+                value,
+                unit,
+                accepted_units,
+                default_unit,
+                separator,
+            );
         }
-        Datatype::String { min, max, unit, .. } => {
-            check_datatype_string(name, value, min, max, unit)?;
+        Datatype::Duration { default_unit, .. } => {
+            return check_datatype_duration(name, value, &bounds, default_unit);
         }
-        Datatype::Array { .. } => {
-            // TODO: implement logic for array type
+    }
+    Ok(value.to_string())
+}
+
+/// Returns a warning for values that are valid but should be used with caution, e.g.
+/// privileged ports. Expects the (normalized) value to have passed [`check_datatype`].
+///
+/// # Arguments
+///
+/// * `datatype` - the datatype of the property
+/// * `name` - name of the property
+/// * `value` - the normalized property value
+///
+pub(crate) fn datatype_warning(datatype: &Datatype, name: &str, value: &str) -> Option<Error> {
+    match datatype {
+        Datatype::Integer {
+            semantic: Some(IntegerSemantic::Port),
+            ..
+        } => {
+            let port = IntegerSemantic::Port.parse(value)?;
+            (1..1024).contains(&port).then(|| Error::PrivilegedPort {
+                property_name: name.to_string(),
+                port,
+            })
         }
+        _ => None,
     }
-    Ok(())
 }
 
 /// Returns the provided scalar parameter value of type T (i16, i32, i64, f32, f62-..) if no parsing errors appear
@@ -69,48 +266,477 @@ pub(crate) fn check_datatype(
 ///
 /// * `name` - name of the property
 /// * `value` - the value belonging to the property to be validated
-/// * `min` - minimum value specified
-/// * `max` - maximum value specified
+/// * `bounds` - minimum and maximum value specified
 ///
-fn check_datatype_scalar<T>(
-    name: &str,
-    value: &str,
-    min: &Option<String>,
-    max: &Option<String>,
-) -> ValidationResult<T>
+fn check_datatype_scalar<T>(name: &str, value: &str, bounds: &Bounds) -> ValidationResult<T>
 where
     T: FromStr + std::cmp::PartialOrd + Display + Copy,
 {
     // check if config_value fits datatype
     let val: T = parse::<T>(name, value)?;
-    // check min bound
-    check_bound(name, val, min, min_bound)?;
-    // check max bound
-    check_bound(name, val, max, max_bound)?;
+    // check min and max bound
+    check_bounds(name, val, bounds)?;
+
+    Ok(val)
+}
+
+/// Checks that the parsed numeric value equals one of the allowed numbers (if provided), which
+/// are parsed to the same type. Allowed numbers that can not be parsed never match.
+///
+/// # Arguments
+///
+/// * `name` - name of the property
+/// * `value` - the raw value belonging to the property
+/// * `parsed` - the parsed value
+/// * `allowed_numbers` - the allowed numbers
+///
+fn check_allowed_numbers<T>(
+    name: &str,
+    value: &str,
+    parsed: T,
+    allowed_numbers: &Option<Vec<String>>,
+) -> ValidationResult<()>
+where
+    T: FromStr + PartialEq,
+{
+    let Some(allowed_numbers) = allowed_numbers else {
+        return Ok(());
+    };
+    if allowed_numbers.iter().any(|allowed| {
+        allowed
+            .trim()
+            .parse::<T>()
+            .is_ok_and(|allowed| allowed == parsed)
+    }) {
+        return Ok(());
+    }
+    Err(Error::PropertyValueNotInAllowedValues {
+        property_name: name.to_string(),
+        value: value.to_string(),
+        allowed_values: allowed_numbers.clone(),
+    })
+}
+
+/// Checks that the normalized value (e.g. "2000m" CPUs) is one of the allowed numbers,
+/// normalized the same way (e.g. "2" CPUs), so different notations of a number match.
+///
+/// # Arguments
+///
+/// * `name` - name of the property
+/// * `value` - the value as provided, used for the error
+/// * `normalized` - the normalized value
+/// * `allowed_numbers` - the allowed numbers
+/// * `normalize` - normalizes an allowed number like the value
+///
+fn check_allowed_normalized_numbers<F>(
+    name: &str,
+    value: &str,
+    normalized: &str,
+    allowed_numbers: &Option<Vec<String>>,
+    normalize: F,
+) -> ValidationResult<()>
+where
+    F: Fn(&str) -> ValidationResult<String>,
+{
+    let Some(allowed_numbers) = allowed_numbers else {
+        return Ok(());
+    };
+    if allowed_numbers
+        .iter()
+        .any(|allowed| normalize(allowed.trim()).is_ok_and(|allowed| allowed == normalized))
+    {
+        return Ok(());
+    }
+    Err(Error::PropertyValueNotInAllowedValues {
+        property_name: name.to_string(),
+        value: value.to_string(),
+        allowed_values: allowed_numbers.clone(),
+    })
+}
+
+/// Checks that the string value is valid in the given built-in format.
+///
+/// # Arguments
+///
+/// * `name` - name of the property
+/// * `value` - the value belonging to the property to be validated
+/// * `format` - the format the value must be in
+///
+fn check_string_format(name: &str, value: &str, format: StringFormat) -> ValidationResult<()> {
+    if !format.is_match(value) {
+        return Err(Error::DatatypeFormatNotMatching {
+            property_name: name.to_string(),
+            value: value.to_string(),
+            format,
+        });
+    }
+    Ok(())
+}
+
+/// Returns the provided float value if no parsing errors appear. Non-finite values ("NaN",
+/// "inf", "-inf"), which `f64::from_str` accepts, are rejected because they can not be
+/// compared against the bounds in a meaningful way.
+///
+/// # Arguments
+///
+/// * `name` - name of the property
+/// * `value` - the value belonging to the property to be validated
+/// * `bounds` - minimum and maximum value specified
+///
+fn check_datatype_float(name: &str, value: &str, bounds: &Bounds) -> ValidationResult<f64> {
+    let val: f64 = parse::<f64>(name, value)?;
+    if !val.is_finite() {
+        return Err(Error::DatatypeNotFinite {
+            property_name: name.to_string(),
+            value: value.to_string(),
+        });
+    }
+    check_bounds(name, val, bounds)?;
 
     Ok(val)
 }
 
+/// Returns the normalized value of an integer with a special notation (e.g. CPU quantities)
+/// if no parsing errors appear
+///
+/// # Arguments
+///
+/// * `name` - name of the property
+/// * `value` - the value belonging to the property to be validated
+/// * `bounds` - minimum and maximum normalized value specified
+/// * `semantic` - the notation of the value
+///
+fn check_datatype_semantic(
+    name: &str,
+    value: &str,
+    bounds: &Bounds,
+    semantic: &IntegerSemantic,
+) -> ValidationResult<String> {
+    let quantity = semantic
+        .parse(value)
+        .ok_or_else(|| Error::DatatypeNotMatching {
+            property_name: name.to_string(),
+            value: value.to_string(),
+            datatype: semantic.to_string(),
+        })?;
+    if let Some(range) = semantic.valid_range() {
+        if !range.contains(&quantity) {
+            return Err(Error::DatatypeSemanticOutOfRange {
+                property_name: name.to_string(),
+                value: value.to_string(),
+                semantic: *semantic,
+            });
+        }
+    }
+    check_bounds(name, quantity, bounds)?;
+
+    Ok(semantic.format(quantity))
+}
+
+/// Returns the value of an integer with a unit suffix (e.g. "1Gi") converted into the default
+/// unit (e.g. "1024Mi") if no parsing errors appear. Values without suffix are in the default
+/// unit. The bounds are checked against the converted value.
+///
+/// # Arguments
+///
+/// * `name` - name of the property
+/// * `value` - the value belonging to the property to be validated
+/// * `bounds` - minimum and maximum value specified (in the default unit)
+/// * `accepted_units` - the unit suffixes the value may use (any known unit if not provided)
+/// * `default_unit` - the unit the value is converted into
+///
+fn check_datatype_quantity(
+    name: &str,
+    value: &str,
+    bounds: &Bounds,
+    accepted_units: &Option<Vec<String>>,
+    default_unit: &Option<String>,
+) -> ValidationResult<String> {
+    let default_unit = default_unit.as_deref().unwrap_or_default();
+    let not_matching = || Error::DatatypeNotMatching {
+        property_name: name.to_string(),
+        value: value.to_string(),
+        datatype: std::any::type_name::<i64>().to_string(),
+    };
+
+    let split = value
+        .find(|c: char| !c.is_ascii_digit() && c != '-')
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let number: i128 = number.parse().map_err(|_| not_matching())?;
+    let unit = if unit.is_empty() { default_unit } else { unit };
+
+    let accepted = match accepted_units {
+        Some(accepted_units) => {
+            unit == default_unit || accepted_units.iter().any(|accepted| accepted == unit)
+        }
+        None => true,
+    };
+    let (factor, default_factor) = match unit_factor(unit).zip(unit_factor(default_unit)) {
+        Some(factors) if accepted => factors,
+        _ => {
+            // without accepted units, any known unit suffix is accepted
+            let accepted_units = match accepted_units {
+                Some(accepted_units) => accepted_units.clone(),
+                None => QUANTITY_UNITS
+                    .iter()
+                    .map(|(unit, _)| unit.to_string())
+                    .collect(),
+            };
+            return Err(Error::UnitNotAccepted {
+                property_name: name.to_string(),
+                value: value.to_string(),
+                unit: unit.to_string(),
+                accepted_units,
+            });
+        }
+    };
+
+    let scaled = number.checked_mul(factor).ok_or_else(not_matching)?;
+    if scaled % default_factor != 0 {
+        return Err(Error::UnitConversionNotExact {
+            property_name: name.to_string(),
+            value: value.to_string(),
+            unit: default_unit.to_string(),
+        });
+    }
+    let converted = i64::try_from(scaled / default_factor).map_err(|_| not_matching())?;
+
+    check_bounds(name, converted, bounds)?;
+
+    Ok(format!("{}{}", converted, default_unit))
+}
+
+/// The units of [`Datatype::Duration`] values and their length in milliseconds.
+pub(crate) const DURATION_UNITS: [(&str, i64); 5] = [
+    ("ms", 1),
+    ("s", 1000),
+    ("m", 60 * 1000),
+    ("h", 60 * 60 * 1000),
+    ("d", 24 * 60 * 60 * 1000),
+];
+
+/// The default unit of [`Datatype::Duration`] values.
+pub(crate) const DEFAULT_DURATION_UNIT: &str = "ms";
+
+/// Returns the length of a duration unit (e.g. "m") in milliseconds.
+pub(crate) fn duration_unit_millis(unit: &str) -> Option<i64> {
+    DURATION_UNITS
+        .iter()
+        .find(|(name, _)| *name == unit)
+        .map(|(_, millis)| *millis)
+}
+
+/// The reasons a duration can not be parsed
+#[derive(Debug, PartialEq)]
+pub(crate) enum DurationError {
+    /// The value is not a (compound) duration
+    Invalid,
+    /// The value uses a unit that is not a duration unit
+    UnknownUnit(String),
+}
+
+/// Parses a duration like "1h30m" into milliseconds. A number without unit (e.g. "30") is in
+/// the provided unit.
+///
+/// # Arguments
+///
+/// * `value` - the duration to be parsed
+/// * `default_unit` - the unit of numbers without unit
+///
+pub(crate) fn parse_duration(value: &str, default_unit: &str) -> Result<i64, DurationError> {
+    if !value.is_empty() && value.chars().all(|c| c.is_ascii_digit()) {
+        let millis = duration_unit_millis(default_unit)
+            .ok_or_else(|| DurationError::UnknownUnit(default_unit.to_string()))?;
+        let number: i64 = value.parse().map_err(|_| DurationError::Invalid)?;
+        return number.checked_mul(millis).ok_or(DurationError::Invalid);
+    }
+
+    let mut total: i64 = 0;
+    let mut rest = value;
+    while !rest.is_empty() {
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let (number, remainder) = rest.split_at(digits);
+        let letters = remainder
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(remainder.len());
+        let (unit, remainder) = remainder.split_at(letters);
+        if number.is_empty() || unit.is_empty() {
+            return Err(DurationError::Invalid);
+        }
+
+        let millis = duration_unit_millis(unit)
+            .ok_or_else(|| DurationError::UnknownUnit(unit.to_string()))?;
+        let number: i64 = number.parse().map_err(|_| DurationError::Invalid)?;
+        total = number
+            .checked_mul(millis)
+            .and_then(|millis| total.checked_add(millis))
+            .ok_or(DurationError::Invalid)?;
+        rest = remainder;
+    }
+
+    if value.is_empty() {
+        return Err(DurationError::Invalid);
+    }
+    Ok(total)
+}
+
+/// Returns the duration value (e.g. "1h30m") converted into a number in the default unit
+/// (e.g. "5400000" for milliseconds) if no parsing errors appear. The bounds are durations as
+/// well and are checked against the converted value.
+///
+/// # Arguments
+///
+/// * `name` - name of the property
+/// * `value` - the value belonging to the property to be validated
+/// * `bounds` - minimum and maximum duration specified
+/// * `default_unit` - the unit the value is converted into, defaults to milliseconds
+///
+fn check_datatype_duration(
+    name: &str,
+    value: &str,
+    bounds: &Bounds,
+    default_unit: &Option<String>,
+) -> ValidationResult<String> {
+    let default_unit = default_unit.as_deref().unwrap_or(DEFAULT_DURATION_UNIT);
+    let unknown_unit = |to_parse: &str, unit: &str| Error::UnitNotAccepted {
+        property_name: name.to_string(),
+        value: to_parse.to_string(),
+        unit: unit.to_string(),
+        accepted_units: DURATION_UNITS
+            .iter()
+            .map(|(unit, _)| unit.to_string())
+            .collect(),
+    };
+    let parse = |to_parse: &str| {
+        parse_duration(to_parse, default_unit).map_err(|err| match err {
+            DurationError::Invalid => Error::DatatypeNotMatching {
+                property_name: name.to_string(),
+                value: to_parse.to_string(),
+                datatype: "duration".to_string(),
+            },
+            DurationError::UnknownUnit(unit) => unknown_unit(to_parse, &unit),
+        })
+    };
+
+    let default_millis =
+        duration_unit_millis(default_unit).ok_or_else(|| unknown_unit(value, default_unit))?;
+    let millis = parse(value)?;
+    for (bound, kind, check_out_of_bound) in [
+        (
+            bounds.min,
+            bounds.min_kind,
+            min_bound::<i64> as fn(_, _, _) -> _,
+        ),
+        (bounds.max, bounds.max_kind, max_bound::<i64>),
+    ] {
+        if let Some(bound) = bound {
+            if check_out_of_bound(millis, parse(bound)?, kind) {
+                return Err(Error::PropertyValueOutOfBounds {
+                    property_name: name.to_string(),
+                    received: value.to_string(),
+                    expected: bound.to_string(),
+                    bound_kind: kind,
+                });
+            }
+        }
+    }
+
+    if millis % default_millis != 0 {
+        return Err(Error::UnitConversionNotExact {
+            property_name: name.to_string(),
+            value: value.to_string(),
+            unit: default_unit.to_string(),
+        });
+    }
+
+    Ok((millis / default_millis).to_string())
+}
+
+/// The known quantity unit suffixes and their factor relative to the base unit. Decimal (e.g.
+/// "k", "M") and binary (e.g. "Ki", "Mi") suffixes are supported.
+pub(crate) const QUANTITY_UNITS: [(&str, i128); 14] = [
+    ("B", 1),
+    ("k", 1000),
+    ("K", 1000),
+    ("M", 1000_i128.pow(2)),
+    ("G", 1000_i128.pow(3)),
+    ("T", 1000_i128.pow(4)),
+    ("P", 1000_i128.pow(5)),
+    ("E", 1000_i128.pow(6)),
+    ("Ki", 1 << 10),
+    ("Mi", 1 << 20),
+    ("Gi", 1 << 30),
+    ("Ti", 1 << 40),
+    ("Pi", 1 << 50),
+    ("Ei", 1 << 60),
+];
+
+/// Returns the factor of a quantity unit suffix relative to the base unit (no suffix or "B"),
+/// see [`QUANTITY_UNITS`].
+pub(crate) fn unit_factor(unit: &str) -> Option<i128> {
+    if unit.is_empty() {
+        return Some(1);
+    }
+    QUANTITY_UNITS
+        .iter()
+        .find(|(name, _)| *name == unit)
+        .map(|(_, factor)| *factor)
+}
+
 /// Returns the provided text parameter value of type T if no parsing errors appear
 ///
 /// # Arguments
 ///
 /// * `name` - name of the property
 /// * `value` - the value belonging to the property to be validated
-/// * `min` - minimum value specified
-/// * `max` - maximum value specified
+/// * `bounds` - minimum and maximum length specified
 /// * `unit` - provided unit to get the regular expression to parse the property_value
+/// * `charset` - provided charset class all characters of the value must belong to
+/// * `length_mode` - how the length of the value is measured for the bounds
+/// * `line_limits` - whether line breaks are allowed and the maximum number of lines
 ///
 fn check_datatype_string(
     name: &str,
     value: &str,
-    min: &Option<String>,
-    max: &Option<String>,
+    bounds: &Bounds,
     unit: &Option<Unit>,
+    charset: &Option<CharsetClass>,
+    length_mode: LengthMode,
+    line_limits: LineLimits,
 ) -> ValidationResult<()> {
-    let len: usize = value.len();
-    check_bound::<usize>(name, len, min, min_bound)?;
-    check_bound::<usize>(name, len, max, max_bound)?;
+    let len: usize = length_mode.len(value);
+    check_bounds::<usize>(name, len, bounds)?;
+
+    if line_limits.allow_newlines == Some(false) && value.contains(['\n', '\r']) {
+        return Err(Error::DatatypeNewlineNotAllowed {
+            property_name: name.to_string(),
+            value: value.to_string(),
+        });
+    }
+
+    if let Some(max_lines) = line_limits.max_lines {
+        let lines = value.lines().count();
+        if lines > max_lines {
+            return Err(Error::DatatypeTooManyLines {
+                property_name: name.to_string(),
+                lines,
+                max_lines,
+            });
+        }
+    }
+
+    if let Some(charset) = charset {
+        if !charset.is_match(value) {
+            return Err(Error::DatatypeCharsetNotMatching {
+                property_name: name.to_string(),
+                value: value.to_string(),
+                charset: *charset,
+            });
+        }
+    }
 
     if let Some(unit) = unit {
         match unit.regex.is_match(value) {
@@ -137,18 +763,192 @@ fn check_datatype_string(
     Ok(())
 }
 
+/// Checks every element of the provided array value against the unit regular expression.
+/// The value is either split by the separator (e.g. "a, b, c") or parsed as JSON array of
+/// strings (e.g. `["a","b","c"]`). Separated elements are trimmed, empty values are treated
+/// as empty arrays. If `accepted_units` or `default_unit` are provided, every element is
+/// converted like a [`Datatype::Integer`] quantity (e.g. "1Gi" to "1024Mi") and the value is
+/// returned with the converted elements. Otherwise the value is returned as is.
+///
+/// # Arguments
+///
+/// * `name` - name of the property
+/// * `value` - the value belonging to the property to be validated
+/// * `unit` - provided unit to get the regular expression to check every element
+/// * `accepted_units` - the unit suffixes the elements may use
+/// * `default_unit` - the unit the elements are converted into
+/// * `separator` - provided separator of the elements, defaults to [`DEFAULT_ARRAY_SEPARATOR`]
+///
+fn check_datatype_array(
+    name: &str,
+    value: &str,
+    unit: &Option<Unit>,
+    accepted_units: &Option<Vec<String>>,
+    default_unit: &Option<String>,
+    separator: &Option<String>,
+) -> ValidationResult<String> {
+    let separator = separator.as_deref().unwrap_or(DEFAULT_ARRAY_SEPARATOR);
+    if separator.is_empty() {
+        return Err(Error::EmptyArraySeparator {
+            property_name: name.to_string(),
+        });
+    }
+    let elements = array_elements(name, value, separator)?;
+
+    if let Some(unit) = unit {
+        for (index, element) in elements.iter().enumerate() {
+            let is_match = unit
+                .regex
+                .is_match(element)
+                .map_err(|e| Error::RegexNotEvaluable {
+                    property_name: name.to_string(),
+                    unit: unit.name.to_string(),
+                    regex: unit.regex.to_string(),
+                    value: element.to_string(),
+                    reason: e.to_string(),
+                })?;
+            if !is_match {
+                return Err(Error::ArrayElementInvalid {
+                    property_name: name.to_string(),
+                    index,
+                    value: element.to_string(),
+                });
+            }
+        }
+    }
+
+    if accepted_units.is_none() && default_unit.is_none() {
+        return Ok(value.to_string());
+    }
+
+    let converted = elements
+        .iter()
+        .map(|element| {
+            check_datatype_quantity(
+                name,
+                element,
+                &Bounds::default(),
+                accepted_units,
+                default_unit,
+            )
+        })
+        .collect::<ValidationResult<Vec<_>>>()?;
+
+    if is_json_array(value) {
+        Ok(serde_json::Value::from(converted).to_string())
+    } else {
+        Ok(converted.join(separator))
+    }
+}
+
+/// Returns true if the array value is a JSON style array (e.g. `["a","b"]`).
+fn is_json_array(value: &str) -> bool {
+    let trimmed = value.trim();
+    trimmed.starts_with('[') && trimmed.ends_with(']')
+}
+
+/// Splits the provided array value into its elements
+///
+/// # Arguments
+///
+/// * `name` - name of the property
+/// * `value` - the array value to be split
+/// * `separator` - the (non empty) separator of the elements
+///
+fn array_elements(name: &str, value: &str, separator: &str) -> ValidationResult<Vec<String>> {
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        return Ok(vec![]);
+    }
+
+    if is_json_array(value) {
+        return serde_json::from_str(trimmed).map_err(|_| Error::DatatypeNotMatching {
+            property_name: name.to_string(),
+            value: value.to_string(),
+            datatype: "array".to_string(),
+        });
+    }
+
+    Ok(value
+        .split(separator)
+        .map(|element| element.trim().to_string())
+        .collect())
+}
+
+/// The line break restrictions of a [`Datatype::String`]
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct LineLimits {
+    pub allow_newlines: Option<bool>,
+    pub max_lines: Option<usize>,
+}
+
+/// The min and max bounds of a datatype and whether they are inclusive or exclusive
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct Bounds<'a> {
+    pub min: Option<&'a str>,
+    pub min_kind: BoundKind,
+    pub max: Option<&'a str>,
+    pub max_kind: BoundKind,
+}
+
+impl<'a> Bounds<'a> {
+    /// Returns the bounds of the datatype (no bounds for datatypes without min and max).
+    pub(crate) fn of(datatype: &'a Datatype) -> Self {
+        match datatype {
+            // the exclusive bound shorthands are resolved into min/max when loading, see
+            // `Datatype::resolve_exclusive_bounds`
+            Datatype::Integer {
+                min,
+                max,
+                min_bound_kind,
+                max_bound_kind,
+                ..
+            }
+            | Datatype::Float {
+                min,
+                max,
+                min_bound_kind,
+                max_bound_kind,
+                ..
+            }
+            | Datatype::String {
+                min,
+                max,
+                min_bound_kind,
+                max_bound_kind,
+                ..
+            } => Bounds {
+                min: min.as_deref(),
+                min_kind: min_bound_kind.unwrap_or_default(),
+                max: max.as_deref(),
+                max_kind: max_bound_kind.unwrap_or_default(),
+            },
+            Datatype::Duration { min, max, .. } => Bounds {
+                min: min.as_deref(),
+                max: max.as_deref(),
+                ..Bounds::default()
+            },
+            Datatype::Bool | Datatype::Array { .. } => Bounds::default(),
+        }
+    }
+}
+
 /// Check if value is out of min bound
 ///
 /// # Arguments
 ///
 /// * `val` - value to be validated
-/// * `min` - min border (exclusive)
+/// * `min` - min border
+/// * `kind` - whether the min border is inclusive or exclusive
 ///
-fn min_bound<T>(val: T, min: T) -> bool
+fn min_bound<T>(val: T, min: T, kind: BoundKind) -> bool
 where
     T: FromStr + std::cmp::PartialOrd + Display + Copy,
 {
-    val < min
+    match kind {
+        BoundKind::Inclusive => val < min,
+        BoundKind::Exclusive => val <= min,
+    }
 }
 
 /// Check if value is out of max bound
@@ -156,13 +956,33 @@ where
 /// # Arguments
 ///
 /// * `val` - value to be validated
-/// * `max` - max border (exclusive)
+/// * `max` - max border
+/// * `kind` - whether the max border is inclusive or exclusive
 ///
-fn max_bound<T>(val: T, min: T) -> bool
+fn max_bound<T>(val: T, max: T, kind: BoundKind) -> bool
 where
     T: FromStr + std::cmp::PartialOrd + Display + Copy,
 {
-    val > min
+    match kind {
+        BoundKind::Inclusive => val > max,
+        BoundKind::Exclusive => val >= max,
+    }
+}
+
+/// Check if a value is inside the min and max bounds
+///
+/// # Arguments
+///
+/// * `name` - name of the property
+/// * `value` - value to be validated
+/// * `bounds` - min and max bounds
+///
+fn check_bounds<T>(name: &str, value: T, bounds: &Bounds) -> ValidationResult<T>
+where
+    T: FromStr + std::cmp::PartialOrd + Display + Copy,
+{
+    check_bound(name, value, bounds.min, bounds.min_kind, min_bound)?;
+    check_bound(name, value, bounds.max, bounds.max_kind, max_bound)
 }
 
 /// Check if a value is inside a certain bound
@@ -172,24 +992,27 @@ where
 /// * `property_name` - name of the property
 /// * `value` - value to be validated
 /// * `bound` - upper/lower bound
+/// * `bound_kind` - whether the bound is inclusive or exclusive
 /// * `check_out_of_bound` - the method to check against the bound
 ///
 fn check_bound<T>(
     name: &str,
     value: T,
-    bound: &Option<String>,
-    check_out_of_bound: fn(T, T) -> bool,
+    bound: Option<&str>,
+    bound_kind: BoundKind,
+    check_out_of_bound: fn(T, T, BoundKind) -> bool,
 ) -> ValidationResult<T>
 where
     T: FromStr + std::cmp::PartialOrd + Display + Copy,
 {
     if let Some(bound) = bound {
-        let bound: T = parse::<T>(name, bound.as_str())?;
-        if check_out_of_bound(value, bound) {
+        let bound: T = parse::<T>(name, bound)?;
+        if check_out_of_bound(value, bound, bound_kind) {
             return Err(Error::PropertyValueOutOfBounds {
                 property_name: name.to_string(),
                 received: value.to_string(),
                 expected: bound.to_string(),
+                bound_kind,
             });
         }
     }
@@ -204,7 +1027,7 @@ where
 /// * `property_name` - name of the property
 /// * `to_parse` - value to be parsed into a certain T
 ///
-fn parse<T: FromStr>(name: &str, to_parse: &str) -> Result<T, Error> {
+pub(crate) fn parse<T: FromStr>(name: &str, to_parse: &str) -> Result<T, Error> {
     match to_parse.parse::<T>() {
         Ok(to_parse) => Ok(to_parse),
         Err(_) => Err(Error::DatatypeNotMatching {
@@ -233,7 +1056,7 @@ mod tests {
         "ENV_ALLOWED_VALUES",
         ALLOWED_VALUE_1,
         Some(vec![ALLOWED_VALUE_1.to_string(), ALLOWED_VALUE_2.to_string(), ALLOWED_VALUE_3.to_string()]),
-        Ok(())
+        Ok(ALLOWED_VALUE_1.to_string())
     )]
     #[case(
         "ENV_ALLOWED_VALUES",
@@ -249,10 +1072,546 @@ mod tests {
         #[case] property_name: &str,
         #[case] property_value: &str,
         #[case] allowed_values: Option<Vec<String>>,
-        #[case] expected: Result<(), Error>,
+        #[case] expected: Result<String, Error>,
     ) {
-        let result = check_allowed_values(property_name, property_value, &allowed_values);
+        let result = check_allowed_values(property_name, property_value, &allowed_values, false);
 
         assert_eq!(result, expected)
     }
+
+    #[rstest]
+    #[case::canonical("true", true, Ok("true".to_string()))]
+    #[case::upper_case("TRUE", true, Ok("true".to_string()))]
+    #[case::mixed_case("ReadWrite", true, Ok("readWrite".to_string()))]
+    #[case::case_sensitive("TRUE", false, Err(Error::PropertyValueNotInAllowedValues {
+        property_name: "ENV_MODE".to_string(),
+        value: "TRUE".to_string(),
+        allowed_values: vec!["true".to_string(), "false".to_string(), "readWrite".to_string()]
+    }))]
+    #[case::not_allowed("yes", true, Err(Error::PropertyValueNotInAllowedValues {
+        property_name: "ENV_MODE".to_string(),
+        value: "yes".to_string(),
+        allowed_values: vec!["true".to_string(), "false".to_string(), "readWrite".to_string()]
+    }))]
+    fn test_check_allowed_values_case_insensitive(
+        #[case] property_value: &str,
+        #[case] case_insensitive: bool,
+        #[case] expected: Result<String, Error>,
+    ) {
+        let allowed_values = Some(vec![
+            "true".to_string(),
+            "false".to_string(),
+            "readWrite".to_string(),
+        ]);
+
+        let result = check_allowed_values(
+            "ENV_MODE",
+            property_value,
+            &allowed_values,
+            case_insensitive,
+        );
+
+        assert_eq!(result, expected)
+    }
+
+    #[rstest]
+    #[case("500m", Ok("500m".to_string()))]
+    #[case("2", Ok("2000m".to_string()))]
+    #[case("2.5x", Err(Error::DatatypeNotMatching {
+        property_name: "ENV_CPU".to_string(),
+        value: "2.5x".to_string(),
+        datatype: "cpu".to_string()
+    }))]
+    #[case("50m", Err(Error::PropertyValueOutOfBounds {
+        property_name: "ENV_CPU".to_string(),
+        received: "50".to_string(),
+        expected: "100".to_string(),
+        bound_kind: BoundKind::Inclusive
+    }))]
+    #[case("5", Err(Error::PropertyValueOutOfBounds {
+        property_name: "ENV_CPU".to_string(),
+        received: "5000".to_string(),
+        expected: "4000".to_string(),
+        bound_kind: BoundKind::Inclusive
+    }))]
+    fn test_check_datatype_semantic_cpu(
+        #[case] value: &str,
+        #[case] expected: ValidationResult<String>,
+    ) {
+        let result = check_datatype_semantic(
+            "ENV_CPU",
+            value,
+            &Bounds {
+                min: Some("100"),
+                max: Some("4000"),
+                ..Bounds::default()
+            },
+            &IntegerSemantic::Cpu,
+        );
+
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case("80,443,8080", None, Ok("80,443,8080".to_string()))]
+    #[case("80, 443", None, Ok("80, 443".to_string()))]
+    #[case("80;443", Some(";"), Ok("80;443".to_string()))]
+    #[case("[\"80\", \"443\"]", None, Ok("[\"80\", \"443\"]".to_string()))]
+    #[case("", None, Ok("".to_string()))]
+    #[case("[]", None, Ok("[]".to_string()))]
+    #[case("80443", Some(""), Err(Error::EmptyArraySeparator {
+        property_name: "ENV_ARRAY".to_string()
+    }))]
+    #[case("80,http,8080", None, Err(Error::ArrayElementInvalid {
+        property_name: "ENV_ARRAY".to_string(),
+        index: 1,
+        value: "http".to_string()
+    }))]
+    #[case("[\"80\", \"\"]", None, Err(Error::ArrayElementInvalid {
+        property_name: "ENV_ARRAY".to_string(),
+        index: 1,
+        value: "".to_string()
+    }))]
+    #[case("[80, 443]", None, Err(Error::DatatypeNotMatching {
+        property_name: "ENV_ARRAY".to_string(),
+        value: "[80, 443]".to_string(),
+        datatype: "array".to_string()
+    }))]
+    fn test_check_datatype_array(
+        #[case] value: &str,
+        #[case] separator: Option<&str>,
+        #[case] expected: ValidationResult<String>,
+    ) {
+        let unit: Unit = serde_yaml::from_str("{name: \"port\", regex: \"^[0-9]{1,5}$\"}").unwrap();
+
+        let result = check_datatype_array(
+            "ENV_ARRAY",
+            value,
+            &Some(unit),
+            &None,
+            &None,
+            &separator.map(str::to_string),
+        );
+
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case("1Gi, 512", Ok("1024Mi,512Mi".to_string()))]
+    #[case("[\"1Gi\", \"512Mi\"]", Ok("[\"1024Mi\",\"512Mi\"]".to_string()))]
+    #[case("", Ok("".to_string()))]
+    #[case("1Gi,1G", Err(Error::UnitNotAccepted {
+        property_name: "ENV_MEMORY_LIMITS".to_string(),
+        value: "1G".to_string(),
+        unit: "G".to_string(),
+        accepted_units: vec!["Mi".to_string(), "Gi".to_string()]
+    }))]
+    fn test_check_datatype_array_units(
+        #[case] value: &str,
+        #[case] expected: ValidationResult<String>,
+    ) {
+        let result = check_datatype_array(
+            "ENV_MEMORY_LIMITS",
+            value,
+            &None,
+            &Some(vec!["Mi".to_string(), "Gi".to_string()]),
+            &Some("Mi".to_string()),
+            &None,
+        );
+
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case("2Gi", Ok("2048Mi".to_string()))]
+    #[case("1024Mi", Ok("1024Mi".to_string()))]
+    #[case("512", Ok("512Mi".to_string()))]
+    #[case("1Ti", Err(Error::PropertyValueOutOfBounds {
+        property_name: "ENV_MEMORY".to_string(),
+        received: "1048576".to_string(),
+        expected: "65536".to_string(),
+        bound_kind: BoundKind::Inclusive
+    }))]
+    #[case("1G", Err(Error::UnitNotAccepted {
+        property_name: "ENV_MEMORY".to_string(),
+        value: "1G".to_string(),
+        unit: "G".to_string(),
+        accepted_units: vec!["Ki".to_string(), "Gi".to_string(), "Ti".to_string()]
+    }))]
+    #[case("1Xi", Err(Error::UnitNotAccepted {
+        property_name: "ENV_MEMORY".to_string(),
+        value: "1Xi".to_string(),
+        unit: "Xi".to_string(),
+        accepted_units: vec!["Ki".to_string(), "Gi".to_string(), "Ti".to_string()]
+    }))]
+    #[case("100Ki", Err(Error::UnitConversionNotExact {
+        property_name: "ENV_MEMORY".to_string(),
+        value: "100Ki".to_string(),
+        unit: "Mi".to_string()
+    }))]
+    #[case("Gi", Err(Error::DatatypeNotMatching {
+        property_name: "ENV_MEMORY".to_string(),
+        value: "Gi".to_string(),
+        datatype: "i64".to_string()
+    }))]
+    fn test_check_datatype_quantity(
+        #[case] value: &str,
+        #[case] expected: ValidationResult<String>,
+    ) {
+        let result = check_datatype_quantity(
+            "ENV_MEMORY",
+            value,
+            &Bounds {
+                min: Some("1"),
+                max: Some("65536"),
+                ..Bounds::default()
+            },
+            &Some(vec!["Ki".to_string(), "Gi".to_string(), "Ti".to_string()]),
+            &Some("Mi".to_string()),
+        );
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_check_datatype_quantity_unknown_unit() {
+        let result = check_datatype_quantity(
+            "ENV_MEMORY",
+            "1Xi",
+            &Bounds::default(),
+            &None,
+            &Some("Mi".to_string()),
+        );
+
+        assert_eq!(
+            result,
+            Err(Error::UnitNotAccepted {
+                property_name: "ENV_MEMORY".to_string(),
+                value: "1Xi".to_string(),
+                unit: "Xi".to_string(),
+                accepted_units: QUANTITY_UNITS
+                    .iter()
+                    .map(|(unit, _)| unit.to_string())
+                    .collect()
+            })
+        );
+    }
+
+    #[rstest]
+    #[case(BoundKind::Inclusive, BoundKind::Inclusive, "0", true)]
+    #[case(BoundKind::Inclusive, BoundKind::Inclusive, "10", true)]
+    #[case(BoundKind::Exclusive, BoundKind::Inclusive, "0", false)]
+    #[case(BoundKind::Exclusive, BoundKind::Inclusive, "1", true)]
+    #[case(BoundKind::Inclusive, BoundKind::Exclusive, "10", false)]
+    #[case(BoundKind::Inclusive, BoundKind::Exclusive, "9", true)]
+    fn test_check_bounds(
+        #[case] min_kind: BoundKind,
+        #[case] max_kind: BoundKind,
+        #[case] value: &str,
+        #[case] is_ok: bool,
+    ) {
+        let bounds = Bounds {
+            min: Some("0"),
+            min_kind,
+            max: Some("10"),
+            max_kind,
+        };
+
+        let result = check_datatype_scalar::<i64>("ENV_BOUNDS", value, &bounds);
+
+        assert_eq!(result.is_ok(), is_ok);
+    }
+
+    #[test]
+    fn test_check_bounds_error() {
+        let bounds = Bounds {
+            min: Some("0.5"),
+            min_kind: BoundKind::Exclusive,
+            ..Bounds::default()
+        };
+
+        let result = check_datatype_scalar::<f64>("ENV_BOUNDS", "0.5", &bounds);
+
+        assert_eq!(
+            result,
+            Err(Error::PropertyValueOutOfBounds {
+                property_name: "ENV_BOUNDS".to_string(),
+                received: "0.5".to_string(),
+                expected: "0.5".to_string(),
+                bound_kind: BoundKind::Exclusive,
+            })
+        );
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "[ENV_BOUNDS]: provided value '0.5' violates exclusive min/max bound '0.5'"
+        );
+    }
+
+    #[rstest]
+    #[case(LengthMode::Chars, "abcde", true)]
+    #[case(LengthMode::Chars, "abcdef", false)]
+    #[case(LengthMode::Chars, "café", true)]
+    #[case(LengthMode::Bytes, "café", true)]
+    #[case(LengthMode::Bytes, "cafés", false)]
+    #[case(LengthMode::Chars, "🦀🦀🦀🦀🦀", true)]
+    #[case(LengthMode::Bytes, "🦀🦀", false)]
+    #[case(LengthMode::Chars, "👩‍👩‍👧👩‍👩‍👧", false)]
+    #[case(LengthMode::Graphemes, "👩‍👩‍👧👩‍👩‍👧", true)]
+    #[case(
+        LengthMode::Graphemes,
+        "e\u{301}e\u{301}e\u{301}e\u{301}e\u{301}",
+        true
+    )]
+    #[case(LengthMode::Chars, "e\u{301}e\u{301}e\u{301}", false)]
+    fn test_check_datatype_string_length_mode(
+        #[case] length_mode: LengthMode,
+        #[case] value: &str,
+        #[case] is_ok: bool,
+    ) {
+        let bounds = Bounds {
+            max: Some("5"),
+            ..Bounds::default()
+        };
+
+        let result = check_datatype_string(
+            "ENV_LENGTH",
+            value,
+            &bounds,
+            &None,
+            &None,
+            length_mode,
+            LineLimits::default(),
+        );
+
+        assert_eq!(result.is_ok(), is_ok);
+    }
+
+    #[rstest]
+    #[case(CharsetClass::Alnum, "abcXYZ123", true)]
+    #[case(CharsetClass::Alnum, "abc-123", false)]
+    #[case(CharsetClass::AlnumDash, "abc-123", true)]
+    #[case(CharsetClass::AlnumDash, "abc_123", false)]
+    #[case(CharsetClass::NoWhitespace, "a-b_c/d:1", true)]
+    #[case(CharsetClass::NoWhitespace, "a b", false)]
+    #[case(CharsetClass::NoWhitespace, "a\tb", false)]
+    #[case(CharsetClass::Ascii, "a b!~", true)]
+    #[case(CharsetClass::Ascii, "café", false)]
+    fn test_check_datatype_string_charset(
+        #[case] charset: CharsetClass,
+        #[case] value: &str,
+        #[case] is_ok: bool,
+    ) {
+        let result = check_datatype_string(
+            "ENV_CHARSET",
+            value,
+            &Bounds::default(),
+            &None,
+            &Some(charset),
+            LengthMode::Chars,
+            LineLimits::default(),
+        );
+
+        if is_ok {
+            assert_eq!(result, Ok(()));
+        } else {
+            assert_eq!(
+                result,
+                Err(Error::DatatypeCharsetNotMatching {
+                    property_name: "ENV_CHARSET".to_string(),
+                    value: value.to_string(),
+                    charset,
+                })
+            );
+        }
+    }
+
+    #[rstest]
+    #[case::single_line(Some(false), None, "a b", Ok(()))]
+    #[case::newline_allowed(None, None, "a\nb", Ok(()))]
+    #[case::newline_forbidden(Some(false), None, "a\nb", Err(Error::DatatypeNewlineNotAllowed { property_name: "ENV_LINES".to_string(), value: "a\nb".to_string() }))]
+    #[case::carriage_return_forbidden(Some(false), None, "a\rb", Err(Error::DatatypeNewlineNotAllowed { property_name: "ENV_LINES".to_string(), value: "a\rb".to_string() }))]
+    #[case::max_lines(Some(true), Some(2), "a\nb\n", Ok(()))]
+    #[case::too_many_lines(None, Some(2), "a\nb\nc", Err(Error::DatatypeTooManyLines { property_name: "ENV_LINES".to_string(), lines: 3, max_lines: 2 }))]
+    fn test_check_datatype_string_lines(
+        #[case] allow_newlines: Option<bool>,
+        #[case] max_lines: Option<usize>,
+        #[case] value: &str,
+        #[case] expected: ValidationResult<()>,
+    ) {
+        let result = check_datatype_string(
+            "ENV_LINES",
+            value,
+            &Bounds::default(),
+            &None,
+            &None,
+            LengthMode::Chars,
+            LineLimits {
+                allow_newlines,
+                max_lines,
+            },
+        );
+
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case::valid("50.5", Ok(50.5))]
+    #[case::min_inclusive("0", Ok(0.0))]
+    #[case::max_inclusive("100", Ok(100.0))]
+    #[case::above_max("100.1", Err(Error::PropertyValueOutOfBounds { property_name: "ENV_FLOAT".to_string(), received: "100.1".to_string(), expected: "100".to_string(), bound_kind: BoundKind::Inclusive }))]
+    #[case::nan("NaN", Err(Error::DatatypeNotFinite { property_name: "ENV_FLOAT".to_string(), value: "NaN".to_string() }))]
+    #[case::inf("inf", Err(Error::DatatypeNotFinite { property_name: "ENV_FLOAT".to_string(), value: "inf".to_string() }))]
+    #[case::negative_inf("-inf", Err(Error::DatatypeNotFinite { property_name: "ENV_FLOAT".to_string(), value: "-inf".to_string() }))]
+    fn test_check_datatype_float(#[case] value: &str, #[case] expected: ValidationResult<f64>) {
+        let bounds = Bounds {
+            min: Some("0"),
+            max: Some("100"),
+            ..Bounds::default()
+        };
+
+        assert_eq!(check_datatype_float("ENV_FLOAT", value, &bounds), expected);
+    }
+
+    #[rstest]
+    #[case::ipv4(StringFormat::Ipv4, "192.168.0.1", true)]
+    #[case::ipv4_octet_out_of_range(StringFormat::Ipv4, "999.0.0.1", false)]
+    #[case::ipv4_too_few_octets(StringFormat::Ipv4, "10.0.1", false)]
+    #[case::ipv4_given_ipv6(StringFormat::Ipv4, "::1", false)]
+    #[case::ipv6(StringFormat::Ipv6, "fe80::1", true)]
+    #[case::ipv6_full(StringFormat::Ipv6, "2001:0db8:0000:0000:0000:ff00:0042:8329", true)]
+    #[case::ipv6_invalid_group(StringFormat::Ipv6, "fe80::g1", false)]
+    #[case::ipv6_given_ipv4(StringFormat::Ipv6, "10.0.0.1", false)]
+    #[case::ip_v4(StringFormat::Ip, "10.0.0.1", true)]
+    #[case::ip_v6(StringFormat::Ip, "::1", true)]
+    #[case::ip_hostname(StringFormat::Ip, "localhost", false)]
+    #[case::cidr_v4(StringFormat::Cidr, "10.0.0.0/8", true)]
+    #[case::cidr_v6(StringFormat::Cidr, "fd00::/64", true)]
+    #[case::cidr_v4_prefix_too_long(StringFormat::Cidr, "10.0.0.0/33", false)]
+    #[case::cidr_v6_prefix_too_long(StringFormat::Cidr, "fd00::/129", false)]
+    #[case::cidr_invalid_address(StringFormat::Cidr, "999.0.0.0/8", false)]
+    #[case::cidr_without_prefix(StringFormat::Cidr, "10.0.0.0", false)]
+    #[case::cidr_signed_prefix(StringFormat::Cidr, "10.0.0.0/+8", false)]
+    fn test_check_string_format(
+        #[case] format: StringFormat,
+        #[case] value: &str,
+        #[case] valid: bool,
+    ) {
+        let expected = if valid {
+            Ok(())
+        } else {
+            Err(Error::DatatypeFormatNotMatching {
+                property_name: "ENV_ADDRESS".to_string(),
+                value: value.to_string(),
+                format,
+            })
+        };
+
+        assert_eq!(check_string_format("ENV_ADDRESS", value, format), expected);
+    }
+
+    #[rstest]
+    #[case::allowed("3", Ok(()))]
+    #[case::leading_zero("03", Ok(()))]
+    #[case::not_allowed("4", Err(Error::PropertyValueNotInAllowedValues { property_name: "replication".to_string(), value: "4".to_string(), allowed_values: vec!["1".to_string(), "3".to_string(), "5".to_string()] }))]
+    fn test_check_allowed_numbers(#[case] value: &str, #[case] expected: ValidationResult<()>) {
+        let allowed_numbers = Some(vec!["1".to_string(), "3".to_string(), "5".to_string()]);
+        let parsed = parse::<i64>("replication", value).unwrap();
+
+        assert_eq!(
+            check_allowed_numbers("replication", value, parsed, &allowed_numbers),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case::integer(Datatype::Integer { min: None, max: None, min_bound_kind: None, max_bound_kind: None, exclusive_min: None, exclusive_max: None, unit: None, accepted_units: None, default_unit: None, semantic: None, allowed_numbers: Some(vec!["3".to_string()]) }, "03", true)]
+    #[case::integer_not_allowed(Datatype::Integer { min: None, max: None, min_bound_kind: None, max_bound_kind: None, exclusive_min: None, exclusive_max: None, unit: None, accepted_units: None, default_unit: None, semantic: None, allowed_numbers: Some(vec!["3".to_string()]) }, "4", false)]
+    #[case::float(Datatype::Float { min: None, max: None, min_bound_kind: None, max_bound_kind: None, exclusive_min: None, exclusive_max: None, unit: None, accepted_units: None, default_unit: None, allowed_numbers: Some(vec!["0.5".to_string()]) }, "0.50", true)]
+    #[case::port(Datatype::Integer { min: None, max: None, min_bound_kind: None, max_bound_kind: None, exclusive_min: None, exclusive_max: None, unit: None, accepted_units: None, default_unit: None, semantic: Some(IntegerSemantic::Port), allowed_numbers: Some(vec!["8080".to_string(), "8443".to_string()]) }, "8443", true)]
+    #[case::port_not_allowed(Datatype::Integer { min: None, max: None, min_bound_kind: None, max_bound_kind: None, exclusive_min: None, exclusive_max: None, unit: None, accepted_units: None, default_unit: None, semantic: Some(IntegerSemantic::Port), allowed_numbers: Some(vec!["8080".to_string(), "8443".to_string()]) }, "9090", false)]
+    #[case::cpu(Datatype::Integer { min: None, max: None, min_bound_kind: None, max_bound_kind: None, exclusive_min: None, exclusive_max: None, unit: None, accepted_units: None, default_unit: None, semantic: Some(IntegerSemantic::Cpu), allowed_numbers: Some(vec!["2".to_string()]) }, "2000m", true)]
+    #[case::units(Datatype::Integer { min: None, max: None, min_bound_kind: None, max_bound_kind: None, exclusive_min: None, exclusive_max: None, unit: None, accepted_units: Some(vec!["k".to_string(), "M".to_string()]), default_unit: None, semantic: None, allowed_numbers: Some(vec!["1M".to_string()]) }, "1000k", true)]
+    #[case::units_not_allowed(Datatype::Integer { min: None, max: None, min_bound_kind: None, max_bound_kind: None, exclusive_min: None, exclusive_max: None, unit: None, accepted_units: Some(vec!["k".to_string(), "M".to_string()]), default_unit: None, semantic: None, allowed_numbers: Some(vec!["1M".to_string()]) }, "2M", false)]
+    fn test_check_datatype_allowed_numbers(
+        #[case] datatype: Datatype,
+        #[case] value: &str,
+        #[case] valid: bool,
+    ) {
+        let result = check_datatype(&datatype, "replication", value);
+
+        assert_eq!(result.is_ok(), valid);
+    }
+
+    #[rstest]
+    #[case::zero("0", Err(Error::DatatypeSemanticOutOfRange { property_name: "ENV_PORT".to_string(), value: "0".to_string(), semantic: IntegerSemantic::Port }), None)]
+    #[case::privileged("80", Ok("80".to_string()), Some(Error::PrivilegedPort { property_name: "ENV_PORT".to_string(), port: 80 }))]
+    #[case::unprivileged("1024", Ok("1024".to_string()), None)]
+    #[case::max("65535", Ok("65535".to_string()), None)]
+    #[case::too_large("65536", Err(Error::DatatypeSemanticOutOfRange { property_name: "ENV_PORT".to_string(), value: "65536".to_string(), semantic: IntegerSemantic::Port }), None)]
+    fn test_check_datatype_semantic_port(
+        #[case] value: &str,
+        #[case] expected: ValidationResult<String>,
+        #[case] expected_warning: Option<Error>,
+    ) {
+        let datatype = Datatype::Integer {
+            min: None,
+            // the valid port range applies regardless of the bounds
+            max: Some("100000".to_string()),
+            min_bound_kind: None,
+            max_bound_kind: None,
+            exclusive_min: None,
+            exclusive_max: None,
+            unit: None,
+            accepted_units: None,
+            default_unit: None,
+            semantic: Some(IntegerSemantic::Port),
+            allowed_numbers: None,
+        };
+
+        assert_eq!(check_datatype(&datatype, "ENV_PORT", value), expected);
+        assert_eq!(
+            datatype_warning(&datatype, "ENV_PORT", value),
+            expected_warning
+        );
+    }
+
+    fn duration_units() -> Vec<String> {
+        DURATION_UNITS
+            .iter()
+            .map(|(unit, _)| unit.to_string())
+            .collect()
+    }
+
+    #[rstest]
+    #[case::millis("2500ms", None, Ok("2500".to_string()))]
+    #[case::compound("1h30m", None, Ok("5400000".to_string()))]
+    #[case::compound_all("1d2h3m4s5ms", None, Ok("93784005".to_string()))]
+    #[case::without_unit("3000", None, Ok("3000".to_string()))]
+    #[case::default_unit("1h30m", Some("m"), Ok("90".to_string()))]
+    #[case::default_unit_without_unit("30", Some("s"), Ok("30".to_string()))]
+    #[case::min("1s", None, Ok("1000".to_string()))]
+    #[case::max("2d", None, Ok("172800000".to_string()))]
+    #[case::below_min("999ms", None, Err(Error::PropertyValueOutOfBounds { property_name: "ENV_TIMEOUT".to_string(), received: "999ms".to_string(), expected: "1s".to_string(), bound_kind: BoundKind::Inclusive }))]
+    #[case::above_max("2d1ms", None, Err(Error::PropertyValueOutOfBounds { property_name: "ENV_TIMEOUT".to_string(), received: "2d1ms".to_string(), expected: "2d".to_string(), bound_kind: BoundKind::Inclusive }))]
+    #[case::not_exact("1500ms", Some("s"), Err(Error::UnitConversionNotExact { property_name: "ENV_TIMEOUT".to_string(), value: "1500ms".to_string(), unit: "s".to_string() }))]
+    #[case::unknown_unit("5w", None, Err(Error::UnitNotAccepted { property_name: "ENV_TIMEOUT".to_string(), value: "5w".to_string(), unit: "w".to_string(), accepted_units: duration_units() }))]
+    #[case::unknown_compound_unit("1h5sec", None, Err(Error::UnitNotAccepted { property_name: "ENV_TIMEOUT".to_string(), value: "1h5sec".to_string(), unit: "sec".to_string(), accepted_units: duration_units() }))]
+    #[case::unknown_default_unit("30s", Some("w"), Err(Error::UnitNotAccepted { property_name: "ENV_TIMEOUT".to_string(), value: "30s".to_string(), unit: "w".to_string(), accepted_units: duration_units() }))]
+    #[case::missing_number("h", None, Err(Error::DatatypeNotMatching { property_name: "ENV_TIMEOUT".to_string(), value: "h".to_string(), datatype: "duration".to_string() }))]
+    #[case::missing_unit("1h30", None, Err(Error::DatatypeNotMatching { property_name: "ENV_TIMEOUT".to_string(), value: "1h30".to_string(), datatype: "duration".to_string() }))]
+    #[case::negative("-5s", None, Err(Error::DatatypeNotMatching { property_name: "ENV_TIMEOUT".to_string(), value: "-5s".to_string(), datatype: "duration".to_string() }))]
+    #[case::empty("", None, Err(Error::DatatypeNotMatching { property_name: "ENV_TIMEOUT".to_string(), value: "".to_string(), datatype: "duration".to_string() }))]
+    fn test_check_datatype_duration(
+        #[case] value: &str,
+        #[case] default_unit: Option<&str>,
+        #[case] expected: ValidationResult<String>,
+    ) {
+        let datatype = Datatype::Duration {
+            min: Some("1s".to_string()),
+            max: Some("2d".to_string()),
+            default_unit: default_unit.map(str::to_string),
+        };
+
+        assert_eq!(check_datatype(&datatype, "ENV_TIMEOUT", value), expected);
+    }
 }
@@ -1,5 +1,7 @@
+use crate::types::REPEATED_VALUE_SEPARATOR;
 use java_properties::{PropertiesError, PropertiesWriter};
 use snafu::{ResultExt, Snafu};
+use std::collections::{BTreeMap, BTreeSet};
 use std::io::Write;
 use xml::escape::escape_str_attribute;
 
@@ -12,6 +14,58 @@ pub enum PropertiesWriterError {
     FromUtf8Error { source: std::string::FromUtf8Error },
 }
 
+#[derive(Debug, PartialEq, Snafu)]
+pub enum EnvWriterError {
+    #[snafu(display("invalid environment variable name '{key}': only ASCII letters, digits and underscores are allowed and the name must not start with a digit"))]
+    InvalidEnvKey { key: String },
+}
+
+#[derive(Debug, PartialEq, Snafu)]
+pub enum TomlWriterError {
+    #[snafu(display("TOML key '{key}' is used as value and as table at the same time"))]
+    ConflictingTomlKey { key: String },
+}
+
+#[derive(Debug, Snafu)]
+pub enum JsonWriterError {
+    #[snafu(display("JSON key '{key}' is used as value and as object at the same time"))]
+    ConflictingJsonKey { key: String },
+
+    #[snafu(display("failed to serialize JSON object"))]
+    JsonSerialization { source: serde_json::Error },
+}
+
+/// The output formats supported by [`crate::ProductConfigManager::render`].
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum OutputFormat {
+    /// See [`to_java_properties_string`].
+    JavaProperties,
+    /// See [`to_hadoop_xml`].
+    HadoopXml,
+    /// See [`to_env_file`], without `export`.
+    Env,
+    /// See [`to_json`], without type inference.
+    Json,
+    /// A flat YAML mapping of property names to string values.
+    Yaml,
+    /// See [`to_toml`].
+    Toml,
+}
+
+impl std::fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            OutputFormat::JavaProperties => "Java properties",
+            OutputFormat::HadoopXml => "Hadoop XML",
+            OutputFormat::Env => "env",
+            OutputFormat::Json => "JSON",
+            OutputFormat::Yaml => "YAML",
+            OutputFormat::Toml => "TOML",
+        };
+        f.write_str(name)
+    }
+}
+
 /// Creates a common Java properties file string in the format:
 /// property_1=value_1\n
 /// property_2=value_2\n
@@ -51,12 +105,418 @@ where
     Ok(())
 }
 
+/// Creates a dotenv file string in the format:
+/// PROPERTY_1=value_1\n
+/// PROPERTY_2="value with spaces"\n
+///
+/// Contrary to shell scripts, no `export` is emitted. Values that contain whitespace or special
+/// characters (e.g. `#`, quotes or `$`) are double quoted and backslashes, double quotes,
+/// dollar signs and newlines are escaped. Properties with a `None` value are skipped.
+/// Keys must only contain ASCII letters, digits and underscores and must not start with a digit.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::BTreeMap;
+/// use product_config::writer::to_dotenv_string;
+/// let mut map = BTreeMap::new();
+/// map.insert("FOO".to_string(), Some("bar".to_string()));
+/// map.insert("GREETING".to_string(), Some("hello world".to_string()));
+/// map.insert("NONE".to_string(), None);
+///
+/// let result = to_dotenv_string(map.iter()).unwrap();
+/// assert_eq!(result, "FOO=bar\nGREETING=\"hello world\"\n");
+/// ```
+pub fn to_dotenv_string<'a, T>(properties: T) -> Result<String, EnvWriterError>
+where
+    T: Iterator<Item = (&'a String, &'a Option<String>)>,
+{
+    let mut result = String::new();
+    for (k, v) in properties {
+        let value = match v {
+            Some(value) => value,
+            None => continue,
+        };
+        if !is_valid_env_key(k) {
+            return InvalidEnvKeySnafu { key: k }.fail();
+        }
+        result.push_str(&format!("{}={}\n", k, quote_dotenv_value(value)));
+    }
+    Ok(result)
+}
+
+/// Creates a shell environment file string (e.g. `env.sh`) in the format:
+/// KEY_1='value_1'\n
+/// export KEY_2='value_2'\n (if `export` is set)
+///
+/// All values are single quoted, single quotes within values are escaped as `'\''`.
+/// Properties with a `None` value are skipped. Keys must be valid shell identifiers, i.e. only
+/// contain ASCII letters, digits and underscores and not start with a digit.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::BTreeMap;
+/// use product_config::writer::to_env_file;
+/// let mut map = BTreeMap::new();
+/// map.insert("FOO".to_string(), Some("bar".to_string()));
+/// map.insert("QUOTE".to_string(), Some("it's".to_string()));
+/// map.insert("NONE".to_string(), None);
+///
+/// let result = to_env_file(map.iter(), true).unwrap();
+/// assert_eq!(result, "export FOO='bar'\nexport QUOTE='it'\\''s'\n");
+/// ```
+pub fn to_env_file<'a, T>(properties: T, export: bool) -> Result<String, EnvWriterError>
+where
+    T: Iterator<Item = (&'a String, &'a Option<String>)>,
+{
+    let mut result = String::new();
+    for (k, v) in properties {
+        let value = match v {
+            Some(value) => value,
+            None => continue,
+        };
+        if !is_valid_env_key(k) {
+            return InvalidEnvKeySnafu { key: k }.fail();
+        }
+        if export {
+            result.push_str("export ");
+        }
+        result.push_str(&format!("{}='{}'\n", k, value.replace('\'', "'\\''")));
+    }
+    Ok(result)
+}
+
+/// Creates a canonical string of the properties for human review (e.g. diffs of generated
+/// configs in pull requests), which is stable across runs. Not meant to be read by products.
+/// Properties are sorted by name and written one per line in the format:
+/// property_1 = value_1\n
+/// property_2 = <unset>\n (for `None` values)
+///
+/// Backslashes and line breaks within values are escaped (`\\`, `\n`, `\r`) to keep one
+/// property per line.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use product_config::writer::to_canonical_review_string;
+/// let mut map = HashMap::new();
+/// map.insert("foo".to_string(), Some("bar".to_string()));
+/// map.insert("baz".to_string(), None);
+///
+/// let result = to_canonical_review_string(map.iter());
+/// assert_eq!(result, "baz = <unset>\nfoo = bar\n");
+/// ```
+pub fn to_canonical_review_string<'a, T>(properties: T) -> String
+where
+    T: Iterator<Item = (&'a String, &'a Option<String>)>,
+{
+    let mut properties: Vec<_> = properties.collect();
+    properties.sort_by_key(|(k, _)| *k);
+
+    let mut result = String::new();
+    for (k, v) in properties {
+        let value = match v {
+            Some(value) => value
+                .replace('\\', "\\\\")
+                .replace('\n', "\\n")
+                .replace('\r', "\\r"),
+            None => "<unset>".to_string(),
+        };
+        result.push_str(&format!("{} = {}\n", k, value));
+    }
+    result
+}
+
+/// Returns true if the key only consists of ASCII letters, digits and underscores and does not
+/// start with a digit.
+fn is_valid_env_key(key: &str) -> bool {
+    let mut chars = key.chars();
+    match chars.next() {
+        Some(first) if first.is_ascii_alphabetic() || first == '_' => {
+            chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        }
+        _ => false,
+    }
+}
+
+/// Double quotes and escapes the value if it contains whitespace or special characters.
+fn quote_dotenv_value(value: &str) -> String {
+    let needs_quotes = value
+        .chars()
+        .any(|c| !(c.is_ascii_alphanumeric() || "_-.,:/@+%".contains(c)));
+    if !needs_quotes {
+        return value.to_string();
+    }
+
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '\\' => quoted.push_str("\\\\"),
+            '"' => quoted.push_str("\\\""),
+            '$' => quoted.push_str("\\$"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Creates a TOML document string. Dotted keys (e.g. `server.port`) are nested into TOML tables,
+/// all values are written as (escaped) basic strings:
+/// root = "value"\n
+/// \n
+/// [server]\n
+/// port = "8080"\n
+///
+/// Properties with a `None` value are skipped, `Some("")` is written as empty string. Key parts
+/// that are not valid bare keys are quoted. A key must not be used as value and as table at the
+/// same time (e.g. `server` and `server.port`).
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::BTreeMap;
+/// use product_config::writer::to_toml;
+/// let mut map = BTreeMap::new();
+/// map.insert("name".to_string(), Some("demo".to_string()));
+/// map.insert("server.port".to_string(), Some("8080".to_string()));
+/// map.insert("none".to_string(), None);
+///
+/// let result = to_toml(map.iter()).unwrap();
+/// assert_eq!(result, "name = \"demo\"\n\n[server]\nport = \"8080\"\n");
+/// ```
+pub fn to_toml<'a, T>(properties: T) -> Result<String, TomlWriterError>
+where
+    T: Iterator<Item = (&'a String, &'a Option<String>)>,
+{
+    let mut root = TomlTable::default();
+    for (k, v) in properties {
+        let value = match v {
+            Some(value) => value,
+            None => continue,
+        };
+
+        let mut parts: Vec<&str> = k.split('.').collect();
+        // split always returns at least one part
+        let last = parts.pop().unwrap_or_default();
+        let mut table = &mut root;
+        for part in parts {
+            if table.values.contains_key(part) {
+                return ConflictingTomlKeySnafu { key: k }.fail();
+            }
+            table = table.tables.entry(part.to_string()).or_default();
+        }
+        if table.tables.contains_key(last) {
+            return ConflictingTomlKeySnafu { key: k }.fail();
+        }
+        table.values.insert(last.to_string(), value.clone());
+    }
+
+    let mut result = String::new();
+    write_toml_table(&mut result, &root, &[]);
+    Ok(result)
+}
+
+/// A TOML table with (string) values and nested tables.
+#[derive(Default)]
+struct TomlTable {
+    values: BTreeMap<String, String>,
+    tables: BTreeMap<String, TomlTable>,
+}
+
+/// Writes the values of the table (with a header if not the root table) followed by all nested
+/// tables. Tables without values are implicitly defined by their nested tables.
+fn write_toml_table(result: &mut String, table: &TomlTable, path: &[String]) {
+    if !table.values.is_empty() {
+        if !path.is_empty() {
+            if !result.is_empty() {
+                result.push('\n');
+            }
+            result.push_str(&format!("[{}]\n", path.join(".")));
+        }
+        for (key, value) in &table.values {
+            result.push_str(&format!(
+                "{} = {}\n",
+                quote_toml_key(key),
+                quote_toml_string(value)
+            ));
+        }
+    }
+
+    for (key, nested) in &table.tables {
+        let mut nested_path = path.to_vec();
+        nested_path.push(quote_toml_key(key));
+        write_toml_table(result, nested, &nested_path);
+    }
+}
+
+/// Returns the key as is if it is a valid bare key, otherwise as quoted key.
+fn quote_toml_key(key: &str) -> String {
+    let is_bare = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if is_bare {
+        key.to_string()
+    } else {
+        quote_toml_string(key)
+    }
+}
+
+/// Double quotes and escapes the value as TOML basic string.
+fn quote_toml_string(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '\\' => quoted.push_str("\\\\"),
+            '"' => quoted.push_str("\\\""),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04X}", c as u32)),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Creates a (pretty printed) JSON object string. Dotted keys (e.g. `a.b.c`) are expanded into
+/// nested objects. Properties with a `None` value are skipped, `Some("")` is written as empty
+/// string. A key must not be used as value and as object at the same time (e.g. `a` and `a.b`).
+///
+/// All values are written as JSON strings unless `infer_types` is set, in which case `true`
+/// and `false` are written as booleans and numbers are written unquoted (if unquoting does not
+/// change them, e.g. `1.50` stays a string).
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::BTreeMap;
+/// use product_config::writer::to_json;
+/// let mut map = BTreeMap::new();
+/// map.insert("server.port".to_string(), Some("8080".to_string()));
+/// map.insert("none".to_string(), None);
+///
+/// let result = to_json(map.iter(), true).unwrap();
+/// assert_eq!(result, "{\n  \"server\": {\n    \"port\": 8080\n  }\n}");
+/// ```
+pub fn to_json<'a, T>(properties: T, infer_types: bool) -> Result<String, JsonWriterError>
+where
+    T: Iterator<Item = (&'a String, &'a Option<String>)>,
+{
+    let mut root = serde_json::Map::new();
+    for (k, v) in properties {
+        let value = match v {
+            Some(value) => value,
+            None => continue,
+        };
+
+        let mut parts: Vec<&str> = k.split('.').collect();
+        // split always returns at least one part
+        let last = parts.pop().unwrap_or_default();
+        let mut object = &mut root;
+        for part in parts {
+            let nested = object
+                .entry(part)
+                .or_insert_with(|| serde_json::Value::Object(serde_json::Map::new()));
+            object = match nested {
+                serde_json::Value::Object(nested) => nested,
+                _ => return ConflictingJsonKeySnafu { key: k }.fail(),
+            };
+        }
+        if object.contains_key(last) {
+            return ConflictingJsonKeySnafu { key: k }.fail();
+        }
+        let json_value = if infer_types {
+            infer_json_value(value)
+        } else {
+            serde_json::Value::String(value.clone())
+        };
+        object.insert(last.to_string(), json_value);
+    }
+
+    serde_json::to_string_pretty(&root).context(JsonSerializationSnafu)
+}
+
+/// Converts booleans and numbers into the respective JSON values, everything else is kept as
+/// string. Numbers are only converted if the JSON number is written exactly like the value
+/// (e.g. not `007`, `1.50`, `1e3` or integers exceeding `i64`), so no value is changed.
+fn infer_json_value(value: &str) -> serde_json::Value {
+    match value {
+        "true" => return serde_json::Value::Bool(true),
+        "false" => return serde_json::Value::Bool(false),
+        _ => {}
+    }
+    if let Ok(integer) = value.parse::<i64>() {
+        if integer.to_string() == value {
+            return serde_json::Value::from(integer);
+        }
+    } else if let Some(float) = value
+        .parse::<f64>()
+        .ok()
+        .and_then(serde_json::Number::from_f64)
+    {
+        if float.to_string() == value {
+            return serde_json::Value::Number(float);
+        }
+    }
+    serde_json::Value::String(value.to_string())
+}
+
+/// Splits the values of repeatable properties into one entry per element, so that writers emit
+/// the property once per element. Values of properties not contained in `repeatable` are kept
+/// as is. See [`crate::types::PropertySpec::repeatable`] and
+/// [`crate::ProductConfigManager::repeatable_properties`].
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::{BTreeMap, BTreeSet};
+/// use product_config::writer::{split_repeated_values, to_java_properties_string};
+/// let mut map = BTreeMap::new();
+/// map.insert("listener".to_string(), Some("a\nb".to_string()));
+/// let repeatable = BTreeSet::from(["listener".to_string()]);
+///
+/// let split = split_repeated_values(map.iter(), &repeatable);
+/// let result = to_java_properties_string(split.iter().map(|(k, v)| (k, v))).unwrap();
+/// assert_eq!(result, "listener=a\nlistener=b\n");
+/// ```
+pub fn split_repeated_values<'a, T>(
+    properties: T,
+    repeatable: &BTreeSet<String>,
+) -> Vec<(String, Option<String>)>
+where
+    T: Iterator<Item = (&'a String, &'a Option<String>)>,
+{
+    let mut result = Vec::new();
+    for (k, v) in properties {
+        match v {
+            Some(value) if repeatable.contains(k) => {
+                for element in value.split(REPEATED_VALUE_SEPARATOR) {
+                    result.push((k.clone(), Some(element.to_string())));
+                }
+            }
+            _ => result.push((k.clone(), v.clone())),
+        }
+    }
+    result
+}
+
 /// Converts properties into a Hadoop configuration XML snippet.
 ///
 /// This is missing the wrapping `<configuration>...</configuration>` elements so it can be composed.
 /// Elements for which the value is `None` will be ignored.
 /// Empty values (i.e. `""`) will be returned though.
 /// This method will properly escape all keys and values to be safe to use in XML.
+/// Properties are sorted by name so the output is deterministic regardless of the iteration
+/// order of the input (e.g. a `HashMap`), which requires collecting the properties first.
 ///
 /// # Examples
 ///
@@ -73,6 +533,9 @@ pub fn to_hadoop_xml_snippet<'a, T>(properties: T) -> String
 where
     T: Iterator<Item = (&'a String, &'a Option<String>)>,
 {
+    let mut properties: Vec<_> = properties.collect();
+    properties.sort_by_key(|(k, _)| *k);
+
     let mut result = String::new();
     for (k, v) in properties {
         let escaped_value = match v {
@@ -88,6 +551,62 @@ where
     result
 }
 
+/// Same as [`to_hadoop_xml_snippet`] but additionally emits the optional `<description>` and
+/// `<final>` elements of a property, e.g. to mark critical properties as final so they cannot be
+/// overridden and to carry inline docs. The iterator items are (name, value, description, final).
+/// The description is escaped like keys and values and properties are sorted by name as well.
+///
+/// # Examples
+///
+/// ```
+/// use product_config::writer::to_hadoop_xml_snippet_with_meta;
+/// let name = "dfs.replication".to_string();
+/// let value = Some("3".to_string());
+/// let description = "Default block replication.".to_string();
+/// let properties = vec![(&name, &value, Some(&description), Some(true))];
+///
+/// let result = to_hadoop_xml_snippet_with_meta(properties.into_iter());
+/// assert!(result.contains("<final>true</final>"));
+/// ```
+pub fn to_hadoop_xml_snippet_with_meta<'a, T>(properties: T) -> String
+where
+    T: Iterator<
+        Item = (
+            &'a String,
+            &'a Option<String>,
+            Option<&'a String>,
+            Option<bool>,
+        ),
+    >,
+{
+    let mut properties: Vec<_> = properties.collect();
+    properties.sort_by_key(|(k, ..)| *k);
+
+    let mut result = String::new();
+    for (k, v, description, is_final) in properties {
+        let escaped_value = match v {
+            Some(value) => escape_str_attribute(value),
+            None => continue,
+        };
+        let escaped_key = escape_str_attribute(k);
+        result.push_str(&format!(
+            "  <property>\n    <name>{}</name>\n    <value>{}</value>\n",
+            escaped_key, escaped_value
+        ));
+        if let Some(description) = description {
+            result.push_str(&format!(
+                "    <description>{}</description>\n",
+                escape_str_attribute(description)
+            ));
+        }
+        if let Some(is_final) = is_final {
+            result.push_str(&format!("    <final>{}</final>\n", is_final));
+        }
+        result.push_str("  </property>\n");
+    }
+    result
+}
+
 /// Converts properties into a Hadoop configuration XML.
 ///
 /// This includes the wrapping `<configuration>...</configuration>` elements so it cannot be composed.
@@ -95,6 +614,7 @@ where
 /// Elements for which the value is `None` will be ignored.
 /// Empty values (i.e. `""`) will be returned though.
 /// This method will properly escape all keys and values to be safe to use in XML.
+/// Properties are sorted by name, see [`crate::writer::to_hadoop_xml_snippet`].
 ///
 /// # Examples
 ///
@@ -127,10 +647,13 @@ pub fn wrap_hadoop_xml_snippet<T: AsRef<str>>(snippet: T) -> String {
 #[cfg(test)]
 mod tests {
     use crate::writer::{
-        to_hadoop_xml, to_hadoop_xml_snippet, to_java_properties_string, write_java_properties,
-        PropertiesWriterError,
+        split_repeated_values, to_canonical_review_string, to_dotenv_string, to_env_file,
+        to_hadoop_xml, to_hadoop_xml_snippet, to_hadoop_xml_snippet_with_meta,
+        to_java_properties_string, to_json, to_toml, write_java_properties, EnvWriterError,
+        JsonWriterError, PropertiesWriterError, TomlWriterError,
     };
-    use std::collections::{BTreeMap, HashMap};
+    use rstest::rstest;
+    use std::collections::{BTreeMap, BTreeSet, HashMap};
 
     const PROPERTY_1: &str = "property";
     const PROPERTY_2: &str = "property2";
@@ -204,6 +727,227 @@ mod tests {
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn test_split_repeated_values() -> Result<(), PropertiesWriterError> {
+        let mut map = BTreeMap::new();
+        map.insert("listener".to_string(), Some("first\nsecond".to_string()));
+        map.insert("normal".to_string(), Some("first\nsecond".to_string()));
+        map.insert("none".to_string(), None);
+        let repeatable = BTreeSet::from(["listener".to_string(), "none".to_string()]);
+
+        let split = split_repeated_values(map.iter(), &repeatable);
+        let result = to_java_properties_string(split.iter().map(|(k, v)| (k, v)))?;
+
+        assert_eq!(
+            result,
+            "listener=first\nlistener=second\nnone=\nnormal=first\\nsecond\n"
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("plain", "KEY=plain\n")]
+    #[case("", "KEY=\n")]
+    #[case("file:///tmp/a-b_c.txt", "KEY=file:///tmp/a-b_c.txt\n")]
+    #[case("with spaces", "KEY=\"with spaces\"\n")]
+    #[case("not#a comment", "KEY=\"not#a comment\"\n")]
+    #[case("first\nsecond", "KEY=\"first\\nsecond\"\n")]
+    #[case("say \"hi\"", "KEY=\"say \\\"hi\\\"\"\n")]
+    #[case("$HOME\\bin", "KEY=\"\\$HOME\\\\bin\"\n")]
+    fn test_dotenv_quoting(#[case] value: &str, #[case] expected: &str) {
+        let map = BTreeMap::from([("KEY".to_string(), Some(value.to_string()))]);
+
+        assert_eq!(to_dotenv_string(map.iter()), Ok(expected.to_string()));
+    }
+
+    #[test]
+    fn test_dotenv_skip_none_and_invalid_keys() {
+        let mut map = BTreeMap::new();
+        map.insert("SOME".to_string(), Some("value".to_string()));
+        map.insert("NONE".to_string(), None);
+        map.insert("_UNDERSCORE_1".to_string(), Some("1".to_string()));
+        assert_eq!(
+            to_dotenv_string(map.iter()),
+            Ok("SOME=value\n_UNDERSCORE_1=1\n".to_string())
+        );
+
+        for key in ["1_DIGIT", "WITH-DASH", "WITH SPACE", ""] {
+            let map = BTreeMap::from([(key.to_string(), Some("value".to_string()))]);
+            assert_eq!(
+                to_dotenv_string(map.iter()),
+                Err(EnvWriterError::InvalidEnvKey {
+                    key: key.to_string()
+                })
+            );
+        }
+    }
+
+    #[rstest]
+    #[case("plain", false, "KEY='plain'\n")]
+    #[case("", false, "KEY=''\n")]
+    #[case("with spaces $HOME", false, "KEY='with spaces $HOME'\n")]
+    #[case("it's", false, "KEY='it'\\''s'\n")]
+    #[case("first\nsecond", true, "export KEY='first\nsecond'\n")]
+    fn test_env_file_quoting(#[case] value: &str, #[case] export: bool, #[case] expected: &str) {
+        let map = BTreeMap::from([("KEY".to_string(), Some(value.to_string()))]);
+
+        assert_eq!(to_env_file(map.iter(), export), Ok(expected.to_string()));
+    }
+
+    #[test]
+    fn test_env_file_skip_none_and_invalid_keys() {
+        let mut map = BTreeMap::new();
+        map.insert("SOME".to_string(), Some("value".to_string()));
+        map.insert("NONE".to_string(), None);
+        assert_eq!(
+            to_env_file(map.iter(), true),
+            Ok("export SOME='value'\n".to_string())
+        );
+
+        for key in ["1_DIGIT", "WITH-DASH", "$(rm -rf)", ""] {
+            let map = BTreeMap::from([(key.to_string(), Some("value".to_string()))]);
+            assert_eq!(
+                to_env_file(map.iter(), false),
+                Err(EnvWriterError::InvalidEnvKey {
+                    key: key.to_string()
+                })
+            );
+        }
+    }
+
+    #[rstest]
+    #[case("plain", "KEY = \"plain\"\n")]
+    #[case("", "KEY = \"\"\n")]
+    #[case("say \"hi\"", "KEY = \"say \\\"hi\\\"\"\n")]
+    #[case("C:\\temp", "KEY = \"C:\\\\temp\"\n")]
+    #[case("first\nsecond", "KEY = \"first\\nsecond\"\n")]
+    #[case("bell\u{7}", "KEY = \"bell\\u0007\"\n")]
+    fn test_toml_escaping(#[case] value: &str, #[case] expected: &str) {
+        let map = BTreeMap::from([("KEY".to_string(), Some(value.to_string()))]);
+
+        assert_eq!(to_toml(map.iter()), Ok(expected.to_string()));
+    }
+
+    #[test]
+    fn test_toml_tables() {
+        let mut map = BTreeMap::new();
+        map.insert("name".to_string(), Some("demo".to_string()));
+        map.insert("none".to_string(), None);
+        map.insert("server.port".to_string(), Some("8080".to_string()));
+        map.insert("server.tls.enabled".to_string(), Some("true".to_string()));
+        map.insert("log.file name".to_string(), Some("".to_string()));
+
+        assert_eq!(
+            to_toml(map.iter()),
+            Ok("name = \"demo\"\n\n[log]\n\"file name\" = \"\"\n\n[server]\nport = \"8080\"\n\n[server.tls]\nenabled = \"true\"\n".to_string())
+        );
+    }
+
+    #[test]
+    fn test_toml_conflicting_keys() {
+        let mut map = BTreeMap::new();
+        map.insert("server".to_string(), Some("value".to_string()));
+        map.insert("server.port".to_string(), Some("8080".to_string()));
+
+        assert_eq!(
+            to_toml(map.iter()),
+            Err(TomlWriterError::ConflictingTomlKey {
+                key: "server.port".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_xml_sorted() {
+        let entries: Vec<(String, Option<String>)> = (0..20)
+            .map(|i| (format!("property.{i:02}"), Some(i.to_string())))
+            .collect();
+        let hash_map: HashMap<_, _> = entries.iter().cloned().collect();
+        let btree_map: BTreeMap<_, _> = entries.iter().cloned().collect();
+
+        let expected = to_hadoop_xml(btree_map.iter());
+        assert_eq!(to_hadoop_xml(hash_map.iter()), expected);
+        assert_eq!(
+            to_hadoop_xml(entries.iter().rev().map(|(k, v)| (k, v))),
+            expected
+        );
+        assert!(expected.find("property.00") < expected.find("property.19"));
+    }
+
+    #[test]
+    fn test_xml_snippet_with_meta() {
+        let (name_1, name_2, name_3) =
+            ("final".to_string(), "plain".to_string(), "none".to_string());
+        let (value, none) = (Some("a&b".to_string()), None);
+        let description = "Use <b> & \"c\"".to_string();
+
+        let properties = vec![
+            (&name_1, &value, Some(&description), Some(true)),
+            (&name_2, &value, None, None),
+            (&name_3, &none, Some(&description), Some(false)),
+        ];
+
+        assert_eq!(
+            to_hadoop_xml_snippet_with_meta(properties.into_iter()),
+            "  <property>\n    <name>final</name>\n    <value>a&amp;b</value>\n    <description>Use &lt;b&gt; &amp; &quot;c&quot;</description>\n    <final>true</final>\n  </property>\n  <property>\n    <name>plain</name>\n    <value>a&amp;b</value>\n  </property>\n"
+        );
+    }
+
+    #[rstest]
+    #[case::strings(false, r#"{"a":{"b":{"c":"1","d":"true"},"e":""},"f":"say \"hi\""}"#)]
+    #[case::inferred(true, r#"{"a":{"b":{"c":1,"d":true},"e":""},"f":"say \"hi\""}"#)]
+    fn test_json_nesting(#[case] infer_types: bool, #[case] expected: &str) {
+        let mut map = BTreeMap::new();
+        map.insert("a.b.c".to_string(), Some("1".to_string()));
+        map.insert("a.b.d".to_string(), Some("true".to_string()));
+        map.insert("a.e".to_string(), Some("".to_string()));
+        map.insert("f".to_string(), Some("say \"hi\"".to_string()));
+        map.insert("none".to_string(), None);
+
+        let result = to_json(map.iter(), infer_types).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
+        let expected: serde_json::Value = serde_json::from_str(expected).unwrap();
+        assert_eq!(parsed, expected);
+    }
+
+    #[rstest]
+    #[case("1.5", serde_json::json!(1.5))]
+    #[case("-3", serde_json::json!(-3))]
+    #[case("007", serde_json::json!("007"))]
+    #[case("True", serde_json::json!("True"))]
+    #[case("NaN", serde_json::json!("NaN"))]
+    #[case("1.50", serde_json::json!("1.50"))]
+    #[case("1e3", serde_json::json!("1e3"))]
+    #[case(".5", serde_json::json!(".5"))]
+    #[case("-0.25", serde_json::json!(-0.25))]
+    #[case("99999999999999999999", serde_json::json!("99999999999999999999"))]
+    #[case("", serde_json::json!(""))]
+    fn test_json_type_inference(#[case] value: &str, #[case] expected: serde_json::Value) {
+        let map = BTreeMap::from([("key".to_string(), Some(value.to_string()))]);
+
+        let result = to_json(map.iter(), true).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
+        assert_eq!(parsed, serde_json::json!({ "key": expected }));
+    }
+
+    #[rstest]
+    #[case("a", "a.b", "a.b")]
+    #[case("a.b.c", "a.b", "a.b.c")]
+    fn test_json_conflicting_keys(
+        #[case] first: &str,
+        #[case] second: &str,
+        #[case] conflicting: &str,
+    ) {
+        let mut map = BTreeMap::new();
+        map.insert(first.to_string(), Some("1".to_string()));
+        map.insert(second.to_string(), Some("2".to_string()));
+
+        let result = to_json(map.iter(), false);
+        assert!(
+            matches!(result, Err(JsonWriterError::ConflictingJsonKey { ref key }) if key == conflicting)
+        );
+    }
+
     #[test]
     fn test_xml_escape_attributes() {
         // TODO: make rstest and check pc data as well
@@ -241,4 +985,30 @@ mod tests {
 
         result
     }
+
+    #[test]
+    fn test_canonical_review_string() {
+        let entries: Vec<(String, Option<String>)> = vec![
+            ("zookeeper.connect".to_string(), Some("zk:2181".to_string())),
+            ("log.dirs".to_string(), None),
+            ("empty".to_string(), Some("".to_string())),
+            (
+                "listeners".to_string(),
+                Some("A://:9092\nB://:9093".to_string()),
+            ),
+            ("path".to_string(), Some("C:\\data".to_string())),
+        ];
+        let hash_map: HashMap<_, _> = entries.iter().cloned().collect();
+
+        let expected = "empty = \n\
+                        listeners = A://:9092\\nB://:9093\n\
+                        log.dirs = <unset>\n\
+                        path = C:\\\\data\n\
+                        zookeeper.connect = zk:2181\n";
+        assert_eq!(
+            to_canonical_review_string(entries.iter().map(|(k, v)| (k, v))),
+            expected
+        );
+        assert_eq!(to_canonical_review_string(hash_map.iter()), expected);
+    }
 }